@@ -0,0 +1,349 @@
+//! Baseline files that grandfather existing violations.
+//!
+//! A baseline records each violation by file, rule and a fingerprint built
+//! from the diagnostic code, the enclosing member and the normalized text of
+//! the violating line. Line and column numbers are left out, so violations
+//! survive unrelated edits that shift them around. Checking against a
+//! baseline hides every violation that matches an entry, and reports entries
+//! that no longer match anything so that the baseline can shrink.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use lintal_java_cst::CstNode;
+use serde_json::{Value, json};
+
+use crate::hash::stable_hash128;
+use crate::output::Message;
+
+/// Version of the baseline file format.
+const FORMAT_VERSION: u64 = 1;
+
+/// Identifies a violation independently of its line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Fingerprint {
+    /// Dotted name of the enclosing type and member, e.g. `Outer.Inner.run`.
+    member: String,
+    hash: String,
+}
+
+impl Fingerprint {
+    /// Fingerprint `message` in `source`, whose syntax tree is `root`.
+    pub(crate) fn new(message: &Message, source: &str, root: &CstNode) -> Self {
+        let member = enclosing_member(root, source, usize::from(message.range.start()));
+        let line = source
+            .lines()
+            .nth(message.location.line.saturating_sub(1))
+            .unwrap_or_default();
+        let normalized = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        let hash = stable_hash128(
+            format!("{}\0{}\0{member}\0{normalized}", message.rule, message.code).as_bytes(),
+        );
+        Self {
+            member,
+            hash: format!("{hash:032x}"),
+        }
+    }
+}
+
+/// A violation recorded in a baseline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BaselineEntry {
+    pub(crate) rule: String,
+    pub(crate) member: String,
+    pub(crate) message: String,
+    hash: String,
+}
+
+/// Violations recorded in a baseline file, keyed by path relative to the
+/// baseline's directory.
+#[derive(Debug)]
+pub(crate) struct Baseline {
+    root: PathBuf,
+    files: BTreeMap<String, Vec<BaselineEntry>>,
+    /// Entries of checked files that matched no violation.
+    fixed: Vec<(String, BaselineEntry)>,
+}
+
+impl Baseline {
+    /// An empty baseline to be written to `path`.
+    pub(crate) fn new(path: &Path) -> Self {
+        let root = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        Self {
+            root: root.canonicalize().unwrap_or_else(|_| root.to_path_buf()),
+            files: BTreeMap::new(),
+            fixed: Vec::new(),
+        }
+    }
+
+    /// Read the baseline file at `path`.
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline {}", path.display()))?;
+        let value: Value = serde_json::from_str(&text)
+            .with_context(|| format!("Failed to parse baseline {}", path.display()))?;
+        let version = value.get("version").and_then(Value::as_u64);
+        if version != Some(FORMAT_VERSION) {
+            anyhow::bail!(
+                "Unsupported baseline version in {}, rewrite it with --write-baseline",
+                path.display()
+            );
+        }
+
+        let mut baseline = Self::new(path);
+        let files = value.get("files").and_then(Value::as_object);
+        for (file, entries) in files.into_iter().flatten() {
+            let entries = entries
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| {
+                    let field = |name: &str| Some(entry.get(name)?.as_str()?.to_string());
+                    Some(BaselineEntry {
+                        rule: field("rule")?,
+                        member: field("member").unwrap_or_default(),
+                        message: field("message").unwrap_or_default(),
+                        hash: field("fingerprint")?,
+                    })
+                })
+                .collect();
+            baseline.files.insert(file.clone(), entries);
+        }
+        Ok(baseline)
+    }
+
+    /// Write the baseline to `path`.
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        let files: serde_json::Map<String, Value> = self
+            .files
+            .iter()
+            .map(|(file, entries)| {
+                let entries: Vec<Value> = entries
+                    .iter()
+                    .map(|entry| {
+                        json!({
+                            "rule": entry.rule,
+                            "member": entry.member,
+                            "message": entry.message,
+                            "fingerprint": entry.hash,
+                        })
+                    })
+                    .collect();
+                (file.clone(), Value::Array(entries))
+            })
+            .collect();
+        let value = json!({ "version": FORMAT_VERSION, "files": files });
+        let mut text = serde_json::to_string_pretty(&value)?;
+        text.push('\n');
+        std::fs::write(path, text)
+            .with_context(|| format!("Failed to write baseline {}", path.display()))
+    }
+
+    /// Number of recorded violations.
+    pub(crate) fn len(&self) -> usize {
+        self.files.values().map(Vec::len).sum()
+    }
+
+    /// Record the violations of the file at `path`.
+    pub(crate) fn add(&mut self, path: &Path, messages: &[Message], fingerprints: &[Fingerprint]) {
+        if messages.is_empty() {
+            return;
+        }
+        let entries = self.files.entry(self.key(path)).or_default();
+        for (message, fingerprint) in messages.iter().zip(fingerprints) {
+            entries.push(BaselineEntry {
+                rule: message.rule.clone(),
+                member: fingerprint.member.clone(),
+                message: message.message.clone(),
+                hash: fingerprint.hash.clone(),
+            });
+        }
+    }
+
+    /// Remove the violations of `path` that are in the baseline from
+    /// `messages`. Each entry hides at most one violation; entries left
+    /// over are recorded as fixed.
+    pub(crate) fn filter(
+        &mut self,
+        path: &Path,
+        messages: &mut Vec<Message>,
+        fingerprints: &[Fingerprint],
+    ) {
+        let key = self.key(path);
+        let Some(entries) = self.files.remove(&key) else {
+            return;
+        };
+
+        let mut remaining: HashMap<&str, Vec<&BaselineEntry>> = HashMap::new();
+        for entry in &entries {
+            remaining.entry(&entry.hash).or_default().push(entry);
+        }
+        let mut fingerprints = fingerprints.iter();
+        messages.retain(|_| {
+            let Some(fingerprint) = fingerprints.next() else {
+                return true;
+            };
+            remaining
+                .get_mut(fingerprint.hash.as_str())
+                .and_then(Vec::pop)
+                .is_none()
+        });
+
+        let fixed = remaining.into_values().flatten().cloned();
+        self.fixed.extend(fixed.map(|entry| (key.clone(), entry)));
+    }
+
+    /// Entries that no longer match a violation: those left over in checked
+    /// files, and those of files that were deleted.
+    pub(crate) fn into_fixed(self) -> Vec<(String, BaselineEntry)> {
+        let mut fixed = self.fixed;
+        for (file, entries) in self.files {
+            if !self.root.join(&file).exists() {
+                fixed.extend(entries.into_iter().map(|entry| (file.clone(), entry)));
+            }
+        }
+        fixed.sort_by(|a, b| (&a.0, &a.1.rule, &a.1.member).cmp(&(&b.0, &b.1.rule, &b.1.member)));
+        fixed
+    }
+
+    /// The key of `path`: relative to the baseline's directory if it is
+    /// inside it and absolute otherwise, with `/` separators.
+    fn key(&self, path: &Path) -> String {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let relative = canonical.strip_prefix(&self.root).unwrap_or(&canonical);
+        relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// Dotted names of the declarations enclosing `offset`, outermost first.
+fn enclosing_member(root: &CstNode, source: &str, offset: usize) -> String {
+    let mut names = Vec::new();
+    let mut node = root
+        .inner()
+        .descendant_for_byte_range(offset, offset)
+        .map(|node| CstNode::new(node, source));
+    while let Some(current) = node {
+        if let Some(name) = declaration_name(&current) {
+            names.push(name);
+        }
+        node = current.parent();
+    }
+    names.reverse();
+    names.join(".")
+}
+
+/// The declared name, if `node` is a type or member declaration.
+fn declaration_name<'a>(node: &CstNode<'a>) -> Option<&'a str> {
+    match node.kind() {
+        "class_declaration"
+        | "interface_declaration"
+        | "enum_declaration"
+        | "record_declaration"
+        | "annotation_type_declaration"
+        | "method_declaration"
+        | "constructor_declaration"
+        | "compact_constructor_declaration"
+        | "annotation_type_element_declaration"
+        | "enum_constant" => node.child_by_field_name("name").map(|name| name.text()),
+        "field_declaration" | "constant_declaration" => node
+            .child_by_field_name("declarator")?
+            .child_by_field_name("name")
+            .map(|name| name.text()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lintal_java_parser::JavaParser;
+
+    use super::*;
+
+    const SOURCE: &str = "class Outer {\n    int a=1;\n    class Inner {\n        void run() {\n            int b=2;\n        }\n    }\n}\n";
+
+    /// A violation at the first occurrence of `needle` in `source`.
+    fn message(source: &str, needle: &str) -> Message {
+        let offset = source.find(needle).unwrap();
+        let line = source[..offset].matches('\n').count() + 1;
+        let start = u32::try_from(offset).unwrap();
+        Message::test("WhitespaceAround", "Missing whitespace before `=`")
+            .code("MissingWhitespaceBefore")
+            .at(start, start + 1, line, 1, 2)
+    }
+
+    fn fingerprint(source: &str, message: &Message) -> Fingerprint {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let root = CstNode::new(result.tree.root_node(), source);
+        Fingerprint::new(message, source, &root)
+    }
+
+    #[test]
+    fn test_fingerprint_member() {
+        let field = message(SOURCE, "=1");
+        assert_eq!(fingerprint(SOURCE, &field).member, "Outer.a");
+        let local = message(SOURCE, "=2");
+        assert_eq!(fingerprint(SOURCE, &local).member, "Outer.Inner.run");
+    }
+
+    #[test]
+    fn test_fingerprint_value() {
+        // Baselines are committed, so fingerprints must never change
+        let field = fingerprint(SOURCE, &message(SOURCE, "=1"));
+        assert_eq!(field.hash, "2e1db74887502a03c7e77d7552b812de");
+    }
+
+    #[test]
+    fn test_fingerprint_tolerates_line_shifts() {
+        let before = fingerprint(SOURCE, &message(SOURCE, "=2"));
+
+        // Shifted down and reindented, the violation keeps its fingerprint
+        let shifted = SOURCE.replace("        void run() {\n", "\n        void run() {\n  ");
+        assert_eq!(fingerprint(&shifted, &message(&shifted, "=2")), before);
+
+        // Moved to another method, it does not
+        let moved = SOURCE.replace("void run()", "void walk()");
+        assert_ne!(fingerprint(&moved, &message(&moved, "=2")), before);
+    }
+
+    #[test]
+    fn test_filter_and_fixed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Outer.java");
+        std::fs::write(&path, SOURCE).unwrap();
+        let baseline_path = dir.path().join("baseline.json");
+
+        let messages = vec![message(SOURCE, "=1"), message(SOURCE, "=2")];
+        let fingerprints: Vec<_> = messages.iter().map(|m| fingerprint(SOURCE, m)).collect();
+        let mut baseline = Baseline::new(&baseline_path);
+        baseline.add(&path, &messages, &fingerprints);
+        baseline.save(&baseline_path).unwrap();
+
+        // The field violation was fixed and a new one appeared
+        let source = SOURCE
+            .replace("int a=1", "int a = 1")
+            .replace("int b=2;", "int b=2;\n            int c=3;");
+        let messages = vec![message(&source, "=2"), message(&source, "=3")];
+        let fingerprints: Vec<_> = messages.iter().map(|m| fingerprint(&source, m)).collect();
+
+        let mut baseline = Baseline::load(&baseline_path).unwrap();
+        assert_eq!(baseline.len(), 2);
+        let mut remaining = messages.clone();
+        baseline.filter(&path, &mut remaining, &fingerprints);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].range, messages[1].range);
+
+        let fixed = baseline.into_fixed();
+        assert_eq!(fixed.len(), 1);
+        assert_eq!(fixed[0].0, "Outer.java");
+        assert_eq!(fixed[0].1.member, "Outer.a");
+    }
+}
@@ -0,0 +1,348 @@
+//! On-disk cache of `lintal check` results.
+//!
+//! Results are stored per file under [`CACHE_DIR`], keyed by a hash of the
+//! file's contents. Each combination of lintal version and effective
+//! configuration gets its own cache file, so changing either starts from an
+//! empty cache instead of returning stale results.
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use lintal_diagnostics::{Applicability, Edit, Severity};
+use lintal_text_size::{Ranged, TextRange, TextSize};
+use serde_json::{Map, Value, json};
+
+use crate::output::{Location, Message, MessageFix, applicability_name};
+
+/// Directory, relative to the working directory, holding the cache.
+pub(crate) const CACHE_DIR: &str = ".lintal_cache";
+
+/// A 128-bit hash, stable for a given lintal build only. Use
+/// [`stable_hash128`](crate::hash::stable_hash128) for values compared
+/// across builds.
+pub(crate) fn hash128(bytes: &[u8]) -> u128 {
+    let half = |seed: u64| {
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        bytes.hash(&mut hasher);
+        hasher.finish()
+    };
+    (u128::from(half(0)) << 64) | u128::from(half(1))
+}
+
+/// Cached results for one file.
+struct CacheEntry {
+    content_hash: u128,
+    messages: Vec<Message>,
+}
+
+/// Check results for one lintal version and configuration.
+pub(crate) struct Cache {
+    /// The JSON file backing this cache.
+    file: PathBuf,
+    /// Entries read from `file`.
+    entries: HashMap<String, CacheEntry>,
+    /// Entries added during this run.
+    updated: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl Cache {
+    /// Open the cache for `config_fingerprint` under `dir`.
+    ///
+    /// A missing or unreadable cache file yields an empty cache.
+    pub(crate) fn open(dir: &Path, config_fingerprint: &str) -> Self {
+        let key = hash128(config_fingerprint.as_bytes());
+        let file = dir
+            .join(env!("CARGO_PKG_VERSION"))
+            .join(format!("{key:032x}.json"));
+        let entries = std::fs::read_to_string(&file)
+            .ok()
+            .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+            .map(|value| entries_from_json(&value))
+            .unwrap_or_default();
+        Self {
+            file,
+            entries,
+            updated: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The cached messages for `path`, if its contents are unchanged.
+    pub(crate) fn get(&self, path: &str, content_hash: u128) -> Option<Vec<Message>> {
+        self.entries
+            .get(path)
+            .filter(|entry| entry.content_hash == content_hash)
+            .map(|entry| entry.messages.clone())
+    }
+
+    /// Record the messages for `path`.
+    pub(crate) fn insert(&self, path: &str, content_hash: u128, messages: &[Message]) {
+        let entry = CacheEntry {
+            content_hash,
+            messages: messages.to_vec(),
+        };
+        self.updated
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(path.to_string(), entry);
+    }
+
+    /// Write the cache back to disk if anything changed.
+    pub(crate) fn save(self) -> std::io::Result<()> {
+        let updated = self
+            .updated
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if updated.is_empty() {
+            return Ok(());
+        }
+
+        let mut entries = self.entries;
+        entries.extend(updated);
+
+        let Some(version_dir) = self.file.parent() else {
+            return Ok(());
+        };
+        std::fs::create_dir_all(version_dir)?;
+        if let Some(cache_dir) = version_dir.parent() {
+            let gitignore = cache_dir.join(".gitignore");
+            if !gitignore.exists() {
+                std::fs::write(gitignore, "# Automatically created by lintal.\n*\n")?;
+            }
+        }
+
+        // Write to a temporary file first so that concurrent runs never see
+        // a partially written cache.
+        let tmp = self
+            .file
+            .with_extension(format!("{}.tmp", std::process::id()));
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(&tmp)?);
+        serde_json::to_writer(&mut writer, &entries_to_json(&entries))?;
+        writer.flush()?;
+        drop(writer);
+        std::fs::rename(&tmp, &self.file)
+    }
+}
+
+/// Remove the cache directory under `root`, returning true if it existed.
+pub(crate) fn clean(root: &Path) -> std::io::Result<bool> {
+    let dir = root.join(CACHE_DIR);
+    if !dir.exists() {
+        return Ok(false);
+    }
+    std::fs::remove_dir_all(dir)?;
+    Ok(true)
+}
+
+fn entries_to_json(entries: &HashMap<String, CacheEntry>) -> Value {
+    let files: Map<String, Value> = entries
+        .iter()
+        .map(|(path, entry)| {
+            let messages: Vec<Value> = entry.messages.iter().map(message_to_json).collect();
+            (
+                path.clone(),
+                json!({
+                    "hash": format!("{:032x}", entry.content_hash),
+                    "messages": messages,
+                }),
+            )
+        })
+        .collect();
+    json!({ "files": files })
+}
+
+fn entries_from_json(value: &Value) -> HashMap<String, CacheEntry> {
+    let Some(files) = value.get("files").and_then(Value::as_object) else {
+        return HashMap::new();
+    };
+    files
+        .iter()
+        .filter_map(|(path, entry)| {
+            let content_hash = u128::from_str_radix(entry.get("hash")?.as_str()?, 16).ok()?;
+            let messages = entry
+                .get("messages")?
+                .as_array()?
+                .iter()
+                .map(message_from_json)
+                .collect::<Option<Vec<_>>>()?;
+            Some((
+                path.clone(),
+                CacheEntry {
+                    content_hash,
+                    messages,
+                },
+            ))
+        })
+        .collect()
+}
+
+fn message_to_json(message: &Message) -> Value {
+    json!([
+        message.rule,
+        message.code,
+        message.message,
+        u32::from(message.range.start()),
+        u32::from(message.range.end()),
+        message.location.line,
+        message.location.column,
+        message.end_location.line,
+        message.end_location.column,
+        message
+            .fix
+            .as_ref()
+            .map(|fix| applicability_name(fix.applicability)),
+        message.severity.as_str(),
+        message.suggestion,
+        message.fix.as_ref().map(|fix| {
+            fix.edits
+                .iter()
+                .map(|edit| {
+                    json!([
+                        u32::from(edit.start()),
+                        u32::from(edit.end()),
+                        edit.content(),
+                    ])
+                })
+                .collect::<Vec<_>>()
+        }),
+    ])
+}
+
+fn message_from_json(value: &Value) -> Option<Message> {
+    let fields = value.as_array()?;
+    let text = |i: usize| fields.get(i)?.as_str().map(str::to_string);
+    let number = |i: usize| fields.get(i)?.as_u64();
+    let offset = |i: usize| {
+        number(i)
+            .and_then(|n| u32::try_from(n).ok())
+            .map(TextSize::new)
+    };
+    let position = |i: usize| number(i).and_then(|n| usize::try_from(n).ok());
+
+    let fix = match fields.get(9)? {
+        Value::Null => None,
+        Value::String(name) => Some(MessageFix {
+            applicability: match name.as_str() {
+                "safe" => Applicability::Safe,
+                "unsafe" => Applicability::Unsafe,
+                "display-only" => Applicability::DisplayOnly,
+                _ => return None,
+            },
+            edits: fields
+                .get(12)?
+                .as_array()?
+                .iter()
+                .map(edit_from_json)
+                .collect::<Option<Vec<_>>>()?,
+        }),
+        _ => return None,
+    };
+    let suggestion = match fields.get(11)? {
+        Value::Null => None,
+        Value::String(suggestion) => Some(suggestion.clone()),
+        _ => return None,
+    };
+
+    Some(Message {
+        rule: text(0)?,
+        code: text(1)?,
+        message: text(2)?,
+        range: TextRange::new(offset(3)?, offset(4)?),
+        location: Location {
+            line: position(5)?,
+            column: position(6)?,
+        },
+        end_location: Location {
+            line: position(7)?,
+            column: position(8)?,
+        },
+        suggestion,
+        fix,
+        severity: text(10)?.parse::<Severity>().ok()?,
+    })
+}
+
+fn edit_from_json(value: &Value) -> Option<Edit> {
+    let fields = value.as_array()?;
+    let offset = |i: usize| {
+        let n = u32::try_from(fields.get(i)?.as_u64()?).ok()?;
+        Some(TextSize::new(n))
+    };
+    let range = TextRange::new(offset(0)?, offset(1)?);
+    Some(match fields.get(2)? {
+        Value::Null => Edit::range_deletion(range),
+        Value::String(content) => Edit::range_replacement(content.clone(), range),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(fix: Option<MessageFix>) -> Message {
+        Message {
+            fix,
+            ..Message::test("WhitespaceAround", "Missing whitespace before `=`")
+                .at(20, 21, 2, 19, 20)
+                .suggestion("Insert whitespace before `=`")
+                .severity(Severity::Warning)
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let hash = hash128(b"class A {}");
+
+        let cache = Cache::open(dir.path(), "config");
+        assert!(cache.get("A.java", hash).is_none());
+        let fix = MessageFix {
+            applicability: Applicability::Safe,
+            edits: vec![Edit::insertion(" ".to_string(), TextSize::new(20))],
+        };
+        cache.insert("A.java", hash, &[message(Some(fix.clone())), message(None)]);
+        cache.save().unwrap();
+
+        let cache = Cache::open(dir.path(), "config");
+        let messages = cache.get("A.java", hash).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].fix, Some(fix));
+        assert_eq!(
+            messages[0].suggestion.as_deref(),
+            Some("Insert whitespace before `=`")
+        );
+        assert_eq!(
+            messages[0].location,
+            Location {
+                line: 2,
+                column: 19
+            }
+        );
+        assert_eq!(messages[0].severity, Severity::Warning);
+        assert_eq!(messages[1].fix, None);
+
+        // Changed contents or configuration miss the cache.
+        assert!(cache.get("A.java", hash128(b"class B {}")).is_none());
+        assert!(
+            Cache::open(dir.path(), "other")
+                .get("A.java", hash)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_clean() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::open(&dir.path().join(CACHE_DIR), "config");
+        cache.insert("A.java", 1, &[]);
+        cache.save().unwrap();
+        assert!(dir.path().join(CACHE_DIR).join(".gitignore").exists());
+
+        assert!(clean(dir.path()).unwrap());
+        assert!(!clean(dir.path()).unwrap());
+    }
+}
@@ -0,0 +1,279 @@
+//! Discovery of files and lines changed relative to a git ref.
+//!
+//! `--diff-base <ref>` runs `git diff` against the merge base of `<ref>` and
+//! `HEAD`, so that only changes made on the current branch (committed or not)
+//! are considered. Untracked files count as changed in full.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use lintal_source_file::{LineIndex, OneIndexed};
+use lintal_text_size::TextRange;
+
+/// The lines of a file that changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ChangedLines {
+    /// The whole file is new.
+    All,
+    /// Sorted, one-indexed, inclusive line ranges.
+    Ranges(Vec<(usize, usize)>),
+}
+
+impl ChangedLines {
+    /// Returns true if any line from `start` to `end` (one-indexed,
+    /// inclusive) changed.
+    pub(crate) fn intersects(&self, start: usize, end: usize) -> bool {
+        match self {
+            ChangedLines::All => true,
+            ChangedLines::Ranges(ranges) => ranges
+                .iter()
+                .any(|&(first, last)| first <= end && start <= last),
+        }
+    }
+
+    /// The changed regions of `source` as byte ranges, or `None` if the
+    /// whole file changed.
+    pub(crate) fn regions(&self, source: &str) -> Option<Vec<TextRange>> {
+        let ChangedLines::Ranges(ranges) = self else {
+            return None;
+        };
+        let index = LineIndex::from_source_text(source);
+        let line_count = index.line_count();
+        let regions = ranges
+            .iter()
+            .filter(|&&(first, _)| first <= line_count)
+            .map(|&(first, last)| {
+                let start = index.line_start(OneIndexed::from_zero_indexed(first - 1), source);
+                let end = index.line_end_exclusive(
+                    OneIndexed::from_zero_indexed(last.min(line_count) - 1),
+                    source,
+                );
+                TextRange::new(start, end)
+            })
+            .collect();
+        Some(regions)
+    }
+}
+
+/// Files changed relative to a git ref, keyed by canonical path.
+#[derive(Debug, Default)]
+pub(crate) struct ChangedFiles {
+    files: HashMap<PathBuf, ChangedLines>,
+}
+
+impl ChangedFiles {
+    /// Ask git for the files changed since the merge base of `base` and
+    /// `HEAD`, including uncommitted and untracked files, in the repository
+    /// containing `dir`.
+    pub(crate) fn discover(base: &str, dir: &Path) -> Result<Self> {
+        let root = PathBuf::from(git(dir, &["rev-parse", "--show-toplevel"])?.trim_end());
+        let diff = git(
+            &root,
+            &[
+                "-c",
+                "core.quotepath=off",
+                "diff",
+                "--merge-base",
+                base,
+                "--unified=0",
+                "--no-color",
+                "--no-ext-diff",
+                "--diff-filter=d",
+                "--",
+            ],
+        )?;
+        let untracked = git(
+            &root,
+            &[
+                "-c",
+                "core.quotepath=off",
+                "ls-files",
+                "--others",
+                "--exclude-standard",
+                "--full-name",
+                ":/",
+            ],
+        )?;
+
+        let mut files = HashMap::new();
+        for (path, lines) in parse_diff(&diff) {
+            files.insert(canonical(&root.join(path)), lines);
+        }
+        for path in untracked.lines().filter(|line| !line.is_empty()) {
+            files.insert(canonical(&root.join(path)), ChangedLines::All);
+        }
+        Ok(Self { files })
+    }
+
+    /// The changed lines of `path`, or `None` if it did not change.
+    pub(crate) fn get(&self, path: &Path) -> Option<&ChangedLines> {
+        self.files.get(&canonical(path))
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Run git with `args` in `dir`, returning its stdout.
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.iter()
+                .find(|arg| !arg.starts_with('-') && !arg.contains('='))
+                .unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    String::from_utf8(output.stdout).context("git produced invalid UTF-8")
+}
+
+/// Parse `git diff --unified=0` output into the added or modified lines of
+/// each file, relative to the repository root.
+///
+/// Pure deletions do not mark any line as changed.
+fn parse_diff(diff: &str) -> HashMap<String, ChangedLines> {
+    let mut files: HashMap<String, ChangedLines> = HashMap::new();
+    let mut current: Option<String> = None;
+    let mut previous = "";
+
+    for line in diff.lines() {
+        // File headers are `--- old` followed by `+++ new`; checking both
+        // keeps added lines that start with `++` from passing as a header
+        let header = previous.starts_with("--- ");
+        previous = line;
+        if header && let Some(path) = line.strip_prefix("+++ ") {
+            current = (path != "/dev/null").then(|| {
+                let path = unquote(path);
+                path.strip_prefix("b/").unwrap_or(&path).to_string()
+            });
+            continue;
+        }
+        let Some(hunk) = line.strip_prefix("@@ ") else {
+            continue;
+        };
+        let Some(path) = &current else {
+            continue;
+        };
+        let Some((start, count)) = hunk
+            .split_whitespace()
+            .find_map(|part| part.strip_prefix('+'))
+            .and_then(parse_hunk_range)
+        else {
+            continue;
+        };
+
+        let entry = files
+            .entry(path.clone())
+            .or_insert_with(|| ChangedLines::Ranges(Vec::new()));
+        if count > 0
+            && let ChangedLines::Ranges(ranges) = entry
+        {
+            ranges.push((start, start + count - 1));
+        }
+    }
+
+    for lines in files.values_mut() {
+        if let ChangedLines::Ranges(ranges) = lines {
+            ranges.sort_unstable();
+        }
+    }
+    files
+}
+
+/// Parse `start[,count]` from a hunk header.
+fn parse_hunk_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+/// Undo git's C-style quoting of unusual paths.
+fn unquote(path: &str) -> String {
+    let Some(inner) = path.strip_prefix('"').and_then(|p| p.strip_suffix('"')) else {
+        return path.to_string();
+    };
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => result.push('\t'),
+            Some('n') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => {}
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_diff() {
+        let diff = "\
+diff --git a/src/A.java b/src/A.java
+index 1111111..2222222 100644
+--- a/src/A.java
++++ b/src/A.java
+@@ -3 +3 @@ class A {
+-    int x=1;
++    int x = 1;
+@@ -10,2 +9,0 @@ class A {
+-    // gone
+-    // gone
+@@ -20,0 +20,3 @@ class A {
++    void a() {}
++    void b() {}
++    void c() {}
+diff --git a/src/New.java b/src/New.java
+new file mode 100644
+--- /dev/null
++++ \"b/src/Odd \\\"name\\\".java\"
+@@ -0,0 +1,2 @@
++class New {
++}
+";
+        let files = parse_diff(diff);
+        assert_eq!(
+            files["src/A.java"],
+            ChangedLines::Ranges(vec![(3, 3), (20, 22)])
+        );
+        assert_eq!(
+            files["src/Odd \"name\".java"],
+            ChangedLines::Ranges(vec![(1, 2)])
+        );
+    }
+
+    #[test]
+    fn test_intersects() {
+        let lines = ChangedLines::Ranges(vec![(3, 3), (20, 22)]);
+        assert!(lines.intersects(3, 3));
+        assert!(lines.intersects(1, 3));
+        assert!(lines.intersects(22, 30));
+        assert!(!lines.intersects(4, 19));
+        assert!(ChangedLines::All.intersects(1, 1));
+    }
+
+    #[test]
+    fn test_regions() {
+        let source = "aaa\nbbb\nccc\n";
+        let regions = ChangedLines::Ranges(vec![(2, 2)]).regions(source).unwrap();
+        assert_eq!(regions, vec![TextRange::new(4.into(), 7.into())]);
+        assert_eq!(ChangedLines::All.regions(source), None);
+    }
+}
@@ -0,0 +1,92 @@
+//! Source files in the encoding named by checkstyle's `charset`.
+
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use encoding_rs::{Encoding, UTF_8};
+
+/// Look up an encoding by the name checkstyle.xml gives it, e.g.
+/// `ISO-8859-1` or `windows-1252`.
+pub(crate) fn for_name(name: &str) -> Result<&'static Encoding> {
+    Encoding::for_label(name.trim().as_bytes())
+        .with_context(|| format!("Unsupported charset '{name}'"))
+}
+
+/// Read the source of the file at `path`.
+pub(crate) fn read_source(path: &Path, encoding: &'static Encoding) -> Result<String> {
+    if encoding == UTF_8 {
+        return std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()));
+    }
+    let bytes =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    decode(&bytes, encoding).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+/// Write the source of the file at `path`.
+pub(crate) fn write_source(path: &Path, source: &str, encoding: &'static Encoding) -> Result<()> {
+    let bytes =
+        encode(source, encoding).with_context(|| format!("Failed to write {}", path.display()))?;
+    std::fs::write(path, bytes).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Decode source text, failing on bytes that are not valid in `encoding`.
+pub(crate) fn decode(bytes: &[u8], encoding: &'static Encoding) -> Result<String> {
+    match encoding.decode_without_bom_handling_and_without_replacement(bytes) {
+        Some(text) => Ok(text.into_owned()),
+        None => bail!("not valid {}", encoding.name()),
+    }
+}
+
+/// Fail if sources cannot be written in `encoding`.
+pub(crate) fn ensure_writable(encoding: &'static Encoding) -> Result<()> {
+    // UTF-16 can be read but not written
+    if encoding.output_encoding() != encoding {
+        bail!("writing {} is not supported", encoding.name());
+    }
+    Ok(())
+}
+
+/// Encode source text, failing on characters `encoding` cannot represent.
+pub(crate) fn encode(source: &str, encoding: &'static Encoding) -> Result<Vec<u8>> {
+    ensure_writable(encoding)?;
+    let (bytes, _, unmappable) = encoding.encode(source);
+    if unmappable {
+        bail!(
+            "the fixed source cannot be represented in {}",
+            encoding.name()
+        );
+    }
+    Ok(bytes.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latin1_round_trip() {
+        let encoding = for_name("ISO-8859-1").unwrap();
+        let bytes = b"// Gr\xfc\xdfe\nclass A {}\n";
+
+        let source = decode(bytes, encoding).unwrap();
+        assert_eq!(source, "// Grüße\nclass A {}\n");
+        assert_eq!(encode(&source, encoding).unwrap(), bytes);
+        assert!(encode("// \u{1F600}", encoding).is_err());
+    }
+
+    #[test]
+    fn test_utf16_cannot_be_written() {
+        let encoding = for_name("UTF-16LE").unwrap();
+        assert_eq!(decode(b"A\0", encoding).unwrap(), "A");
+        assert!(ensure_writable(encoding).is_err());
+        assert!(encode("A", encoding).is_err());
+        assert!(ensure_writable(UTF_8).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_utf8_is_an_error() {
+        assert!(decode(b"\xfc", UTF_8).is_err());
+        assert!(for_name("no-such-charset").is_err());
+    }
+}
@@ -0,0 +1,562 @@
+//! `lintal config`: inspect the checkstyle.xml configuration.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use clap::{Subcommand, ValueEnum};
+use colored::Colorize;
+use lintal::{NEARBY_COMMENT_FILTER, effective_properties};
+use lintal_checkstyle::{
+    CheckerSettings, CheckstyleConfig, ConfiguredRule, MergedConfig, RuleMode,
+};
+use lintal_diagnostics::Severity;
+use lintal_linter::{
+    CHECKER_PROPERTIES, PropertyIssue, RuleRegistry, TREE_WALKER_PROPERTIES, validate_properties,
+};
+use serde::Serialize;
+
+use crate::{ConfigArgs, LoadedConfig, charset, load_config};
+
+#[derive(Subcommand)]
+pub(crate) enum ConfigCommand {
+    /// Validate checkstyle.xml module properties without linting any files
+    Check {
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+    /// Print the effective configuration: the files it was read from, the
+    /// enabled rules with all their properties, and the suppressions
+    Show {
+        /// Only show the rules that apply to this file
+        #[arg(long, value_name = "PATH")]
+        file: Option<PathBuf>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ShowFormat::Toml)]
+        output_format: ShowFormat,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+}
+
+/// Output format for `lintal config show`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub(crate) enum ShowFormat {
+    /// TOML.
+    #[default]
+    Toml,
+    /// JSON.
+    Json,
+}
+
+/// Run `lintal config`.
+pub(crate) fn run(command: ConfigCommand) -> Result<()> {
+    match command {
+        ConfigCommand::Check { config } => check(&config),
+        ConfigCommand::Show {
+            file,
+            output_format,
+            config,
+        } => show(file.as_deref(), output_format, &config),
+    }
+}
+
+/// A problem with a module in checkstyle.xml.
+#[derive(Debug, PartialEq, Eq)]
+struct Problem {
+    module: String,
+    message: String,
+    /// Errors make lintal behave differently from checkstyle; warnings
+    /// only point out configuration lintal ignores.
+    error: bool,
+}
+
+/// Run `lintal config check`, exiting with 1 if there are errors.
+fn check(config_args: &ConfigArgs) -> Result<()> {
+    let LoadedConfig {
+        merged_config,
+        config_path,
+        checkstyle,
+        ..
+    } = load_config(config_args, &[PathBuf::from(".")])?;
+    let (Some(merged_config), Some(config_path), Some(checkstyle)) =
+        (merged_config, config_path, checkstyle)
+    else {
+        bail!("No checkstyle.xml found; pass one with --config");
+    };
+
+    let problems = validate(&RuleRegistry::builtin(), &checkstyle, &merged_config);
+    for problem in &problems {
+        let label = if problem.error {
+            "error:".red().bold()
+        } else {
+            "warning:".yellow().bold()
+        };
+        println!(
+            "{label} {}: {}: {}",
+            config_path.display(),
+            problem.module,
+            problem.message
+        );
+    }
+
+    let errors = problems.iter().filter(|problem| problem.error).count();
+    let warnings = problems.len() - errors;
+    if problems.is_empty() {
+        println!("{}", format!("{} is valid", config_path.display()).green());
+    } else {
+        println!(
+            "\nFound {} errors and {} warnings",
+            errors.to_string().red(),
+            warnings.to_string().yellow()
+        );
+    }
+
+    if errors > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Check the `Checker` and `TreeWalker` modules and every configured rule
+/// against their schemas.
+fn validate(
+    registry: &RuleRegistry,
+    checkstyle: &CheckstyleConfig,
+    config: &MergedConfig,
+) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    let checker_properties: HashMap<&str, &str> = checkstyle
+        .properties
+        .iter()
+        .map(|p| (p.name.as_str(), p.value.as_str()))
+        .collect();
+    problems.extend(to_problems(
+        &checkstyle.name,
+        validate_properties(&[CHECKER_PROPERTIES], &checker_properties),
+    ));
+    if let Err(error) = charset::for_name(&config.checker.charset) {
+        problems.push(Problem {
+            module: checkstyle.name.clone(),
+            message: error.to_string(),
+            error: true,
+        });
+    }
+    if let Some(tree_walker) = checkstyle.tree_walker() {
+        problems.extend(to_problems(
+            &tree_walker.name,
+            validate_properties(&[TREE_WALKER_PROPERTIES], &tree_walker.properties_map()),
+        ));
+    }
+
+    for rule in &config.rules {
+        // Filters are applied through suppressions, not run as rules
+        if rule.name == NEARBY_COMMENT_FILTER {
+            continue;
+        }
+        let Some(metadata) = registry.metadata(&rule.name) else {
+            problems.push(Problem {
+                module: rule.name.clone(),
+                message: "module is not supported by lintal and will be skipped".to_string(),
+                error: false,
+            });
+            continue;
+        };
+        problems.extend(to_problems(
+            &rule.name,
+            metadata.validate(&rule.properties_ref()),
+        ));
+    }
+    problems
+}
+
+fn to_problems(module: &str, issues: Vec<PropertyIssue>) -> impl Iterator<Item = Problem> {
+    issues.into_iter().map(move |issue| Problem {
+        module: module.to_string(),
+        message: issue.to_string(),
+        error: issue.is_error(),
+    })
+}
+
+/// Run `lintal config show`.
+fn show(file: Option<&Path>, format: ShowFormat, config_args: &ConfigArgs) -> Result<()> {
+    // Discover the configuration the way `lintal check <file>` would
+    let base = file.map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+    let loaded = load_config(config_args, &[base])?;
+    let config = effective_config(&RuleRegistry::builtin(), &loaded, file);
+    match format {
+        ShowFormat::Toml => print!("{}", toml::to_string_pretty(&config)?),
+        ShowFormat::Json => println!("{}", serde_json::to_string_pretty(&config)?),
+    }
+    Ok(())
+}
+
+/// The configuration lintal runs with.
+///
+/// Plain values come before tables so that it serializes as TOML.
+#[derive(Serialize)]
+struct EffectiveConfig {
+    unsafe_fixes: bool,
+    /// Enabled modules that lintal skips.
+    unsupported: Vec<String>,
+    /// The build's source directories that checked directories narrow to.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    source_roots: Vec<String>,
+    /// The build's source directories that checked directories skip.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    excluded_roots: Vec<String>,
+    paths: ConfigPaths,
+    checker: Checker,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<FileRules>,
+    rules: Vec<EffectiveRule>,
+    suppression_filters: Vec<SuppressionFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file_suppressions: Option<FileSuppressions>,
+}
+
+/// The files the configuration was read from.
+#[derive(Serialize)]
+struct ConfigPaths {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checkstyle: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lintal: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suppressions: Option<String>,
+    /// The pom.xml or build.gradle(.kts) with checkstyle plugin settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    build: Option<String>,
+}
+
+/// Settings of the `Checker` module.
+#[derive(Serialize)]
+struct Checker {
+    tab_width: usize,
+    /// Empty if files of every extension are checked.
+    file_extensions: Vec<String>,
+    charset: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    basedir: Option<String>,
+}
+
+/// The file `--file` asked about.
+#[derive(Serialize)]
+struct FileRules {
+    path: String,
+    /// Whether the file has one of the extensions that are checked.
+    checked: bool,
+    /// Rules that suppressions.xml turns off for the whole file.
+    suppressed: Vec<String>,
+}
+
+/// An enabled rule with every property it reads: the configured value, or
+/// the default if there is none.
+#[derive(Serialize)]
+struct EffectiveRule {
+    name: String,
+    mode: &'static str,
+    severity: &'static str,
+    properties: BTreeMap<String, String>,
+}
+
+/// A comment filter, described with its checkstyle property names.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum SuppressionFilter {
+    PlainText {
+        module: &'static str,
+        #[serde(rename = "offCommentFormat")]
+        off_comment_format: String,
+        #[serde(rename = "onCommentFormat")]
+        on_comment_format: String,
+        #[serde(rename = "checkFormat", skip_serializing_if = "Option::is_none")]
+        check_format: Option<String>,
+    },
+    Nearby {
+        module: &'static str,
+        #[serde(rename = "commentFormat")]
+        comment_format: String,
+        #[serde(rename = "checkFormat")]
+        check_format: String,
+        #[serde(rename = "messageFormat", skip_serializing_if = "Option::is_none")]
+        message_format: Option<String>,
+        #[serde(rename = "idFormat", skip_serializing_if = "Option::is_none")]
+        id_format: Option<String>,
+        #[serde(rename = "influenceFormat")]
+        influence_format: String,
+        #[serde(rename = "checkCPP")]
+        check_cpp: bool,
+        #[serde(rename = "checkC")]
+        check_c: bool,
+    },
+}
+
+/// The suppressions.xml in use.
+#[derive(Serialize)]
+struct FileSuppressions {
+    path: String,
+    entries: usize,
+}
+
+/// Describe the configuration lintal runs with, restricted to the rules
+/// that apply to `file` if one is given.
+fn effective_config(
+    registry: &RuleRegistry,
+    loaded: &LoadedConfig,
+    file: Option<&Path>,
+) -> EffectiveConfig {
+    let rules = match &loaded.merged_config {
+        Some(config) => config.enabled_rules().cloned().collect(),
+        // Without checkstyle.xml, lintal runs WhitespaceAround alone
+        None => vec![ConfiguredRule {
+            name: "WhitespaceAround".to_string(),
+            properties: HashMap::new(),
+            mode: RuleMode::Fix,
+            severity: Severity::Error,
+        }],
+    };
+    let settings = loaded
+        .merged_config
+        .as_ref()
+        .map(|config| config.checker.clone())
+        .unwrap_or_default();
+    let (mut applied, unsupported): (Vec<_>, Vec<_>) = rules
+        .into_iter()
+        .filter(|rule| rule.name != NEARBY_COMMENT_FILTER)
+        .partition(|rule| registry.metadata(&rule.name).is_some());
+
+    let file = file.map(|file| {
+        let checked = file
+            .file_name()
+            .is_some_and(|name| settings.accepts(&name.to_string_lossy()));
+        if !checked {
+            applied.clear();
+        }
+        let path = settings.relative_path(file).to_string_lossy().into_owned();
+        let (suppressed, rest): (Vec<_>, Vec<_>) = applied
+            .drain(..)
+            .partition(|rule| loaded.file_suppressions.is_suppressed(&path, &rule.name));
+        applied = rest;
+        FileRules {
+            path,
+            checked,
+            suppressed: suppressed.into_iter().map(|rule| rule.name).collect(),
+        }
+    });
+
+    let display = |path: &Option<PathBuf>| path.as_ref().map(|path| path.display().to_string());
+    EffectiveConfig {
+        unsafe_fixes: loaded
+            .merged_config
+            .as_ref()
+            .is_some_and(|config| config.unsafe_fixes),
+        unsupported: unsupported.into_iter().map(|rule| rule.name).collect(),
+        source_roots: loaded
+            .build
+            .iter()
+            .flat_map(|build| &build.source_roots)
+            .map(|root| root.display().to_string())
+            .collect(),
+        excluded_roots: loaded
+            .build
+            .iter()
+            .flat_map(|build| &build.excluded_roots)
+            .map(|root| root.display().to_string())
+            .collect(),
+        paths: ConfigPaths {
+            checkstyle: display(&loaded.config_path),
+            lintal: display(&loaded.lintal_path),
+            suppressions: display(&loaded.file_suppressions_path),
+            build: display(&loaded.build.as_ref().map(|build| build.build_file.clone())),
+        },
+        checker: Checker {
+            tab_width: settings.tab_width(),
+            file_extensions: settings.file_extensions.clone(),
+            charset: settings.charset.clone(),
+            basedir: display(&settings.basedir),
+        },
+        file,
+        rules: applied
+            .iter()
+            .map(|rule| effective_rule(registry, rule, &settings))
+            .collect(),
+        suppression_filters: suppression_filters(loaded),
+        file_suppressions: display(&loaded.file_suppressions_path).map(|path| FileSuppressions {
+            path,
+            entries: loaded.file_suppressions.len(),
+        }),
+    }
+}
+
+fn effective_rule(
+    registry: &RuleRegistry,
+    rule: &ConfiguredRule,
+    settings: &CheckerSettings,
+) -> EffectiveRule {
+    let mut properties: BTreeMap<String, String> = registry
+        .metadata(&rule.name)
+        .into_iter()
+        .flat_map(|metadata| metadata.properties)
+        .map(|property| (property.name.to_string(), property.default.to_string()))
+        .collect();
+    // Severity is shown resolved, inherited from the enclosing modules
+    for (name, value) in effective_properties(registry, rule, settings) {
+        if name != "severity" {
+            properties.insert(name, value);
+        }
+    }
+
+    EffectiveRule {
+        name: rule.name.clone(),
+        mode: rule.mode.as_str(),
+        severity: rule.severity.as_str(),
+        properties,
+    }
+}
+
+fn suppression_filters(loaded: &LoadedConfig) -> Vec<SuppressionFilter> {
+    let plain_text = loaded
+        .suppression_filters
+        .iter()
+        .map(|filter| SuppressionFilter::PlainText {
+            module: "SuppressWithPlainTextCommentFilter",
+            off_comment_format: filter.off_pattern.to_string(),
+            on_comment_format: filter.on_pattern.to_string(),
+            check_format: (filter.check_format_group > 0)
+                .then(|| format!("${}", filter.check_format_group)),
+        });
+    let nearby = loaded
+        .nearby_filters
+        .iter()
+        .map(|filter| SuppressionFilter::Nearby {
+            module: NEARBY_COMMENT_FILTER,
+            comment_format: filter.comment_pattern.to_string(),
+            check_format: filter.check_format.clone(),
+            message_format: filter.message_format.clone(),
+            id_format: filter.id_format.clone(),
+            influence_format: filter.influence_format.clone(),
+            check_cpp: filter.check_cpp,
+            check_c: filter.check_c,
+        });
+    plain_text.chain(nearby).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use lintal_linter::{FileSuppressionsConfig, PlainTextCommentFilterConfig};
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_validate_reports_each_problem() {
+        let xml = r#"<?xml version="1.0"?>
+<module name="Checker">
+    <property name="charset" value="EBCDIC-42"/>
+    <module name="TreeWalker">
+        <property name="fileExtension" value="java"/>
+        <module name="Indentation">
+            <property name="basicOffset" value="four"/>
+            <property name="basicOfset" value="4"/>
+        </module>
+        <module name="EmptyBlock">
+            <property name="tokens" value="LITERAL_IF"/>
+        </module>
+        <module name="JavadocMethod"/>
+        <module name="WhitespaceAround">
+            <property name="severity" value="warning"/>
+        </module>
+    </module>
+</module>"#;
+        let checkstyle = CheckstyleConfig::parse(xml).unwrap();
+        let config = MergedConfig::new(&checkstyle, None);
+
+        let problems = validate(&RuleRegistry::builtin(), &checkstyle, &config);
+        let problems: Vec<(&str, bool)> = problems
+            .iter()
+            .map(|problem| (problem.message.as_str(), problem.error))
+            .collect();
+        assert_eq!(
+            problems,
+            [
+                ("Unsupported charset 'EBCDIC-42'", true),
+                (
+                    "unknown property 'fileExtension' (did you mean 'fileExtensions'?)",
+                    true
+                ),
+                (
+                    "invalid value 'four' for property 'basicOffset': expected an integer",
+                    true
+                ),
+                (
+                    "unknown property 'basicOfset' (did you mean 'basicOffset'?)",
+                    true
+                ),
+                (
+                    "property 'tokens' is supported by checkstyle but ignored by lintal",
+                    false
+                ),
+                (
+                    "module is not supported by lintal and will be skipped",
+                    false
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_effective_config_for_file() {
+        let xml = r#"<?xml version="1.0"?>
+<module name="Checker">
+    <module name="TreeWalker">
+        <module name="Indentation">
+            <property name="basicOffset" value="2"/>
+            <property name="severity" value="warning"/>
+        </module>
+        <module name="UpperEll"/>
+        <module name="JavadocMethod"/>
+    </module>
+</module>"#;
+        let checkstyle = CheckstyleConfig::parse(xml).unwrap();
+        let suppressions = r#"<suppressions>
+    <suppress files="Generated\.java" checks="UpperEll"/>
+</suppressions>"#;
+        let loaded = LoadedConfig {
+            merged_config: Some(MergedConfig::new(&checkstyle, None)),
+            suppression_filters: vec![PlainTextCommentFilterConfig::checkstyle_default()],
+            file_suppressions: FileSuppressionsConfig::from_xml(suppressions),
+            file_suppressions_path: Some(PathBuf::from("suppressions.xml")),
+            config_path: Some(PathBuf::from("checkstyle.xml")),
+            ..LoadedConfig::default()
+        };
+        let registry = RuleRegistry::builtin();
+
+        let config = effective_config(&registry, &loaded, Some(Path::new("src/Generated.java")));
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value["unsupported"], json!(["JavadocMethod"]));
+        assert_eq!(value["file"]["suppressed"], json!(["UpperEll"]));
+        let rules = value["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0]["severity"], "warning");
+        assert_eq!(rules[0]["properties"]["basicOffset"], "2");
+        assert_eq!(rules[0]["properties"]["caseIndent"], "4");
+        assert!(rules[0]["properties"].get("severity").is_none());
+
+        let config = effective_config(&registry, &loaded, None);
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value["rules"].as_array().unwrap().len(), 2);
+        assert_eq!(value["file_suppressions"]["entries"], 1);
+
+        let toml = toml::to_string_pretty(&config).unwrap();
+        assert!(
+            toml.contains("[paths]\ncheckstyle = \"checkstyle.xml\"\n"),
+            "{toml}"
+        );
+        assert!(toml.contains("[[rules]]\nname = \"UpperEll\"\n"), "{toml}");
+    }
+}
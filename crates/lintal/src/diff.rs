@@ -0,0 +1,141 @@
+//! Unified diffs for `lintal fix --diff`.
+
+use std::fmt::Write;
+use std::path::{Component, Path, PathBuf};
+
+use colored::Colorize;
+use similar::TextDiff;
+
+/// Lines of unchanged context around each change, as in `diff -u`.
+const CONTEXT_LINES: usize = 3;
+
+/// Format a unified diff between the original and fixed source of `path`.
+///
+/// Without colors the output is a patch that `git apply` and `patch -p1`
+/// accept, with the usual `a/` and `b/` prefixes.
+pub(crate) fn format_diff(path: &Path, original: &str, fixed: &str) -> String {
+    let cwd = std::env::current_dir().ok();
+    let (old, new) = header_paths(path, cwd.as_deref());
+
+    let mut output = String::new();
+    writeln!(output, "{}", format!("--- {old}").bold()).unwrap();
+    writeln!(output, "{}", format!("+++ {new}").bold()).unwrap();
+
+    let diff = TextDiff::from_lines(original, fixed);
+    for hunk in diff
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .iter_hunks()
+    {
+        // The hunk renders its header followed by one line per change,
+        // keeping each line's original terminator
+        let text = hunk.to_string();
+        for line in text.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\r', '\n']);
+            let terminator = &line[content.len()..];
+            let content = match content.as_bytes().first() {
+                Some(b'@') => content.cyan(),
+                Some(b'-') => content.red(),
+                Some(b'+') => content.green(),
+                _ => content.normal(),
+            };
+            write!(output, "{content}{terminator}").unwrap();
+        }
+    }
+    output
+}
+
+/// The paths of the `---` and `+++` header lines. Absolute paths are made
+/// relative to `cwd`; those outside of it are shown without prefixes, as
+/// `a//tmp/A.java` would not name the file.
+fn header_paths(path: &Path, cwd: Option<&Path>) -> (String, String) {
+    let path = cwd
+        .filter(|_| path.is_absolute())
+        .and_then(|cwd| path.strip_prefix(cwd).ok())
+        .unwrap_or(path);
+    let path: PathBuf = path
+        .components()
+        .filter(|component| *component != Component::CurDir)
+        .collect();
+    let display = path.display();
+    if path.is_absolute() {
+        (display.to_string(), display.to_string())
+    } else {
+        (format!("a/{display}"), format!("b/{display}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain_diff(original: &str, fixed: &str) -> String {
+        colored::control::set_override(false);
+        format_diff(Path::new("./src/A.java"), original, fixed)
+    }
+
+    #[test]
+    fn test_deleted_line_keeps_hunks_aligned() {
+        let original = "a\nb;\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n";
+        let fixed = "a\nc\nd\ne\nf\ng\nh\ni\nj\nK\nl\n";
+        assert_eq!(
+            plain_diff(original, fixed),
+            "\
+--- a/src/A.java
++++ b/src/A.java
+@@ -1,5 +1,4 @@
+ a
+-b;
+ c
+ d
+ e
+@@ -8,5 +7,5 @@
+ h
+ i
+ j
+-k
++K
+ l
+"
+        );
+    }
+
+    #[test]
+    fn test_missing_newline_at_end_of_file() {
+        assert_eq!(
+            plain_diff("class A {}", "class A { }"),
+            "\
+--- a/src/A.java
++++ b/src/A.java
+@@ -1 +1 @@
+-class A {}
+\\ No newline at end of file
++class A { }
+\\ No newline at end of file
+"
+        );
+    }
+
+    #[test]
+    fn test_crlf_line_endings_are_kept() {
+        assert_eq!(
+            plain_diff("a\r\nb\r\n", "a\r\nB\r\n"),
+            "--- a/src/A.java\n+++ b/src/A.java\n@@ -1,2 +1,2 @@\n a\r\n-b\r\n+B\r\n"
+        );
+    }
+
+    #[test]
+    fn test_absolute_paths() {
+        let cwd = std::env::current_dir().unwrap();
+        assert_eq!(
+            header_paths(&cwd.join("src/A.java"), Some(&cwd)),
+            ("a/src/A.java".to_string(), "b/src/A.java".to_string())
+        );
+
+        // Outside the working directory there is no prefix to strip
+        let outside = cwd.join("../elsewhere/A.java");
+        let (old, new) = header_paths(&outside, Some(&cwd.join("project")));
+        assert_eq!(old, outside.display().to_string());
+        assert_eq!(new, old);
+    }
+}
@@ -0,0 +1,291 @@
+//! Applying fixes to a source buffer until it is stable.
+
+use std::path::Path;
+
+use lintal_checkstyle::RuleMode;
+use lintal_diagnostics::{Applicability, Edit};
+use lintal_linter::SYNTAX_ERROR;
+use lintal_text_size::{Ranged, TextRange, TextSize};
+
+use crate::{Linter, RuleDiagnostic};
+
+/// Default cap on fix iterations per file, matching ruff's limit.
+pub const DEFAULT_MAX_ITERATIONS: usize = 100;
+
+/// The result of repeatedly fixing a source buffer.
+#[derive(Debug)]
+pub struct FixOutcome {
+    /// The fixed source.
+    pub source: String,
+    /// Number of fixes applied.
+    pub fixed: usize,
+    /// Number of diagnostics left after the last pass.
+    pub remaining: usize,
+    /// Rules that still had applicable fixes when the iteration cap was hit.
+    pub pending_rules: Vec<&'static str>,
+    /// Set if fixing stopped because of a syntax error.
+    pub syntax_error: Option<SyntaxErrorStop>,
+    /// The regions fixes were limited to, mapped onto `source`.
+    pub scope: Option<Vec<TextRange>>,
+}
+
+/// Why fixing a source buffer was stopped by a syntax error.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SyntaxErrorStop {
+    /// The original source has syntax errors, so nothing was fixed.
+    InSource,
+    /// A pass of fixes from these rules introduced a syntax error and was
+    /// reverted.
+    Introduced(Vec<&'static str>),
+}
+
+/// The state before a pass of fixes, kept so the pass can be reverted.
+struct FixPass {
+    source: String,
+    scope: Option<Vec<TextRange>>,
+    remaining: usize,
+    applied: usize,
+    rules: Vec<&'static str>,
+}
+
+/// Apply fixes from rules whose mode satisfies `fixable` until no applicable
+/// fixes remain, or until `max_iterations` passes have run.
+///
+/// The source is re-parsed and re-checked after every pass. This picks up
+/// fixes that lost an overlap in an earlier pass as well as violations that
+/// only appear once another fix has been applied. Sources with syntax errors
+/// are never fixed, and a pass that introduces a syntax error is reverted.
+/// With `scope`, only diagnostics touching one of its byte ranges are fixed
+/// or counted; the ranges follow the text as fixes are applied.
+/// Returns `None` if the source could not be parsed.
+pub fn fix_until_stable(
+    linter: &Linter,
+    path: &Path,
+    source: &str,
+    mut scope: Option<Vec<TextRange>>,
+    applicability: Applicability,
+    max_iterations: usize,
+    fixable: impl Fn(RuleMode) -> bool,
+) -> Option<FixOutcome> {
+    let applies = |d: &RuleDiagnostic| {
+        fixable(d.mode)
+            && d.diagnostic
+                .fix
+                .as_ref()
+                .is_some_and(|fix| fix.applies(applicability))
+    };
+
+    let mut current = source.to_string();
+    let mut fixed = 0;
+    let mut iterations = 0;
+    let mut pending_rules: Vec<&'static str> = Vec::new();
+    let mut syntax_error = None;
+    let mut previous: Option<FixPass> = None;
+
+    let remaining = loop {
+        let diagnostics = linter.check(&current, path)?;
+
+        if diagnostics.iter().any(|d| d.rule == SYNTAX_ERROR) {
+            let Some(pass) = previous.take() else {
+                syntax_error = Some(SyntaxErrorStop::InSource);
+                break diagnostics.len();
+            };
+            current = pass.source;
+            scope = pass.scope;
+            fixed -= pass.applied;
+            syntax_error = Some(SyntaxErrorStop::Introduced(pass.rules));
+            break pass.remaining;
+        }
+
+        let diagnostics: Vec<RuleDiagnostic> = match &scope {
+            Some(regions) => diagnostics
+                .into_iter()
+                .filter(|d| in_regions(regions, d.diagnostic.range))
+                .collect(),
+            None => diagnostics,
+        };
+        let applicable: Vec<&RuleDiagnostic> = diagnostics.iter().filter(|d| applies(d)).collect();
+        if applicable.is_empty() {
+            break diagnostics.len();
+        }
+
+        if iterations == max_iterations {
+            for d in &applicable {
+                if !pending_rules.contains(&d.rule) {
+                    pending_rules.push(d.rule);
+                }
+            }
+            break diagnostics.len();
+        }
+
+        let (edits, applied) = select_fixes(&applicable);
+        let fixed_source = apply_edits(&current, &edits);
+        if applied == 0 || fixed_source == current {
+            break diagnostics.len();
+        }
+
+        let mut rules: Vec<&'static str> = Vec::new();
+        for d in &applicable {
+            if !rules.contains(&d.rule) {
+                rules.push(d.rule);
+            }
+        }
+        let previous_scope = scope.clone();
+        if let Some(regions) = &mut scope {
+            shift_regions(regions, &edits);
+        }
+        previous = Some(FixPass {
+            source: std::mem::replace(&mut current, fixed_source),
+            scope: previous_scope,
+            remaining: diagnostics.len(),
+            applied,
+            rules,
+        });
+        fixed += applied;
+        iterations += 1;
+    };
+
+    Some(FixOutcome {
+        source: current,
+        fixed,
+        remaining,
+        pending_rules,
+        syntax_error,
+        scope,
+    })
+}
+
+/// Select one pass of fixes to apply.
+///
+/// Fixes are treated atomically: a fix is only applied if none of its edits
+/// overlap an edit from a fix that was already accepted. Skipped fixes are
+/// picked up again on the next pass. Returns the accepted edits, sorted for
+/// [`apply_edits`], and the number of fixes applied.
+fn select_fixes(diagnostics: &[&RuleDiagnostic]) -> (Vec<Edit>, usize) {
+    let mut fixes: Vec<&[Edit]> = diagnostics
+        .iter()
+        .filter_map(|d| {
+            let fix = d.diagnostic.fix.as_ref()?;
+            (!fix.edits().is_empty()).then_some(fix.edits())
+        })
+        .collect();
+    fixes.sort_by_key(|edits| (edits[0].start(), edits[0].end()));
+
+    let mut accepted: Vec<Edit> = Vec::new();
+    let mut applied = 0;
+
+    for edits in fixes {
+        // Fixes that exactly duplicate already accepted edits (e.g. two rules
+        // inserting the same space) count as applied without editing twice.
+        if edits.iter().all(|edit| accepted.contains(edit)) {
+            applied += 1;
+            continue;
+        }
+        if edits.iter().any(|edit| {
+            accepted
+                .iter()
+                .any(|existing| edits_overlap(edit, existing))
+        }) {
+            continue;
+        }
+        accepted.extend(edits.iter().cloned());
+        applied += 1;
+    }
+
+    // Apply from end to start; for equal starts, apply the wider edit first so
+    // an insertion at the same offset ends up in front of the replacement.
+    accepted.sort_by_key(|e| std::cmp::Reverse((e.start(), e.end())));
+    (accepted, applied)
+}
+
+/// Returns true if two distinct edits cannot both be applied.
+fn edits_overlap(a: &Edit, b: &Edit) -> bool {
+    if a.start() < b.end() && b.start() < a.end() {
+        return true;
+    }
+    // Two different insertions at the same offset have no well-defined order.
+    a.is_insertion() && b.is_insertion() && a.start() == b.start()
+}
+
+/// Apply edits, sorted descending by start position, to source text.
+pub fn apply_edits(source: &str, edits: &[Edit]) -> String {
+    let mut result = source.to_string();
+
+    // Edits are sorted descending by start position, so we can apply them in order
+    for edit in edits {
+        let start = usize::from(edit.start());
+        let end = usize::from(edit.end());
+        let content = edit.content().unwrap_or("");
+
+        result.replace_range(start..end, content);
+    }
+
+    result
+}
+
+/// Returns true if `range` touches any of `regions`.
+fn in_regions(regions: &[TextRange], range: TextRange) -> bool {
+    regions
+        .iter()
+        .any(|region| region.start() <= range.end() && range.start() <= region.end())
+}
+
+/// Map `regions` through `edits`, which must not overlap. A region that an
+/// edit touches grows to cover the edit's replacement.
+fn shift_regions(regions: &mut [TextRange], edits: &[Edit]) {
+    let mut edits: Vec<&Edit> = edits.iter().collect();
+    edits.sort_by_key(|edit| edit.start());
+
+    let map = |offset: TextSize, is_end: bool| {
+        let mut delta: i64 = 0;
+        for edit in &edits {
+            let inserted = edit.content().map_or(0, str::len);
+            let growth = i64::try_from(inserted).unwrap_or(i64::MAX)
+                - i64::from(u32::from(edit.range().len()));
+            if edit.end() <= offset {
+                delta += growth;
+            } else if edit.start() < offset {
+                // The offset lies inside the edit: snap it to the edit's
+                // replacement boundary.
+                let snapped = if is_end {
+                    i64::from(u32::from(edit.end())) + delta + growth
+                } else {
+                    i64::from(u32::from(edit.start())) + delta
+                };
+                return TextSize::new(u32::try_from(snapped.max(0)).unwrap_or(u32::MAX));
+            }
+        }
+        let shifted = i64::from(u32::from(offset)) + delta;
+        TextSize::new(u32::try_from(shifted.max(0)).unwrap_or(u32::MAX))
+    };
+
+    for region in regions {
+        let start = map(region.start(), false);
+        let end = map(region.end(), true).max(start);
+        *region = TextRange::new(start, end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shift_regions() {
+        let mut regions = vec![TextRange::new(4.into(), 7.into())];
+
+        // An insertion before the region shifts it, one inside grows it.
+        shift_regions(
+            &mut regions,
+            &[
+                Edit::insertion("xx".to_string(), 1.into()),
+                Edit::insertion("y".to_string(), 5.into()),
+            ],
+        );
+        assert_eq!(regions, vec![TextRange::new(6.into(), 10.into())]);
+
+        // A deletion after the region leaves it alone.
+        shift_regions(&mut regions, &[Edit::deletion(11.into(), 13.into())]);
+        assert_eq!(regions, vec![TextRange::new(6.into(), 10.into())]);
+    }
+}
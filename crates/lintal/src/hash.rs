@@ -0,0 +1,41 @@
+//! Hashes for values that are written to files and compared across runs.
+//!
+//! Baseline and GitLab Code Quality fingerprints are compared with ones
+//! produced by other lintal builds, so they cannot use
+//! [`DefaultHasher`](std::hash::DefaultHasher), whose algorithm may change
+//! between Rust releases.
+
+/// FNV-1a offset basis for 128-bit hashes.
+const FNV_OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+
+/// FNV-1a prime for 128-bit hashes.
+const FNV_PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+/// The 128-bit FNV-1a hash of `bytes`, which never changes between builds.
+pub(crate) fn stable_hash128(bytes: &[u8]) -> u128 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u128::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stable_hash128() {
+        // Reference values of 128-bit FNV-1a
+        assert_eq!(
+            format!("{:032x}", stable_hash128(b"")),
+            "6c62272e07bb014262b821756295c58d"
+        );
+        assert_eq!(
+            format!("{:032x}", stable_hash128(b"a")),
+            "d228cb696f1a8caf78912b704e4a8964"
+        );
+        assert_eq!(
+            format!("{:032x}", stable_hash128(b"foobar")),
+            "343e1662793c64bf6f0d3597ba446f18"
+        );
+    }
+}
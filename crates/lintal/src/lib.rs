@@ -0,0 +1,64 @@
+//! lintal - A fast Java linter with auto-fix support.
+//!
+//! The `lintal` binary is a thin wrapper around this library, which checks
+//! and fixes Java source held in memory:
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! use lintal::{Applicability, Linter};
+//! use lintal_checkstyle::{CheckstyleConfig, MergedConfig};
+//!
+//! let checkstyle = CheckstyleConfig::from_file(Path::new("checkstyle.xml")).unwrap();
+//! let linter = Linter::new(&MergedConfig::new(&checkstyle, None));
+//!
+//! let source = "class A { long a = 1l; }\n";
+//! for d in linter.check(source, Path::new("src/A.java")).unwrap() {
+//!     println!("[{}] {}", d.rule, d.diagnostic.kind.body);
+//! }
+//! let outcome = linter.fix(source, Path::new("src/A.java"), Applicability::Safe);
+//! ```
+//!
+//! Finding configuration files, resolving `${...}` placeholders and loading
+//! suppressions.xml are left to the caller.
+
+mod fix;
+mod linter;
+
+pub use fix::{DEFAULT_MAX_ITERATIONS, FixOutcome, SyntaxErrorStop, apply_edits, fix_until_stable};
+pub use linter::{Linter, RuleDiagnostic, effective_properties, expanded_column};
+
+pub use lintal_diagnostics::Applicability;
+
+use std::path::Path;
+
+use lintal_checkstyle::MergedConfig;
+
+/// Checkstyle module name of the nearby comment suppression filter, which
+/// is configured like a rule but applied as a suppression.
+pub const NEARBY_COMMENT_FILTER: &str = "SuppressWithNearbyCommentFilter";
+
+/// Check `source` with the rules of `config`. See [`Linter::check`].
+///
+/// Builds a new [`Linter`] on every call; create one and reuse it to check
+/// many sources.
+pub fn lint_source(
+    config: &MergedConfig,
+    source: &str,
+    path: &Path,
+) -> Option<Vec<RuleDiagnostic>> {
+    Linter::new(config).check(source, path)
+}
+
+/// Fix `source` with the rules of `config`. See [`Linter::fix`].
+///
+/// Builds a new [`Linter`] on every call; create one and reuse it to fix
+/// many sources.
+pub fn fix_source(
+    config: &MergedConfig,
+    source: &str,
+    path: &Path,
+    applicability: Applicability,
+) -> Option<FixOutcome> {
+    Linter::new(config).fix(source, path, applicability)
+}
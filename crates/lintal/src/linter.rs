@@ -0,0 +1,602 @@
+//! The configured rules together with everything needed to run them.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use lintal_checkstyle::{CheckerSettings, ConfiguredRule, MergedConfig, RuleMode};
+use lintal_diagnostics::{Applicability, Diagnostic, Severity};
+use lintal_java_cst::{CstNode, TreeWalker};
+use lintal_java_parser::{JavaParser, java_kind_id_map, java_language};
+use lintal_linter::{
+    CheckContext, FileSuppressionsConfig, NearbyCommentFilterConfig, PlainTextCommentFilterConfig,
+    Rule, RuleCoverage, RuleRegistry, SYNTAX_ERROR, SuppressionContext, SuppressionTarget,
+    UNUSED_SUPPRESSION, syntax_errors,
+};
+use lintal_source_file::SourceCode;
+use lintal_text_size::TextSize;
+use tree_sitter::Tree;
+
+use crate::{DEFAULT_MAX_ITERATIONS, FixOutcome, NEARBY_COMMENT_FILTER, fix_until_stable};
+
+// Thread-local parser to avoid repeated initialization overhead
+thread_local! {
+    static PARSER: RefCell<JavaParser> = RefCell::new(JavaParser::new());
+}
+
+struct DispatchTable {
+    per_kind: Vec<Vec<usize>>,
+    catch_all: Vec<usize>,
+    /// Bitmap of which node kinds have any rules (including catch_all)
+    has_rules: Vec<bool>,
+}
+
+impl DispatchTable {
+    fn new(rules: &[Box<dyn Rule>]) -> Self {
+        let language = java_language();
+        let kind_count = language.node_kind_count();
+        let mut per_kind: Vec<Vec<usize>> = vec![Vec::new(); kind_count];
+        let mut catch_all = Vec::new();
+        let kind_map = java_kind_id_map();
+        let mut unknown_kinds: Vec<(&'static str, &'static str)> = Vec::new();
+
+        for (idx, rule) in rules.iter().enumerate() {
+            let kinds = rule.relevant_kinds();
+            if kinds.is_empty() {
+                catch_all.push(idx);
+                continue;
+            }
+
+            for &kind in kinds {
+                if let Some(ids) = kind_map.get(kind) {
+                    for id in ids {
+                        let slot = &mut per_kind[*id as usize];
+                        if !slot.contains(&idx) {
+                            slot.push(idx);
+                        }
+                    }
+                } else {
+                    unknown_kinds.push((rule.name(), kind));
+                }
+            }
+        }
+
+        #[cfg(debug_assertions)]
+        if !unknown_kinds.is_empty() {
+            let mut seen: std::collections::HashSet<(&'static str, &'static str)> =
+                std::collections::HashSet::new();
+            for (rule, kind) in unknown_kinds {
+                if seen.insert((rule, kind)) {
+                    eprintln!(
+                        "Debug: rule '{}' references unknown node kind '{}'",
+                        rule, kind
+                    );
+                }
+            }
+        }
+
+        // Pre-compute which kinds have any rules
+        let has_catch_all = !catch_all.is_empty();
+        let has_rules: Vec<bool> = per_kind
+            .iter()
+            .map(|rules| has_catch_all || !rules.is_empty())
+            .collect();
+
+        Self {
+            per_kind,
+            catch_all,
+            has_rules,
+        }
+    }
+
+    /// Quick check if this node kind has any rules to run
+    #[inline]
+    fn has_rules_for_kind(&self, kind_id: u16) -> bool {
+        self.has_rules[kind_id as usize]
+    }
+
+    fn rule_indices_for_kind(&self, kind_id: u16) -> impl Iterator<Item = usize> + '_ {
+        self.per_kind[kind_id as usize]
+            .iter()
+            .copied()
+            .chain(self.catch_all.iter().copied())
+    }
+}
+
+/// A diagnostic paired with the rule that produced it.
+#[derive(Debug, Clone)]
+pub struct RuleDiagnostic {
+    /// The checkstyle module name of the rule, or [`SYNTAX_ERROR`] and
+    /// [`UNUSED_SUPPRESSION`] for diagnostics of lintal's own.
+    pub rule: &'static str,
+    /// The lintal.toml mode of the rule.
+    pub mode: RuleMode,
+    /// The diagnostic, with the rule's configured severity.
+    pub diagnostic: Diagnostic,
+}
+
+/// The configured rules together with everything needed to run them.
+///
+/// A linter checks and fixes source held in memory; reading files and
+/// finding configuration is left to the caller.
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+    /// The lintal.toml mode of each rule, parallel to `rules`.
+    modes: Vec<RuleMode>,
+    /// The checkstyle severity of each rule, parallel to `rules`.
+    severities: Vec<Severity>,
+    /// The checkstyle module `id` of each rule, parallel to `rules`.
+    ids: Vec<Option<String>>,
+    /// Configured modules that lintal has no rule for.
+    unsupported: Vec<String>,
+    dispatch: DispatchTable,
+    suppression_filters: Vec<PlainTextCommentFilterConfig>,
+    nearby_filters: Vec<NearbyCommentFilterConfig>,
+    file_suppressions: FileSuppressionsConfig,
+    /// Only report syntax errors for files that do not parse cleanly.
+    skip_rules_on_syntax_error: bool,
+    /// Report suppressions that did not suppress any diagnostic.
+    report_unused_suppressions: bool,
+    /// The rules whose suppressions can be reported as unused.
+    coverage: RuleCoverage,
+    /// Settings of the `Checker` module, with `basedir` made absolute.
+    settings: CheckerSettings,
+}
+
+impl Linter {
+    /// A linter running the enabled rules of `config`.
+    ///
+    /// Modules lintal has no rule for are skipped and listed by
+    /// [`Linter::unsupported_rules`]. Suppression filters are not part of
+    /// the merged configuration; add them with
+    /// [`Linter::with_suppression_filters`], [`Linter::with_nearby_filters`]
+    /// and [`Linter::with_file_suppressions`].
+    pub fn new(config: &MergedConfig) -> Self {
+        let registry = RuleRegistry::builtin();
+        let mut settings = config.checker.clone();
+        settings.basedir = settings
+            .basedir
+            .map(|basedir| std::path::absolute(&basedir).unwrap_or(basedir));
+
+        let mut rules: Vec<Box<dyn Rule>> = Vec::new();
+        let mut modes = Vec::new();
+        let mut severities = Vec::new();
+        let mut ids = Vec::new();
+        let mut unsupported = Vec::new();
+        for configured_rule in config.enabled_rules() {
+            // Filters are applied through suppressions, not run as rules
+            if configured_rule.name == NEARBY_COMMENT_FILTER {
+                continue;
+            }
+            let Some(rule) = create_rule(&registry, configured_rule, &settings) else {
+                unsupported.push(configured_rule.name.clone());
+                continue;
+            };
+            rules.push(rule);
+            modes.push(configured_rule.mode);
+            severities.push(configured_rule.severity);
+            ids.push(configured_rule.property("id").map(str::to_string));
+        }
+
+        Self::from_parts(rules, modes, severities, ids, unsupported, settings)
+    }
+
+    /// A linter running only `rule`, in fix mode and without suppressions.
+    pub fn for_rule(rule: Box<dyn Rule>) -> Self {
+        Self::from_parts(
+            vec![rule],
+            vec![RuleMode::Fix],
+            vec![Severity::Error],
+            vec![None],
+            Vec::new(),
+            CheckerSettings::default(),
+        )
+    }
+
+    fn from_parts(
+        rules: Vec<Box<dyn Rule>>,
+        modes: Vec<RuleMode>,
+        severities: Vec<Severity>,
+        ids: Vec<Option<String>>,
+        unsupported: Vec<String>,
+        settings: CheckerSettings,
+    ) -> Self {
+        let dispatch = DispatchTable::new(&rules);
+        let coverage = RuleCoverage::new(rules.iter().map(|rule| rule.name()), unsupported.clone());
+        Self {
+            rules,
+            modes,
+            severities,
+            ids,
+            unsupported,
+            dispatch,
+            suppression_filters: Vec::new(),
+            nearby_filters: Vec::new(),
+            file_suppressions: FileSuppressionsConfig::default(),
+            skip_rules_on_syntax_error: false,
+            report_unused_suppressions: false,
+            coverage,
+            settings,
+        }
+    }
+
+    /// Suppress diagnostics between `// CHECKSTYLE:OFF` style comments.
+    #[must_use]
+    pub fn with_suppression_filters(mut self, filters: Vec<PlainTextCommentFilterConfig>) -> Self {
+        self.suppression_filters = filters;
+        self
+    }
+
+    /// Suppress diagnostics near `// SUPPRESS CHECKSTYLE` style comments.
+    #[must_use]
+    pub fn with_nearby_filters(mut self, filters: Vec<NearbyCommentFilterConfig>) -> Self {
+        self.nearby_filters = filters;
+        self
+    }
+
+    /// Suppress diagnostics listed in suppressions.xml.
+    #[must_use]
+    pub fn with_file_suppressions(mut self, file_suppressions: FileSuppressionsConfig) -> Self {
+        self.file_suppressions = file_suppressions;
+        self
+    }
+
+    /// Only report syntax errors for sources that do not parse cleanly.
+    #[must_use]
+    pub fn with_skip_rules_on_syntax_error(mut self, skip: bool) -> Self {
+        self.skip_rules_on_syntax_error = skip;
+        self
+    }
+
+    /// Report suppressions that did not suppress any diagnostic, under
+    /// [`UNUSED_SUPPRESSION`].
+    #[must_use]
+    pub fn with_report_unused_suppressions(mut self, report: bool) -> Self {
+        self.report_unused_suppressions = report;
+        self
+    }
+
+    /// The names and modes of the rules, in configuration order.
+    pub fn rules(&self) -> impl Iterator<Item = (&'static str, RuleMode)> + '_ {
+        self.rules
+            .iter()
+            .zip(&self.modes)
+            .map(|(rule, mode)| (rule.name(), *mode))
+    }
+
+    /// Configured modules that lintal has no rule for.
+    pub fn unsupported_rules(&self) -> &[String] {
+        &self.unsupported
+    }
+
+    /// Settings of the `Checker` module, with `basedir` made absolute.
+    pub fn settings(&self) -> &CheckerSettings {
+        &self.settings
+    }
+
+    /// Filters for `// CHECKSTYLE:OFF` style comments.
+    pub fn suppression_filters(&self) -> &[PlainTextCommentFilterConfig] {
+        &self.suppression_filters
+    }
+
+    /// Filters for `// SUPPRESS CHECKSTYLE` style comments.
+    pub fn nearby_filters(&self) -> &[NearbyCommentFilterConfig] {
+        &self.nearby_filters
+    }
+
+    /// The suppressions.xml entries.
+    pub fn file_suppressions(&self) -> &FileSuppressionsConfig {
+        &self.file_suppressions
+    }
+
+    /// Returns true if only syntax errors are reported for sources that do
+    /// not parse cleanly.
+    pub fn skip_rules_on_syntax_error(&self) -> bool {
+        self.skip_rules_on_syntax_error
+    }
+
+    /// Returns true if unused suppressions are reported.
+    pub fn report_unused_suppressions(&self) -> bool {
+        self.report_unused_suppressions
+    }
+
+    /// The rules whose suppressions can be reported as unused.
+    pub fn coverage(&self) -> &RuleCoverage {
+        &self.coverage
+    }
+
+    /// The path of a file as it is reported and matched against
+    /// suppressions.
+    pub fn report_path(&self, path: &Path) -> PathBuf {
+        self.settings.relative_path(path)
+    }
+
+    /// Returns true if suppressions.xml suppresses every rule for the file.
+    pub fn is_file_suppressed(&self, path: &Path) -> bool {
+        self.file_suppressions
+            .is_file_fully_suppressed(&self.report_path(path).to_string_lossy())
+    }
+
+    /// Parse and check the source of the file at `path`, returning the
+    /// unsuppressed diagnostics paired with the rule that produced them.
+    ///
+    /// Syntax errors are always reported first, under [`SYNTAX_ERROR`]. The
+    /// path is only used to match suppressions and need not exist.
+    ///
+    /// Returns `None` if the source could not be parsed.
+    pub fn check(&self, source: &str, path: &Path) -> Option<Vec<RuleDiagnostic>> {
+        // Use thread-local parser to avoid repeated initialization
+        let result = PARSER.with(|parser| parser.borrow_mut().parse(source))?;
+        Some(self.check_tree(source, path, &result.tree))
+    }
+
+    /// Fix the source of the file at `path` with the fixes of rules in
+    /// `fix` mode that meet `applicability`, as `lintal fix` does.
+    ///
+    /// Returns `None` if the source could not be parsed.
+    pub fn fix(
+        &self,
+        source: &str,
+        path: &Path,
+        applicability: Applicability,
+    ) -> Option<FixOutcome> {
+        fix_until_stable(
+            self,
+            path,
+            source,
+            None,
+            applicability,
+            DEFAULT_MAX_ITERATIONS,
+            |mode| mode == RuleMode::Fix,
+        )
+    }
+
+    /// Check a source buffer that has already been parsed into `tree`.
+    pub fn check_tree(&self, source: &str, path: &Path, tree: &Tree) -> Vec<RuleDiagnostic> {
+        let path = self.report_path(path);
+        let path_str: &str = &path.to_string_lossy();
+        let ctx = CheckContext::new(source);
+        let mut suppression_ctx =
+            SuppressionContext::from_source(source, &self.suppression_filters);
+
+        let root = CstNode::new(tree.root_node(), source);
+        let mut diagnostics: Vec<RuleDiagnostic> = syntax_errors(&root, source)
+            .into_iter()
+            .map(|diagnostic| RuleDiagnostic {
+                rule: SYNTAX_ERROR,
+                mode: RuleMode::Check,
+                diagnostic,
+            })
+            .collect();
+        if self.skip_rules_on_syntax_error && !diagnostics.is_empty() {
+            return diagnostics;
+        }
+
+        // Parse @SuppressWarnings annotations for additional suppressions
+        suppression_ctx.parse_suppress_warnings(source, &root);
+        suppression_ctx.parse_nearby_comments(source, &root, &self.nearby_filters);
+
+        // Cache which rules are suppressed for this file (check once, not per-node)
+        let suppressed_rules: Option<Vec<bool>> =
+            if self.file_suppressions.is_empty() || self.report_unused_suppressions {
+                None
+            } else {
+                Some(
+                    self.rules
+                        .iter()
+                        .map(|rule| self.file_suppressions.is_suppressed(path_str, rule.name()))
+                        .collect(),
+                )
+            };
+
+        // Entries restricted by message, id, position or query are matched
+        // per diagnostic, as are nearby comment suppressions
+        let file_suppressions = self.file_suppressions.for_file(path_str, &root, source);
+
+        let has_suppressions = suppression_ctx.has_suppressions();
+        let needs_target =
+            !file_suppressions.is_empty() || suppression_ctx.has_nearby_suppressions();
+        for node in TreeWalker::new(root.inner(), source) {
+            // Quick skip for nodes with no rules
+            let kind_id = node.kind_id();
+            if !self.dispatch.has_rules_for_kind(kind_id) {
+                continue;
+            }
+            for rule_idx in self.dispatch.rule_indices_for_kind(kind_id) {
+                if suppressed_rules.as_ref().is_some_and(|mask| mask[rule_idx]) {
+                    continue;
+                }
+                let rule = &self.rules[rule_idx];
+                for diagnostic in rule.check(&ctx, &node) {
+                    // Skip suppressed diagnostics (comment-based and @SuppressWarnings).
+                    // When reporting unused suppressions, every mechanism is
+                    // consulted so that each one that applies is marked used.
+                    let mut suppressed = has_suppressions
+                        && suppression_ctx.is_suppressed(rule.name(), diagnostic.range.start());
+                    if needs_target && (!suppressed || self.report_unused_suppressions) {
+                        let start = ctx.source_code().line_column(diagnostic.range.start());
+                        let target = SuppressionTarget {
+                            rule: rule.name(),
+                            id: self.ids[rule_idx].as_deref(),
+                            message: &diagnostic.kind.body,
+                            line: start.line.get(),
+                            column: expanded_column(
+                                &ctx.source_code(),
+                                diagnostic.range.start(),
+                                self.settings.tab_width(),
+                            ),
+                            offset: diagnostic.range.start(),
+                        };
+                        let nearby = suppression_ctx.is_suppressed_nearby(&target);
+                        let listed = file_suppressions.is_suppressed(&target);
+                        suppressed |= nearby || listed;
+                    }
+                    if suppressed {
+                        continue;
+                    }
+                    diagnostics.push(RuleDiagnostic {
+                        rule: rule.name(),
+                        mode: self.modes[rule_idx],
+                        diagnostic: diagnostic.with_severity(self.severities[rule_idx]),
+                    });
+                }
+            }
+        }
+
+        if self.report_unused_suppressions {
+            diagnostics.extend(
+                suppression_ctx
+                    .unused_suppressions(&self.coverage)
+                    .into_iter()
+                    .map(|unused| RuleDiagnostic {
+                        rule: UNUSED_SUPPRESSION,
+                        mode: RuleMode::Fix,
+                        diagnostic: unused.into_diagnostic(),
+                    }),
+            );
+        }
+
+        diagnostics
+    }
+}
+
+/// Create a rule from configuration using the registry, or `None` if there
+/// is no rule for the module.
+fn create_rule(
+    registry: &RuleRegistry,
+    configured_rule: &ConfiguredRule,
+    settings: &CheckerSettings,
+) -> Option<Box<dyn Rule>> {
+    let properties = effective_properties(registry, configured_rule, settings);
+    let props = properties
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+    registry.create_rule(&configured_rule.name, &props)
+}
+
+/// The properties a rule is created with: those configured for it, and the
+/// Checker's `tabWidth` for rules that measure indentation and do not set
+/// their own.
+pub fn effective_properties(
+    registry: &RuleRegistry,
+    rule: &ConfiguredRule,
+    settings: &CheckerSettings,
+) -> HashMap<String, String> {
+    let mut properties = rule.properties.clone();
+    if let Some(tab_width) = settings.tab_width
+        && registry
+            .metadata(&rule.name)
+            .is_some_and(|metadata| metadata.property("tabWidth").is_some())
+    {
+        properties
+            .entry("tabWidth".to_string())
+            .or_insert_with(|| tab_width.to_string());
+    }
+    properties
+}
+
+/// The 1-based column of `offset`, with tabs advancing to the next multiple
+/// of `tab_width`.
+pub fn expanded_column(source_code: &SourceCode, offset: TextSize, tab_width: usize) -> usize {
+    let line = source_code.line_index(offset);
+    let before = &source_code.text()[source_code.line_start(line).to_usize()..offset.to_usize()];
+    let width = before.chars().fold(0, |width, c| {
+        if c == '\t' && tab_width > 0 {
+            width + tab_width - width % tab_width
+        } else {
+            width + 1
+        }
+    });
+    width + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use lintal_checkstyle::CheckstyleConfig;
+    use lintal_linter::FileSuppressionsConfig;
+    use lintal_source_file::LineIndex;
+
+    use super::*;
+
+    fn linter(xml: &str) -> Linter {
+        let checkstyle = CheckstyleConfig::parse(xml).unwrap();
+        Linter::new(&MergedConfig::new(&checkstyle, None))
+    }
+
+    #[test]
+    fn test_new_skips_unsupported_modules() {
+        let linter = linter(
+            r#"<module name="Checker"><module name="TreeWalker">
+                 <module name="UpperEll"/>
+                 <module name="NoSuchCheck"/>
+                 <module name="SuppressWithNearbyCommentFilter"/>
+               </module></module>"#,
+        );
+        let rules: Vec<_> = linter.rules().collect();
+        assert_eq!(rules, [("UpperEll", RuleMode::Fix)]);
+        assert_eq!(linter.unsupported_rules(), ["NoSuchCheck"]);
+    }
+
+    #[test]
+    fn test_check_and_fix() {
+        let linter = linter(
+            r#"<module name="Checker"><module name="TreeWalker">
+                 <module name="UpperEll"><property name="severity" value="warning"/></module>
+               </module></module>"#,
+        );
+        let path = Path::new("src/A.java");
+        let source = "class A {\n    long a = 1l;\n}\n";
+
+        let diagnostics = linter.check(source, path).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "UpperEll");
+        assert_eq!(diagnostics[0].diagnostic.severity, Severity::Warning);
+
+        let outcome = linter.fix(source, path, Applicability::Safe).unwrap();
+        assert_eq!(outcome.source, "class A {\n    long a = 1L;\n}\n");
+        assert_eq!(outcome.fixed, 1);
+        assert_eq!(outcome.remaining, 0);
+        assert_eq!(outcome.syntax_error, None);
+    }
+
+    #[test]
+    fn test_suppressions_are_applied() {
+        let suppressions =
+            r#"<suppressions><suppress files="B\.java" checks="UpperEll"/></suppressions>"#;
+        let (file_suppressions, errors) = FileSuppressionsConfig::parse(suppressions);
+        assert!(errors.is_empty());
+        let linter = linter(
+            r#"<module name="Checker"><module name="TreeWalker">
+                 <module name="UpperEll"/>
+               </module></module>"#,
+        )
+        .with_file_suppressions(file_suppressions);
+
+        let source = "class A {\n    @SuppressWarnings(\"checkstyle:UpperEll\")\n    long a = 1l;\n    long b = 2l;\n}\n";
+        let diagnostics = linter.check(source, Path::new("A.java")).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(
+            linter
+                .check(source, Path::new("B.java"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_expanded_column() {
+        let source = "class A {\n\tint a;\n  \tint b;\n}\n";
+        let index = LineIndex::from_source_text(source);
+        let source_code = SourceCode::new(source, &index);
+        let column = |text: &str| {
+            let offset = TextSize::try_from(source.find(text).unwrap()).unwrap();
+            expanded_column(&source_code, offset, 4)
+        };
+        assert_eq!(column("class"), 1);
+        assert_eq!(column("int a"), 5);
+        // A tab after two spaces advances to the same tab stop
+        assert_eq!(column("int b"), 5);
+        assert_eq!(column("b;"), 9);
+    }
+}
@@ -1,19 +1,46 @@
 //! lintal - A fast Java linter with auto-fix support.
 
+mod baseline;
+mod cache;
+mod changes;
+mod charset;
+mod config;
+mod diff;
+mod hash;
+mod output;
+mod rules;
+mod server;
+mod watch;
+
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use baseline::{Baseline, Fingerprint};
+use cache::Cache;
+use changes::{ChangedFiles, ChangedLines};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use colored::Colorize;
-use lintal_checkstyle::{CheckstyleConfig, ConfiguredRule, LintalConfig, MergedConfig};
-use lintal_diagnostics::{Applicability, Diagnostic, Edit};
-use lintal_java_cst::{CstNode, TreeWalker};
-use lintal_java_parser::{JavaParser, java_kind_id_map, java_language};
+use config::ConfigCommand;
+use diff::format_diff;
+use encoding_rs::Encoding;
+use lintal::{
+    DEFAULT_MAX_ITERATIONS, Linter, NEARBY_COMMENT_FILTER, SyntaxErrorStop, fix_until_stable,
+};
+use lintal_checkstyle::{
+    BUILD_FILE_NAMES, BuildPluginConfig, CheckstyleConfig, LintalConfig, MergedConfig,
+    PropertyResolver, RuleMode,
+};
+use lintal_diagnostics::{Applicability, Severity};
+use lintal_java_cst::CstNode;
+use lintal_java_parser::JavaParser;
 use lintal_linter::{
-    CheckContext, FileSuppressionsConfig, PlainTextCommentFilterConfig, Rule, RuleRegistry,
-    SuppressionContext,
+    FileSuppressionsConfig, NearbyCommentFilterConfig, PlainTextCommentFilterConfig, RuleRegistry,
+    UNUSED_SUPPRESSION,
 };
-use lintal_text_size::Ranged;
+use lintal_source_file::{LineIndex, SourceCode};
+use output::{Emitter, FileMessages, Message, OutputFormat, Statistics, StatisticsEmitter};
 use rayon::prelude::*;
+use rules::RulesFormat;
 use std::cell::RefCell;
+use std::io::{BufRead, Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use walkdir::WalkDir;
@@ -23,90 +50,78 @@ thread_local! {
     static PARSER: RefCell<JavaParser> = RefCell::new(JavaParser::new());
 }
 
-struct DispatchTable {
-    per_kind: Vec<Vec<usize>>,
-    catch_all: Vec<usize>,
-    /// Bitmap of which node kinds have any rules (including catch_all)
-    has_rules: Vec<bool>,
+/// A [`Linter`] loaded from the configuration files found for the command
+/// line, with what is needed to read sources and to watch its configuration.
+struct LoadedLinter {
+    linter: Linter,
+    /// The checkstyle.xml file the rules were loaded from.
+    config_path: Option<PathBuf>,
+    /// The suppressions.xml file that file suppressions were loaded from.
+    file_suppressions_path: Option<PathBuf>,
+    /// The pom.xml or build.gradle(.kts) checkstyle settings were read from.
+    build_file: Option<PathBuf>,
+    /// The build's source directories, which directories on the command
+    /// line are narrowed to.
+    source_roots: Vec<PathBuf>,
+    /// The build's source directories that are skipped when searching
+    /// directories on the command line.
+    excluded_roots: Vec<PathBuf>,
+    /// The encoding of source files, from the Checker's `charset`.
+    encoding: &'static Encoding,
 }
 
-impl DispatchTable {
-    fn new(rules: &[Box<dyn Rule>]) -> Self {
-        let language = java_language();
-        let kind_count = language.node_kind_count();
-        let mut per_kind: Vec<Vec<usize>> = vec![Vec::new(); kind_count];
-        let mut catch_all = Vec::new();
-        let kind_map = java_kind_id_map();
-        let mut unknown_kinds: Vec<(&'static str, &'static str)> = Vec::new();
-
-        for (idx, rule) in rules.iter().enumerate() {
-            let kinds = rule.relevant_kinds();
-            if kinds.is_empty() {
-                catch_all.push(idx);
-                continue;
-            }
-
-            for &kind in kinds {
-                if let Some(ids) = kind_map.get(kind) {
-                    for id in ids {
-                        let slot = &mut per_kind[*id as usize];
-                        if !slot.contains(&idx) {
-                            slot.push(idx);
-                        }
-                    }
-                } else {
-                    unknown_kinds.push((rule.name(), kind));
-                }
-            }
-        }
-
-        #[cfg(debug_assertions)]
-        if !unknown_kinds.is_empty() {
-            let mut seen: std::collections::HashSet<(&'static str, &'static str)> =
-                std::collections::HashSet::new();
-            for (rule, kind) in unknown_kinds {
-                if seen.insert((rule, kind)) {
-                    eprintln!(
-                        "Debug: rule '{}' references unknown node kind '{}'",
-                        rule, kind
-                    );
-                }
-            }
-        }
-
-        // Pre-compute which kinds have any rules
-        let has_catch_all = !catch_all.is_empty();
-        let has_rules: Vec<bool> = per_kind
-            .iter()
-            .map(|rules| has_catch_all || !rules.is_empty())
-            .collect();
-
+impl LoadedLinter {
+    /// Reconfigure the linter, e.g. with command line options.
+    fn map(self, f: impl FnOnce(Linter) -> Linter) -> Self {
         Self {
-            per_kind,
-            catch_all,
-            has_rules,
+            linter: f(self.linter),
+            ..self
         }
     }
+}
+
+impl std::ops::Deref for LoadedLinter {
+    type Target = Linter;
 
-    /// Quick check if this node kind has any rules to run
-    #[inline]
-    fn has_rules_for_kind(&self, kind_id: u16) -> bool {
-        self.has_rules[kind_id as usize]
+    fn deref(&self) -> &Linter {
+        &self.linter
     }
+}
 
-    fn rule_indices_for_kind(&self, kind_id: u16) -> impl Iterator<Item = usize> + '_ {
-        self.per_kind[kind_id as usize]
-            .iter()
-            .copied()
-            .chain(self.catch_all.iter().copied())
+/// Minimum severity that makes `lintal check` exit with a non-zero status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum FailOn {
+    /// Fail on any reported violation.
+    Info,
+    /// Fail on warnings and errors.
+    Warning,
+    /// Fail only on errors, like checkstyle.
+    Error,
+    /// Never fail because of violations.
+    Never,
+}
+
+impl FailOn {
+    /// Returns true if a violation with `severity` fails the run.
+    fn fails(self, severity: Severity) -> bool {
+        let threshold = match self {
+            FailOn::Info => Severity::Info,
+            FailOn::Warning => Severity::Warning,
+            FailOn::Error => Severity::Error,
+            FailOn::Never => return false,
+        };
+        severity >= threshold
     }
 }
 
 /// Result of checking a single file.
 struct FileCheckResult {
-    violations: Vec<String>,
-    violation_count: usize,
-    fixable_count: usize,
+    /// The checked file, as found on disk. `messages.path` is the path shown
+    /// in output, which may be relative to the Checker's `basedir`.
+    path: PathBuf,
+    messages: FileMessages,
+    /// Baseline fingerprints of `messages`, if requested.
+    fingerprints: Vec<Fingerprint>,
 }
 
 /// Result of fixing a single file.
@@ -114,15 +129,161 @@ struct FileFixResult {
     fixed: usize,
     unfixable: usize,
     changed: bool,
+    converged: bool,
     messages: Vec<String>,
+    /// With `--diff`, the diff of the fixes.
+    diff: Option<String>,
+    /// Fixes from `suggest` rules that were computed but not applied.
+    suggestion: Option<Suggestion>,
+    /// Set if the file was left alone because it has syntax errors.
+    syntax_error: bool,
+}
+
+/// Pending fixes from rules in `suggest` mode for a single file.
+struct Suggestion {
+    path: PathBuf,
+    /// The file contents after all other fixes.
+    original: String,
+    /// The file contents with the suggested fixes applied on top.
+    suggested: String,
+    count: usize,
 }
 
+/// Options controlling what `lintal check` reports and how.
+struct CheckOptions {
+    output_format: OutputFormat,
+    skip_rules_on_syntax_error: bool,
+    fail_on: FailOn,
+    report_unused_suppressions: bool,
+    no_cache: bool,
+    /// Hide violations recorded in this baseline file.
+    baseline: Option<PathBuf>,
+    /// Record all violations in this baseline file instead of reporting them.
+    write_baseline: Option<PathBuf>,
+    /// The name of the file read from stdin.
+    stdin_filename: Option<PathBuf>,
+    /// Keep running and re-check files as they change.
+    watch: bool,
+    /// Report counts per rule and file instead of each violation.
+    statistics: bool,
+}
+
+impl CheckOptions {
+    /// Create the emitter for the report, for files checked with `tab_width`.
+    fn emitter(&self, tab_width: usize) -> Box<dyn Emitter> {
+        if self.statistics {
+            Box::new(StatisticsEmitter {
+                json: self.output_format == OutputFormat::Json,
+            })
+        } else {
+            self.output_format.emitter(tab_width)
+        }
+    }
+
+    /// Returns true if the violation summary follows the report.
+    fn has_summary(&self) -> bool {
+        !self.statistics && self.output_format.has_summary()
+    }
+}
+
+/// Options controlling which fixes `lintal fix` applies and how.
+struct FixOptions {
+    allow_unsafe: bool,
+    diff_only: bool,
+    apply_suggestions: bool,
+    interactive: bool,
+    max_iterations: usize,
+    remove_unused_suppressions: bool,
+    /// The name of the file read from stdin.
+    stdin_filename: Option<PathBuf>,
+    /// Keep running and fix files as they change.
+    watch: bool,
+}
+
+/// The path argument that reads source from stdin.
+const STDIN_PATH: &str = "-";
+
 #[derive(Parser)]
 #[command(name = "lintal")]
 #[command(about = "A fast Java linter with auto-fix support", long_about = None)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// When to color the output
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+}
+
+/// When to color the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ColorChoice {
+    /// Color output written to a terminal, unless NO_COLOR is set.
+    Auto,
+    /// Always color the output.
+    Always,
+    /// Never color the output.
+    Never,
+}
+
+/// Options for locating and loading configuration, shared by all commands.
+#[derive(Args)]
+struct ConfigArgs {
+    /// Path to checkstyle.xml config
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Value for ${config_loc} in checkstyle.xml
+    /// (defaults to the directory containing checkstyle.xml)
+    #[arg(long)]
+    config_loc: Option<PathBuf>,
+
+    /// Define a value for ${key} placeholders in checkstyle.xml
+    #[arg(short = 'D', value_name = "KEY=VALUE", value_parser = parse_define)]
+    define: Vec<(String, String)>,
+}
+
+/// Options for limiting a command to changes relative to a git ref.
+#[derive(Args)]
+struct ChangeArgs {
+    /// Only check files changed since the merge base with this git ref,
+    /// including uncommitted and untracked files
+    #[arg(long, value_name = "REF")]
+    diff_base: Option<String>,
+
+    /// Only report (or fix) violations on lines changed since --diff-base
+    #[arg(long, requires = "diff_base")]
+    changed_lines_only: bool,
+}
+
+impl ChangeArgs {
+    /// Ask git for the changed files, if `--diff-base` was given. Git runs
+    /// in the repository containing the first of `paths`.
+    fn discover(&self, paths: &[PathBuf]) -> Result<Option<ChangedFiles>> {
+        let dir = match paths.first() {
+            Some(path) if path.is_dir() => path.as_path(),
+            Some(path) => path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .unwrap_or(Path::new(".")),
+            None => Path::new("."),
+        };
+        self.diff_base
+            .as_deref()
+            .map(|base| {
+                ChangedFiles::discover(base, dir)
+                    .with_context(|| format!("Failed to find changes since {base}"))
+            })
+            .transpose()
+    }
+}
+
+/// Parse a `-D key=value` definition.
+fn parse_define(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got '{s}'")),
+    }
 }
 
 #[derive(Subcommand)]
@@ -133,14 +294,63 @@ enum Commands {
         #[arg(required = true)]
         paths: Vec<PathBuf>,
 
-        /// Path to checkstyle.xml config
-        #[arg(short, long)]
-        config: Option<PathBuf>,
+        #[command(flatten)]
+        config: ConfigArgs,
+
+        #[command(flatten)]
+        changes: ChangeArgs,
+
+        /// Output format for violations
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output_format: OutputFormat,
+
+        /// Only report syntax errors for files that fail to parse, skipping
+        /// all other rules
+        #[arg(long)]
+        skip_rules_on_syntax_error: bool,
+
+        /// Minimum severity that causes a non-zero exit code
+        #[arg(long, value_enum, default_value_t = FailOn::Error)]
+        fail_on: FailOn,
 
-        /// Directory for resolving ${config_loc} in suppressions.xml paths
-        /// (defaults to the directory containing checkstyle.xml)
+        /// Report suppression comments, annotations and suppressions.xml
+        /// entries that no longer suppress anything
         #[arg(long)]
-        config_loc: Option<PathBuf>,
+        report_unused_suppressions: bool,
+
+        /// Always check every file instead of reusing results from
+        /// .lintal_cache
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Only report violations that are not recorded in this baseline
+        /// file, and list baseline entries that have been fixed
+        #[arg(long, value_name = "PATH")]
+        baseline: Option<PathBuf>,
+
+        /// Record all current violations in a baseline file
+        #[arg(long, value_name = "PATH", conflicts_with = "baseline")]
+        write_baseline: Option<PathBuf>,
+
+        /// The path of the file read from stdin (given as `-`), used for
+        /// config discovery, suppressions and output
+        #[arg(long, value_name = "PATH")]
+        stdin_filename: Option<PathBuf>,
+
+        /// Keep running and re-check files as they change; changes to
+        /// checkstyle.xml, lintal.toml or suppressions.xml reload the
+        /// configuration
+        #[arg(
+            long,
+            conflicts_with_all = ["diff_base", "baseline", "write_baseline", "stdin_filename"]
+        )]
+        watch: bool,
+
+        /// Report the number of violations and fixes per rule and the files
+        /// with the most violations, as a table or with `--output-format
+        /// json` as JSON
+        #[arg(long, conflicts_with = "write_baseline")]
+        statistics: bool,
     },
     /// Fix violations in files
     Fix {
@@ -148,14 +358,11 @@ enum Commands {
         #[arg(required = true)]
         paths: Vec<PathBuf>,
 
-        /// Path to checkstyle.xml config
-        #[arg(short, long)]
-        config: Option<PathBuf>,
+        #[command(flatten)]
+        config: ConfigArgs,
 
-        /// Directory for resolving ${config_loc} in suppressions.xml paths
-        /// (defaults to the directory containing checkstyle.xml)
-        #[arg(long)]
-        config_loc: Option<PathBuf>,
+        #[command(flatten)]
+        changes: ChangeArgs,
 
         /// Show diff without applying fixes
         #[arg(long)]
@@ -164,190 +371,637 @@ enum Commands {
         /// Apply unsafe fixes
         #[arg(long)]
         r#unsafe: bool,
+
+        /// Apply fixes from rules configured as "suggest" in lintal.toml
+        #[arg(long)]
+        apply_suggestions: bool,
+
+        /// Ask before applying fixes from rules configured as "suggest"
+        #[arg(short, long, conflicts_with_all = ["apply_suggestions", "diff"])]
+        interactive: bool,
+
+        /// Maximum number of fix passes per file before giving up
+        #[arg(long, default_value_t = DEFAULT_MAX_ITERATIONS)]
+        max_iterations: usize,
+
+        /// Remove suppression comments and annotation values that no longer
+        /// suppress anything
+        #[arg(long)]
+        remove_unused_suppressions: bool,
+
+        /// The path of the file read from stdin (given as `-`), used for
+        /// config discovery and suppressions; the fixed source is written to
+        /// stdout
+        #[arg(long, value_name = "PATH")]
+        stdin_filename: Option<PathBuf>,
+
+        /// Keep running and fix files as they change; changes to
+        /// checkstyle.xml, lintal.toml or suppressions.xml reload the
+        /// configuration
+        #[arg(long, conflicts_with_all = ["diff_base", "interactive", "stdin_filename"])]
+        watch: bool,
+    },
+    /// Remove the .lintal_cache directory
+    Clean,
+    /// Inspect the checkstyle.xml configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// List the supported checkstyle modules
+    Rules {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = RulesFormat::Text)]
+        output_format: RulesFormat,
+    },
+    /// Describe a rule: its properties, fix support and an example
+    Rule {
+        /// The checkstyle module name, e.g. WhitespaceAround
+        name: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = RulesFormat::Text)]
+        output_format: RulesFormat,
+    },
+    /// Run a language server over stdio, publishing diagnostics and fixes
+    /// to editors
+    Server {
+        #[command(flatten)]
+        config: ConfigArgs,
     },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    match cli.color {
+        ColorChoice::Auto => {}
+        ColorChoice::Always => colored::control::set_override(true),
+        ColorChoice::Never => colored::control::set_override(false),
+    }
 
     match cli.command {
         Commands::Check {
             paths,
             config,
-            config_loc,
-        } => run_check(&paths, config.as_deref(), config_loc.as_deref()),
+            changes,
+            output_format,
+            skip_rules_on_syntax_error,
+            fail_on,
+            report_unused_suppressions,
+            no_cache,
+            baseline,
+            write_baseline,
+            stdin_filename,
+            watch,
+            statistics,
+        } => run_check(
+            &paths,
+            &config,
+            &changes,
+            &CheckOptions {
+                output_format,
+                skip_rules_on_syntax_error,
+                fail_on,
+                report_unused_suppressions,
+                no_cache,
+                baseline,
+                write_baseline,
+                stdin_filename,
+                watch,
+                statistics,
+            },
+        ),
         Commands::Fix {
             paths,
             config,
-            config_loc,
+            changes,
             diff,
             r#unsafe: allow_unsafe,
+            apply_suggestions,
+            interactive,
+            max_iterations,
+            remove_unused_suppressions,
+            stdin_filename,
+            watch,
         } => run_fix(
             &paths,
-            config.as_deref(),
-            config_loc.as_deref(),
-            diff,
-            allow_unsafe,
+            &config,
+            &changes,
+            &FixOptions {
+                allow_unsafe,
+                diff_only: diff,
+                apply_suggestions,
+                interactive,
+                max_iterations,
+                remove_unused_suppressions,
+                stdin_filename,
+                watch,
+            },
         ),
+        Commands::Clean => run_clean(),
+        Commands::Config { command } => config::run(command),
+        Commands::Rules { output_format } => rules::list(output_format),
+        Commands::Rule {
+            name,
+            output_format,
+        } => rules::show(&name, output_format),
+        Commands::Server { config } => server::run(&config),
     }
 }
 
-/// Run the check command.
-fn run_check(
-    paths: &[PathBuf],
-    config_path: Option<&Path>,
-    config_loc: Option<&Path>,
-) -> Result<()> {
-    // Load configuration
-    let (rules, merged_config, suppression_filters, file_suppressions) =
-        load_rules(config_path, config_loc, paths)?;
-    let dispatch = DispatchTable::new(&rules);
+/// Run the clean command.
+fn run_clean() -> Result<()> {
+    if cache::clean(Path::new("."))? {
+        eprintln!("Removed {}", cache::CACHE_DIR);
+    } else {
+        eprintln!("No cache to remove");
+    }
+    Ok(())
+}
 
-    if rules.is_empty() {
+/// Load the rules for the check command and list them.
+fn load_check_rules(
+    config_args: &ConfigArgs,
+    base_paths: &[PathBuf],
+    options: &CheckOptions,
+) -> Result<(LoadedLinter, Option<MergedConfig>)> {
+    let (linter, merged_config) = load_rules(config_args, base_paths)?;
+    let linter = linter.map(|linter| {
+        linter
+            .with_skip_rules_on_syntax_error(options.skip_rules_on_syntax_error)
+            .with_report_unused_suppressions(options.report_unused_suppressions)
+    });
+
+    if linter.rules().next().is_none() {
         eprintln!("{}", "Warning: No rules configured".yellow());
     } else {
-        let rule_names: Vec<_> = merged_config
-            .as_ref()
-            .map(|c| c.enabled_rules().map(|r| r.name.as_str()).collect())
-            .unwrap_or_else(|| rules.iter().map(|r| r.name()).collect());
+        let rule_names = rule_names(&linter, merged_config.as_ref());
         eprintln!(
             "Checking with {} rule(s): {}",
             rule_names.len(),
             rule_names.join(", ")
         );
     }
+    Ok((linter, merged_config))
+}
 
-    let files = collect_java_files(paths);
+/// The names of the configured rules, in configuration order.
+fn rule_names<'a>(linter: &'a Linter, merged_config: Option<&'a MergedConfig>) -> Vec<&'a str> {
+    merged_config
+        .map(|c| {
+            c.enabled_rules()
+                .map(|r| r.name.as_str())
+                .filter(|name| *name != NEARBY_COMMENT_FILTER)
+                .collect()
+        })
+        .unwrap_or_else(|| linter.rules().map(|(name, _)| name).collect())
+}
+
+/// Run the check command.
+fn run_check(
+    paths: &[PathBuf],
+    config_args: &ConfigArgs,
+    change_args: &ChangeArgs,
+    options: &CheckOptions,
+) -> Result<()> {
+    let output_format = options.output_format;
+    if options.statistics && !matches!(output_format, OutputFormat::Text | OutputFormat::Json) {
+        anyhow::bail!("--statistics only supports the text and json output formats");
+    }
+    let stdin_path = stdin_path(paths, options.stdin_filename.as_deref())?;
+    if options.watch {
+        if stdin_path.is_some() {
+            anyhow::bail!("--watch cannot be used when reading from stdin");
+        }
+        return watch::check(paths, config_args, options);
+    }
+    // Changes are only known for files on disk
+    let changes = if stdin_path.is_some() {
+        None
+    } else {
+        change_args.discover(paths)?
+    };
+    let mut baseline = options
+        .baseline
+        .as_deref()
+        .map(Baseline::load)
+        .transpose()?;
+    let fingerprint = baseline.is_some() || options.write_baseline.is_some();
+    let keep_source = output_format.needs_source();
+
+    // Load configuration
+    let base_paths = stdin_path.as_ref().map_or(paths, std::slice::from_ref);
+    let (linter, merged_config) = load_check_rules(config_args, base_paths, options)?;
+
+    // Unused suppressions are only found by linting, so their reports cannot
+    // come from the cache
+    let cache = (!options.no_cache && !linter.report_unused_suppressions() && stdin_path.is_none())
+        .then(|| {
+            Cache::open(
+                Path::new(cache::CACHE_DIR),
+                &cache_fingerprint(&linter, merged_config.as_ref()),
+            )
+        });
+
+    let stdin_source = stdin_path
+        .as_ref()
+        .map(|_| read_stdin(linter.encoding))
+        .transpose()?;
+    let files = match &stdin_path {
+        Some(path) => vec![path.clone()],
+        None => collect_changed_java_files(paths, &linter, changes.as_ref()),
+    };
     let file_count = files.len();
     let files_processed = AtomicUsize::new(0);
 
     // Process files in parallel
-    let results: Vec<FileCheckResult> = files
+    let outcomes: Vec<Result<FileCheckResult>> = files
         .par_iter()
         .filter_map(|path| {
-            // Skip files that are fully suppressed by file-based suppressions
-            let path_str = path.to_string_lossy();
-            if file_suppressions.is_file_fully_suppressed(&path_str) {
+            // Skip files that are fully suppressed by file-based suppressions,
+            // unless every suppressions.xml entry has to be judged
+            if !linter.report_unused_suppressions() && linter.is_file_suppressed(path) {
                 files_processed.fetch_add(1, Ordering::Relaxed);
                 return None;
             }
 
-            let result = check_file(
-                path,
-                &rules,
-                &dispatch,
-                &suppression_filters,
-                &file_suppressions,
-            );
+            let changed_lines = changes
+                .as_ref()
+                .filter(|_| change_args.changed_lines_only)
+                .and_then(|changes| changes.get(path));
+            let result = match &stdin_source {
+                Some(source) => Ok(check_source(
+                    path,
+                    source,
+                    &linter,
+                    None,
+                    None,
+                    fingerprint,
+                    keep_source,
+                )),
+                None => check_file(
+                    path,
+                    &linter,
+                    cache.as_ref(),
+                    changed_lines,
+                    fingerprint,
+                    keep_source,
+                ),
+            };
             files_processed.fetch_add(1, Ordering::Relaxed);
-            result.ok()
+            Some(result)
         })
         .collect();
+    let (mut results, unreadable) = report_file_errors(outcomes);
 
-    // Aggregate and output results
-    let mut total_violations = 0;
-    let mut total_fixable = 0;
+    if let Some(cache) = cache
+        && let Err(error) = cache.save()
+    {
+        eprintln!(
+            "{}",
+            format!("Warning: Failed to write {}: {error}", cache::CACHE_DIR).yellow()
+        );
+    }
 
-    for result in results {
-        for violation in &result.violations {
-            println!("{violation}");
+    // Report files that could not be checked
+    for result in &results {
+        if let Some(error) = &result.messages.error {
+            eprintln!("{}: {}", result.messages.path.display(), error);
         }
-        total_violations += result.violation_count;
-        total_fixable += result.fixable_count;
     }
 
+    if let Some(path) = &options.write_baseline {
+        let count = write_baseline(path, &results)?;
+        eprintln!("Wrote {count} violation(s) to baseline {}", path.display());
+        if unreadable > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(baseline) = &mut baseline {
+        apply_baseline(baseline, &mut results);
+    }
+
+    let mut files: Vec<FileMessages> = results.into_iter().map(|r| r.messages).collect();
+    if linter.report_unused_suppressions()
+        && let Some(messages) = unused_file_suppressions(&linter)?
+    {
+        files.push(messages);
+    }
+    let stdout = std::io::stdout();
+    let mut writer = std::io::BufWriter::new(stdout.lock());
+    options
+        .emitter(linter.settings().tab_width())
+        .emit(&mut writer, &files)?;
+    std::io::Write::flush(&mut writer)?;
+    drop(writer);
+
     eprintln!("Checked {} files", file_count);
 
-    if total_violations > 0 {
+    // Filtered diagnostics would make every baseline entry outside the
+    // changed lines look fixed
+    if let Some(baseline) = baseline
+        && !change_args.changed_lines_only
+    {
+        report_fixed_baseline_entries(baseline);
+    }
+
+    if options.has_summary() {
+        print_check_summary(&files);
+    }
+
+    let failed = files
+        .iter()
+        .flat_map(|f| &f.messages)
+        .any(|m| options.fail_on.fails(m.severity));
+    if failed || unreadable > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Print the number of violations found, by severity.
+fn print_check_summary(files: &[FileMessages]) {
+    let total = Statistics::new(files).total;
+    if total.violations == 0 {
+        println!("{}", "No violations found".green());
+    } else if total.errors == total.violations {
         println!(
             "\nFound {} violations ({} fixable)",
-            total_violations.to_string().red(),
-            total_fixable.to_string().yellow()
+            total.violations.to_string().red(),
+            total.fixable().to_string().yellow()
         );
-        std::process::exit(1);
     } else {
-        println!("{}", "No violations found".green());
+        println!(
+            "\nFound {} violations ({} errors, {} warnings, {} info; {} fixable)",
+            total.violations.to_string().red(),
+            total.errors,
+            total.warnings,
+            total.info,
+            total.fixable().to_string().yellow()
+        );
     }
-
-    Ok(())
 }
 
-/// Run the fix command.
-fn run_fix(
-    paths: &[PathBuf],
-    config_path: Option<&Path>,
-    config_loc: Option<&Path>,
-    diff_only: bool,
-    allow_unsafe: bool,
-) -> Result<()> {
-    let (rules, merged_config, suppression_filters, file_suppressions) =
-        load_rules(config_path, config_loc, paths)?;
-    let dispatch = DispatchTable::new(&rules);
+/// Record the violations of `results` in a baseline file at `path`,
+/// returning how many were recorded.
+fn write_baseline(path: &Path, results: &[FileCheckResult]) -> Result<usize> {
+    let mut baseline = Baseline::new(path);
+    for result in results {
+        baseline.add(
+            &result.path,
+            &result.messages.messages,
+            &result.fingerprints,
+        );
+    }
+    baseline.save(path)?;
+    Ok(baseline.len())
+}
 
-    if rules.is_empty() {
-        eprintln!("{}", "Warning: No rules configured".yellow());
-        return Ok(());
+/// Hide the violations of `results` that are recorded in `baseline`.
+/// Entries are keyed on the files on disk rather than their output paths,
+/// which depend on the Checker's `basedir`.
+fn apply_baseline(baseline: &mut Baseline, results: &mut [FileCheckResult]) {
+    for result in results.iter_mut().filter(|r| r.messages.error.is_none()) {
+        baseline.filter(
+            &result.path,
+            &mut result.messages.messages,
+            &result.fingerprints,
+        );
     }
+}
 
-    let rule_names: Vec<_> = merged_config
-        .as_ref()
-        .map(|c| c.enabled_rules().map(|r| r.name.as_str()).collect())
-        .unwrap_or_else(|| rules.iter().map(|r| r.name()).collect());
+/// List the baseline entries that no longer match a violation.
+fn report_fixed_baseline_entries(baseline: Baseline) {
+    let fixed = baseline.into_fixed();
+    if fixed.is_empty() {
+        return;
+    }
+    for (file, entry) in &fixed {
+        let member = if entry.member.is_empty() {
+            String::new()
+        } else {
+            format!(" in {}", entry.member)
+        };
+        eprintln!("{file}: [{}] {}{member}", entry.rule, entry.message);
+    }
     eprintln!(
-        "Fixing with {} rule(s): {}",
-        rule_names.len(),
-        rule_names.join(", ")
+        "{} baseline entr{} fixed; run with --write-baseline to remove {}",
+        fixed.len().to_string().green(),
+        if fixed.len() == 1 { "y" } else { "ies" },
+        if fixed.len() == 1 { "it" } else { "them" }
     );
+}
 
-    let applicability = if allow_unsafe {
-        Applicability::Unsafe
+/// Report the suppressions.xml entries that applied to a checked file but
+/// never suppressed a diagnostic.
+fn unused_file_suppressions(linter: &LoadedLinter) -> Result<Option<FileMessages>> {
+    let Some(path) = &linter.file_suppressions_path else {
+        return Ok(None);
+    };
+    let unused = linter.file_suppressions().unused(linter.coverage());
+    if unused.is_empty() {
+        return Ok(None);
+    }
+
+    let xml = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let line_index = LineIndex::from_source_text(&xml);
+    let source_code = SourceCode::new(&xml, &line_index);
+    let messages = unused
+        .into_iter()
+        .map(|unused| {
+            Message::from_diagnostic(
+                UNUSED_SUPPRESSION,
+                &unused.into_diagnostic(),
+                &source_code,
+                linter.settings().tab_width(),
+            )
+        })
+        .collect();
+
+    Ok(Some(FileMessages {
+        path: path.clone(),
+        messages,
+        source: Some(xml),
+        error: None,
+    }))
+}
+
+/// Run the fix command.
+fn run_fix(
+    paths: &[PathBuf],
+    config_args: &ConfigArgs,
+    change_args: &ChangeArgs,
+    options: &FixOptions,
+) -> Result<()> {
+    let stdin_path = stdin_path(paths, options.stdin_filename.as_deref())?;
+    if options.watch {
+        if stdin_path.is_some() {
+            anyhow::bail!("--watch cannot be used when reading from stdin");
+        }
+        return watch::fix(paths, config_args, options);
+    }
+    let changes = if stdin_path.is_some() {
+        None
     } else {
-        Applicability::Safe
+        change_args.discover(paths)?
     };
+    let base_paths = stdin_path.as_ref().map_or(paths, std::slice::from_ref);
+    // Reading from stdin keeps stderr for the file's own messages
+    let (linter, applicability) =
+        load_fix_rules(config_args, base_paths, options, stdin_path.is_none())?;
 
-    let files = collect_java_files(paths);
+    if let Some(path) = stdin_path {
+        return fix_stdin(&path, &linter, applicability, options);
+    }
+
+    if linter.rules().next().is_none() {
+        return Ok(());
+    }
+
+    let files = collect_changed_java_files(paths, &linter, changes.as_ref());
 
     // Process files in parallel
-    let results: Vec<FileFixResult> = files
+    let outcomes: Vec<Result<FileFixResult>> = files
         .par_iter()
         .filter_map(|path| {
             // Skip files that are fully suppressed
-            let path_str = path.to_string_lossy();
-            if file_suppressions.is_file_fully_suppressed(&path_str) {
+            if linter.is_file_suppressed(path) {
                 return None;
             }
 
-            fix_file(
+            let changed_lines = changes
+                .as_ref()
+                .filter(|_| change_args.changed_lines_only)
+                .and_then(|changes| changes.get(path));
+            Some(fix_file(
                 path,
-                &rules,
-                &dispatch,
-                &suppression_filters,
-                &file_suppressions,
+                &linter,
                 applicability,
-                diff_only,
-            )
-            .ok()
+                options,
+                changed_lines,
+            ))
         })
         .collect();
+    let (results, failed) = report_file_errors(outcomes);
+
+    let files_changed = report_fix_results(results, failed, options, linter.encoding)?;
+    if failed > 0 || (options.diff_only && files_changed > 0) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Load the rules for the fix command, and list them if `announce` is set.
+/// Also returns the applicability of the fixes to apply.
+fn load_fix_rules(
+    config_args: &ConfigArgs,
+    base_paths: &[PathBuf],
+    options: &FixOptions,
+    announce: bool,
+) -> Result<(LoadedLinter, Applicability)> {
+    let (linter, merged_config) = load_rules(config_args, base_paths)?;
+    let linter = linter
+        .map(|linter| linter.with_report_unused_suppressions(options.remove_unused_suppressions));
+    // With --diff, fixed sources are only shown, never written
+    if !options.diff_only {
+        charset::ensure_writable(linter.encoding).with_context(|| {
+            format!(
+                "Cannot fix files in charset '{}'",
+                linter.settings().charset
+            )
+        })?;
+    }
 
-    // Aggregate and output results
+    let allow_unsafe =
+        options.allow_unsafe || merged_config.as_ref().is_some_and(|c| c.unsafe_fixes);
+    let applicability = if allow_unsafe {
+        Applicability::Unsafe
+    } else {
+        Applicability::Safe
+    };
+
+    if announce {
+        if linter.rules().next().is_none() {
+            eprintln!("{}", "Warning: No rules configured".yellow());
+        } else {
+            let rule_names = rule_names(&linter, merged_config.as_ref());
+            eprintln!(
+                "Fixing with {} rule(s): {}",
+                rule_names.len(),
+                rule_names.join(", ")
+            );
+        }
+    }
+    Ok((linter, applicability))
+}
+
+/// Print the errors of files that could not be read or written, returning
+/// the results of the others and the number of errors.
+fn report_file_errors<T>(outcomes: Vec<Result<T>>) -> (Vec<T>, usize) {
+    let mut results = Vec::with_capacity(outcomes.len());
+    let mut errors = 0;
+    for outcome in outcomes {
+        match outcome {
+            Ok(result) => results.push(result),
+            Err(error) => {
+                eprintln!("{error:#}");
+                errors += 1;
+            }
+        }
+    }
+    (results, errors)
+}
+
+/// Print the outcome of fixing files and review any suggested fixes.
+/// `failed` files could not be read or written. Returns the number of files
+/// that were (or with `--diff`, would be) changed.
+///
+/// With `--diff`, only the diffs go to stdout, so that it can be applied as
+/// a patch.
+fn report_fix_results(
+    results: Vec<FileFixResult>,
+    failed: usize,
+    options: &FixOptions,
+    encoding: &'static Encoding,
+) -> Result<usize> {
     let mut total_fixed = 0;
     let mut total_unfixable = 0;
     let mut files_changed = 0;
+    let mut files_unconverged = 0;
+    let mut files_with_syntax_errors = 0;
+    let mut suggestions = Vec::new();
 
     for result in results {
         for msg in &result.messages {
-            print!("{msg}");
+            if options.diff_only {
+                eprint!("{msg}");
+            } else {
+                print!("{msg}");
+            }
+        }
+        if let Some(diff) = &result.diff {
+            print!("{diff}");
         }
         total_fixed += result.fixed;
         total_unfixable += result.unfixable;
         if result.changed {
             files_changed += 1;
         }
+        if !result.converged {
+            files_unconverged += 1;
+        }
+        if result.syntax_error {
+            files_with_syntax_errors += 1;
+        }
+        suggestions.extend(result.suggestion);
     }
 
-    if diff_only {
-        println!(
+    if options.diff_only {
+        eprintln!(
             "\n{} fix(es) available in {} file(s)",
             total_fixed.to_string().green(),
             files_changed
@@ -362,325 +1016,357 @@ fn run_fix(
         println!("{}", "No fixes to apply".green());
     }
 
-    if total_unfixable > 0 {
+    if options.diff_only && !suggestions.is_empty() {
+        let count: usize = suggestions.iter().map(|s| s.count).sum();
         eprintln!(
-            "{} violation(s) could not be fixed automatically",
-            total_unfixable.to_string().yellow()
+            "{} suggested fix(es) not shown (use --apply-suggestions to include them)",
+            count.to_string().yellow()
         );
+    } else if !suggestions.is_empty() {
+        review_suggestions(&suggestions, options.interactive, encoding)?;
     }
 
-    Ok(())
-}
-
-/// Fix violations in a single file.
-fn fix_file(
-    path: &PathBuf,
-    rules: &[Box<dyn Rule>],
-    dispatch: &DispatchTable,
-    suppression_filters: &[PlainTextCommentFilterConfig],
-    file_suppressions: &FileSuppressionsConfig,
-    applicability: Applicability,
-    diff_only: bool,
-) -> Result<FileFixResult> {
-    let source = std::fs::read_to_string(path)
-        .with_context(|| format!("Failed to read {}", path.display()))?;
-
-    // Use thread-local parser to avoid repeated initialization
-    let parse_result = PARSER.with(|parser| parser.borrow_mut().parse(&source));
-    let Some(result) = parse_result else {
-        return Ok(FileFixResult {
-            fixed: 0,
-            unfixable: 0,
-            changed: false,
-            messages: vec![format!("{}: Failed to parse\n", path.display())],
-        });
-    };
-
-    let ctx = CheckContext::new(&source);
-    let mut suppression_ctx = SuppressionContext::from_source(&source, suppression_filters);
-
-    // Parse @SuppressWarnings annotations for additional suppressions
-    let root = CstNode::new(result.tree.root_node(), &source);
-    suppression_ctx.parse_suppress_warnings(&source, &root);
-
-    let path_str = path.to_string_lossy();
-
-    // Cache which rules are suppressed for this file (check once, not per-node)
-    let suppressed_rules: Option<Vec<bool>> = if file_suppressions.is_empty() {
-        None
-    } else {
-        Some(
-            rules
-                .iter()
-                .map(|rule| file_suppressions.is_suppressed(&path_str, rule.name()))
-                .collect(),
-        )
-    };
-
-    // Collect all diagnostics, filtering out suppressed ones
-    let mut diagnostics: Vec<Diagnostic> = Vec::new();
-    let has_suppressions = suppression_ctx.has_suppressions();
-    for node in TreeWalker::new(root.inner(), &source) {
-        // Quick skip for nodes with no rules
-        let kind_id = node.kind_id();
-        if !dispatch.has_rules_for_kind(kind_id) {
-            continue;
-        }
-        for rule_idx in dispatch.rule_indices_for_kind(kind_id) {
-            if suppressed_rules.as_ref().is_some_and(|mask| mask[rule_idx]) {
-                continue;
-            }
-            let rule = &rules[rule_idx];
-            for diagnostic in rule.check(&ctx, &node) {
-                if has_suppressions
-                    && suppression_ctx.is_suppressed(rule.name(), diagnostic.range.start())
-                {
-                    continue;
-                }
-                diagnostics.push(diagnostic);
-            }
-        }
-    }
-
-    if diagnostics.is_empty() {
-        return Ok(FileFixResult {
-            fixed: 0,
-            unfixable: 0,
-            changed: false,
-            messages: vec![],
-        });
+    if total_unfixable > 0 {
+        eprintln!(
+            "{} violation(s) could not be fixed automatically",
+            total_unfixable.to_string().yellow()
+        );
     }
 
-    // Collect applicable fixes
-    let mut edits: Vec<Edit> = Vec::new();
-    let mut fixed = 0;
-    let mut unfixable = 0;
-
-    for diagnostic in &diagnostics {
-        if let Some(fix) = &diagnostic.fix {
-            if fix.applies(applicability) {
-                edits.extend(fix.edits().iter().cloned());
-                fixed += 1;
-            } else {
-                unfixable += 1;
-            }
-        } else {
-            unfixable += 1;
-        }
+    if files_unconverged > 0 {
+        eprintln!(
+            "{} file(s) failed to converge after {} iteration(s)",
+            files_unconverged.to_string().red(),
+            options.max_iterations
+        );
     }
 
-    if edits.is_empty() {
-        return Ok(FileFixResult {
-            fixed: 0,
-            unfixable,
-            changed: false,
-            messages: vec![],
-        });
+    if files_with_syntax_errors > 0 {
+        eprintln!(
+            "{} file(s) with syntax errors were not fixed",
+            files_with_syntax_errors.to_string().red()
+        );
     }
 
-    // Sort edits by position (descending) to apply from end to start
-    edits.sort_by_key(|e| std::cmp::Reverse(e.start()));
+    if failed > 0 {
+        eprintln!(
+            "{} file(s) could not be read or written",
+            failed.to_string().red()
+        );
+    }
 
-    // Remove overlapping edits (keep first one, which is the one with highest start)
-    let edits = remove_overlapping_edits(edits);
+    Ok(files_changed)
+}
 
-    // Apply edits to source
-    let fixed_source = apply_edits(&source, &edits);
+/// Fix source read from stdin. The fixed source, or with `--diff` its diff,
+/// is written to stdout and everything else to stderr.
+fn fix_stdin(
+    path: &Path,
+    linter: &LoadedLinter,
+    applicability: Applicability,
+    options: &FixOptions,
+) -> Result<()> {
+    if options.interactive {
+        anyhow::bail!("--interactive cannot be used when reading from stdin");
+    }
 
-    let mut messages = Vec::new();
+    let source = read_stdin(linter.encoding)?;
+    let (result, fixed) = fix_source(path, &source, linter, applicability, options, None);
+    for msg in &result.messages {
+        eprint!("{msg}");
+    }
+    if let Some(suggestion) = &result.suggestion {
+        eprintln!(
+            "{} suggested fix(es) not applied (use --apply-suggestions to apply)",
+            suggestion.count.to_string().yellow()
+        );
+    }
 
-    if diff_only {
-        // Buffer diff output
-        messages.push(format_diff(path, &source, &fixed_source));
-    } else {
-        // Write fixed source
-        std::fs::write(path, &fixed_source)
-            .with_context(|| format!("Failed to write {}", path.display()))?;
-        messages.push(format!("{}: {} fix(es) applied\n", path.display(), fixed));
+    let mut stdout = std::io::stdout().lock();
+    if !options.diff_only {
+        stdout.write_all(&charset::encode(&fixed, linter.encoding)?)?;
+    } else if result.changed {
+        stdout.write_all(format_diff(path, &source, &fixed).as_bytes())?;
     }
+    stdout.flush()?;
 
-    Ok(FileFixResult {
-        fixed,
-        unfixable,
-        changed: true,
-        messages,
-    })
+    eprintln!(
+        "{} fix(es) {}",
+        result.fixed,
+        if options.diff_only {
+            "available"
+        } else {
+            "applied"
+        }
+    );
+    if options.diff_only && result.changed {
+        std::process::exit(1);
+    }
+    Ok(())
 }
 
-/// Remove overlapping edits, keeping the first one (highest start position).
-fn remove_overlapping_edits(edits: Vec<Edit>) -> Vec<Edit> {
-    let mut result: Vec<Edit> = Vec::new();
-
-    for edit in edits {
-        // Check if this edit overlaps with any already accepted edit
-        let overlaps = result.iter().any(|existing| {
-            // Since edits are sorted descending by start, existing edits have higher starts
-            // An overlap occurs if edit.end > existing.start
-            edit.end() > existing.start()
-        });
-
-        if !overlaps {
-            result.push(edit);
+/// Resolve the file name of the source read from stdin, if `-` is among
+/// `paths`.
+fn stdin_path(paths: &[PathBuf], stdin_filename: Option<&Path>) -> Result<Option<PathBuf>> {
+    if !paths.iter().any(|path| path.as_os_str() == STDIN_PATH) {
+        if stdin_filename.is_some() {
+            anyhow::bail!("--stdin-filename requires `{STDIN_PATH}` as the path");
         }
+        return Ok(None);
+    }
+    if paths.len() > 1 {
+        anyhow::bail!("`{STDIN_PATH}` cannot be combined with other paths");
     }
+    Ok(Some(stdin_filename.map_or_else(
+        || PathBuf::from(STDIN_PATH),
+        Path::to_path_buf,
+    )))
+}
 
-    result
+fn read_stdin(encoding: &'static Encoding) -> Result<String> {
+    let mut bytes = Vec::new();
+    std::io::stdin()
+        .read_to_end(&mut bytes)
+        .context("Failed to read stdin")?;
+    charset::decode(&bytes, encoding).context("Failed to read stdin")
 }
 
-/// Apply edits to source text.
-fn apply_edits(source: &str, edits: &[Edit]) -> String {
-    let mut result = source.to_string();
+/// Show the diff of each pending suggestion, and in interactive mode ask
+/// whether to apply it.
+fn review_suggestions(
+    suggestions: &[Suggestion],
+    interactive: bool,
+    encoding: &'static Encoding,
+) -> Result<()> {
+    let stdin = std::io::stdin();
+    let mut input = stdin.lock();
+    let mut total_pending = 0;
+
+    for suggestion in suggestions {
+        print!(
+            "\n{}",
+            format_diff(
+                &suggestion.path,
+                &suggestion.original,
+                &suggestion.suggested
+            )
+        );
+
+        if !interactive {
+            total_pending += suggestion.count;
+            continue;
+        }
 
-    // Edits are sorted descending by start position, so we can apply them in order
-    for edit in edits {
-        let start = usize::from(edit.start());
-        let end = usize::from(edit.end());
-        let content = edit.content().unwrap_or("");
+        print!(
+            "Apply {} suggested fix(es) to {}? [y/N] ",
+            suggestion.count,
+            suggestion.path.display()
+        );
+        std::io::stdout().flush()?;
+
+        let mut answer = String::new();
+        input.read_line(&mut answer)?;
+        if matches!(answer.trim(), "y" | "Y" | "yes") {
+            charset::write_source(&suggestion.path, &suggestion.suggested, encoding)?;
+            println!(
+                "{}: {} suggested fix(es) applied",
+                suggestion.path.display(),
+                suggestion.count
+            );
+        } else {
+            total_pending += suggestion.count;
+        }
+    }
 
-        result.replace_range(start..end, content);
+    if total_pending > 0 {
+        println!(
+            "\n{} suggested fix(es) not applied (use --apply-suggestions or --interactive to apply)",
+            total_pending.to_string().yellow()
+        );
     }
 
-    result
+    Ok(())
 }
 
-/// Print a unified diff between original and fixed source.
-fn format_diff(path: &Path, original: &str, fixed: &str) -> String {
-    use std::fmt::Write;
-
-    let mut output = String::new();
-    writeln!(output, "--- a/{}", path.display()).unwrap();
-    writeln!(output, "+++ b/{}", path.display()).unwrap();
+/// Fix violations in a single file, writing the fixed source back or
+/// buffering its diff with `--diff`.
+fn fix_file(
+    path: &Path,
+    linter: &LoadedLinter,
+    applicability: Applicability,
+    options: &FixOptions,
+    changed_lines: Option<&ChangedLines>,
+) -> Result<FileFixResult> {
+    let source = charset::read_source(path, linter.encoding)?;
+    let (mut result, fixed) =
+        fix_source(path, &source, linter, applicability, options, changed_lines);
+    if !result.changed {
+        return Ok(result);
+    }
 
-    let original_lines: Vec<&str> = original.lines().collect();
-    let fixed_lines: Vec<&str> = fixed.lines().collect();
+    if options.diff_only {
+        result.diff = Some(format_diff(path, &source, &fixed));
+    } else {
+        // Write fixed source
+        charset::write_source(path, &fixed, linter.encoding)?;
+        result.messages.push(format!(
+            "{}: {} fix(es) applied\n",
+            path.display(),
+            result.fixed
+        ));
+    }
+    Ok(result)
+}
 
-    // Simple line-by-line diff
-    let mut i = 0;
-    let mut j = 0;
-    let mut in_hunk = false;
-    let mut hunk_start_orig = 0;
-    let mut hunk_start_fixed = 0;
-    let mut hunk_lines: Vec<String> = Vec::new();
+/// Fix violations in the source of the file at `path`, returning the result
+/// and the fixed source.
+///
+/// Sources with syntax errors are left untouched. Only fixes from rules in
+/// `fix` mode are applied, plus rules in `suggest` mode when
+/// `--apply-suggestions` is set. Otherwise, fixes from `suggest`
+/// rules are computed on top of the fixed source and returned as a
+/// [`Suggestion`] for review.
+///
+/// With `changed_lines`, only violations on those lines are fixed.
+fn fix_source(
+    path: &Path,
+    source: &str,
+    linter: &LoadedLinter,
+    applicability: Applicability,
+    options: &FixOptions,
+    changed_lines: Option<&ChangedLines>,
+) -> (FileFixResult, String) {
+    let Some(outcome) = fix_until_stable(
+        linter,
+        path,
+        source,
+        changed_lines.and_then(|lines| lines.regions(source)),
+        applicability,
+        options.max_iterations,
+        |mode| mode == RuleMode::Fix || (options.apply_suggestions && mode == RuleMode::Suggest),
+    ) else {
+        let result = FileFixResult {
+            fixed: 0,
+            unfixable: 0,
+            changed: false,
+            converged: true,
+            messages: vec![format!("{}: Failed to parse\n", path.display())],
+            diff: None,
+            suggestion: None,
+            syntax_error: false,
+        };
+        return (result, source.to_string());
+    };
 
-    while i < original_lines.len() || j < fixed_lines.len() {
-        let orig_line = original_lines.get(i);
-        let fixed_line = fixed_lines.get(j);
+    let converged = outcome.pending_rules.is_empty();
+    let mut messages = Vec::new();
 
-        match (orig_line, fixed_line) {
-            (Some(o), Some(f)) if o == f => {
-                // Lines match
-                if in_hunk {
-                    hunk_lines.push(format!(" {}", o));
-                }
-                i += 1;
-                j += 1;
-            }
-            (Some(o), Some(f)) => {
-                // Lines differ
-                if !in_hunk {
-                    in_hunk = true;
-                    hunk_start_orig = i + 1;
-                    hunk_start_fixed = j + 1;
-                }
-                hunk_lines.push(format!("{}{}", "-".red(), o));
-                hunk_lines.push(format!("{}{}", "+".green(), f));
-                i += 1;
-                j += 1;
-            }
-            (Some(o), None) => {
-                // Original has extra line
-                if !in_hunk {
-                    in_hunk = true;
-                    hunk_start_orig = i + 1;
-                    hunk_start_fixed = j + 1;
-                }
-                hunk_lines.push(format!("{}{}", "-".red(), o));
-                i += 1;
-            }
-            (None, Some(f)) => {
-                // Fixed has extra line
-                if !in_hunk {
-                    in_hunk = true;
-                    hunk_start_orig = i + 1;
-                    hunk_start_fixed = j + 1;
-                }
-                hunk_lines.push(format!("{}{}", "+".green(), f));
-                j += 1;
-            }
-            (None, None) => break,
+    match &outcome.syntax_error {
+        Some(SyntaxErrorStop::InSource) => {
+            messages.push(format!(
+                "{}: not fixed, file has syntax errors\n",
+                path.display()
+            ));
+            let result = FileFixResult {
+                fixed: 0,
+                unfixable: outcome.remaining,
+                changed: false,
+                converged,
+                messages,
+                diff: None,
+                suggestion: None,
+                syntax_error: true,
+            };
+            return (result, outcome.source);
         }
-
-        // Flush hunk if we have context after changes
-        if in_hunk && hunk_lines.len() > 6 {
-            let context_count = hunk_lines
-                .iter()
-                .rev()
-                .take_while(|l| l.starts_with(' '))
-                .count();
-            if context_count >= 3 {
-                // Flush the hunk
-                writeln!(
-                    output,
-                    "@@ -{},{} +{},{} @@",
-                    hunk_start_orig,
-                    hunk_lines.iter().filter(|l| !l.starts_with('+')).count(),
-                    hunk_start_fixed,
-                    hunk_lines.iter().filter(|l| !l.starts_with('-')).count()
-                )
-                .unwrap();
-                for line in &hunk_lines {
-                    writeln!(output, "{}", line).unwrap();
-                }
-                hunk_lines.clear();
-                in_hunk = false;
-            }
+        Some(SyntaxErrorStop::Introduced(rules)) => {
+            messages.push(format!(
+                "{}: reverted fixes that introduced a syntax error, from: {}\n",
+                path.display(),
+                rules.join(", ")
+            ));
         }
+        None => {}
     }
 
-    // Flush remaining hunk
-    if !hunk_lines.is_empty() {
-        writeln!(
-            output,
-            "@@ -{},{} +{},{} @@",
-            hunk_start_orig,
-            hunk_lines.iter().filter(|l| !l.starts_with('+')).count(),
-            hunk_start_fixed,
-            hunk_lines.iter().filter(|l| !l.starts_with('-')).count()
-        )
-        .unwrap();
-        for line in &hunk_lines {
-            writeln!(output, "{}", line).unwrap();
-        }
+    if !converged {
+        messages.push(format!(
+            "{}: failed to converge after {} iteration(s), fixes still pending for: {}\n",
+            path.display(),
+            options.max_iterations,
+            outcome.pending_rules.join(", ")
+        ));
     }
 
-    output
+    let suggestion = if options.apply_suggestions
+        || !linter.rules().any(|(_, mode)| mode == RuleMode::Suggest)
+    {
+        None
+    } else {
+        fix_until_stable(
+            linter,
+            path,
+            &outcome.source,
+            outcome.scope.clone(),
+            applicability,
+            options.max_iterations,
+            |mode| mode == RuleMode::Fix || mode == RuleMode::Suggest,
+        )
+        .filter(|suggested| suggested.fixed > 0 && suggested.source != outcome.source)
+        .map(|suggested| Suggestion {
+            path: path.to_path_buf(),
+            original: outcome.source.clone(),
+            suggested: suggested.source,
+            count: suggested.fixed,
+        })
+    };
+
+    let unfixable = outcome
+        .remaining
+        .saturating_sub(suggestion.as_ref().map_or(0, |s| s.count));
+
+    let changed = outcome.source != source;
+    let result = FileFixResult {
+        fixed: if changed { outcome.fixed } else { 0 },
+        unfixable,
+        changed,
+        converged,
+        messages,
+        diff: None,
+        suggestion,
+        syntax_error: false,
+    };
+    (result, outcome.source)
 }
 
 /// Load rules from configuration or use defaults.
-#[allow(clippy::type_complexity)]
+///
+/// Each rule is paired with its lintal.toml mode so that `fix` can tell which
+/// rules may edit files.
 fn load_rules(
-    config_path: Option<&Path>,
-    config_loc: Option<&Path>,
+    config_args: &ConfigArgs,
     base_paths: &[PathBuf],
-) -> Result<(
-    Vec<Box<dyn Rule>>,
-    Option<MergedConfig>,
-    Vec<PlainTextCommentFilterConfig>,
-    FileSuppressionsConfig,
-)> {
-    let registry = RuleRegistry::builtin();
-
+) -> Result<(LoadedLinter, Option<MergedConfig>)> {
     // Try to load configuration
-    let (merged_config, suppression_filters, file_suppressions) =
-        load_config(config_path, config_loc, base_paths)?;
+    let LoadedConfig {
+        merged_config,
+        suppression_filters,
+        nearby_filters,
+        file_suppressions,
+        file_suppressions_path,
+        config_path,
+        build,
+        ..
+    } = load_config(config_args, base_paths)?;
 
-    let rules: Vec<Box<dyn Rule>> = match &merged_config {
+    let linter = match &merged_config {
         Some(config) => {
-            // Create rules from configuration
-            config
-                .enabled_rules()
-                .filter_map(|configured_rule| create_rule_from_config(&registry, configured_rule))
-                .collect()
+            warn_invalid_properties(config);
+            let linter = Linter::new(config);
+            for name in linter.unsupported_rules() {
+                eprintln!("{}: Unknown rule '{name}', skipping", "Warning".yellow());
+            }
+            linter
         }
         None => {
             // No config found, use default WhitespaceAround
@@ -688,61 +1374,223 @@ fn load_rules(
                 "{}",
                 "No checkstyle.xml found, using default WhitespaceAround rule".yellow()
             );
-            vec![Box::new(lintal_linter::rules::WhitespaceAround::default())]
+            Linter::for_rule(Box::new(lintal_linter::rules::WhitespaceAround::default()))
         }
     };
+    let encoding = charset::for_name(&linter.settings().charset).with_context(|| {
+        format!(
+            "Invalid charset in {}",
+            config_path.as_deref().unwrap_or(Path::new("")).display()
+        )
+    })?;
+
+    let linter = LoadedLinter {
+        linter: linter
+            .with_suppression_filters(suppression_filters)
+            .with_nearby_filters(nearby_filters)
+            .with_file_suppressions(file_suppressions),
+        config_path,
+        file_suppressions_path,
+        build_file: build.as_ref().map(|build| build.build_file.clone()),
+        source_roots: build
+            .as_ref()
+            .map(|build| build.source_roots.clone())
+            .unwrap_or_default(),
+        excluded_roots: build.map(|build| build.excluded_roots).unwrap_or_default(),
+        encoding,
+    };
+    Ok((linter, merged_config))
+}
+
+/// Warn about module properties whose values rules cannot use.
+///
+/// Rules fall back to defaults for such values, so say so; properties
+/// lintal merely ignores are left to `lintal config check`.
+fn warn_invalid_properties(config: &MergedConfig) {
+    let registry = RuleRegistry::builtin();
+    for rule in config.enabled_rules() {
+        let Some(metadata) = registry.metadata(&rule.name) else {
+            continue;
+        };
+        for issue in metadata.validate(&rule.properties_ref()) {
+            if issue.is_error() {
+                eprintln!("{}: {}: {issue}", "Warning".yellow(), rule.name);
+            }
+        }
+    }
+}
+
+/// Describe everything besides a file's contents that affects its check
+/// results, so that cached results are only reused under the same settings.
+fn cache_fingerprint(linter: &LoadedLinter, merged_config: Option<&MergedConfig>) -> String {
+    let mut fingerprint = String::new();
+    if let Some(config) = merged_config {
+        for rule in config.enabled_rules() {
+            let mut properties: Vec<_> = rule.properties.iter().collect();
+            properties.sort();
+            fingerprint.push_str(&format!(
+                "{} {:?} {:?} {properties:?}\n",
+                rule.name, rule.mode, rule.severity
+            ));
+        }
+    } else {
+        let names: Vec<_> = linter.rules().map(|(name, _)| name).collect();
+        fingerprint.push_str(&format!("{names:?}\n"));
+    }
+    fingerprint.push_str(&format!(
+        "{:?}\n{:?}\n{:?}\nskip_rules_on_syntax_error={}\n",
+        linter.settings(),
+        linter.suppression_filters(),
+        linter.nearby_filters(),
+        linter.skip_rules_on_syntax_error()
+    ));
+    if let Some(path) = &linter.file_suppressions_path {
+        fingerprint.push_str(&std::fs::read_to_string(path).unwrap_or_default());
+    }
+    fingerprint
+}
 
-    Ok((rules, merged_config, suppression_filters, file_suppressions))
+/// Configuration loaded from checkstyle.xml, lintal.toml and the build file.
+#[derive(Default)]
+struct LoadedConfig {
+    merged_config: Option<MergedConfig>,
+    suppression_filters: Vec<PlainTextCommentFilterConfig>,
+    nearby_filters: Vec<NearbyCommentFilterConfig>,
+    file_suppressions: FileSuppressionsConfig,
+    file_suppressions_path: Option<PathBuf>,
+    config_path: Option<PathBuf>,
+    lintal_path: Option<PathBuf>,
+    /// The checkstyle.xml the configuration was merged from.
+    checkstyle: Option<CheckstyleConfig>,
+    /// Checkstyle settings of the Maven or Gradle build.
+    build: Option<BuildPluginConfig>,
 }
 
 /// Load merged configuration from files.
-fn load_config(
-    config_path: Option<&Path>,
-    config_loc: Option<&Path>,
-    base_paths: &[PathBuf],
-) -> Result<(
-    Option<MergedConfig>,
-    Vec<PlainTextCommentFilterConfig>,
-    FileSuppressionsConfig,
-)> {
+fn load_config(config_args: &ConfigArgs, base_paths: &[PathBuf]) -> Result<LoadedConfig> {
     // Load lintal.toml if it exists
-    let lintal = find_lintal_config();
+    let (lintal, lintal_path) = find_lintal_config().unzip();
+    let build = find_build_config(base_paths);
 
     // Determine checkstyle.xml path
-    let checkstyle_path = config_path
-        .map(PathBuf::from)
+    let checkstyle_path = config_args
+        .config
+        .clone()
         .or_else(|| {
             lintal
                 .as_ref()
                 .and_then(|l| l.checkstyle.config.clone().map(PathBuf::from))
         })
+        .or_else(|| {
+            build
+                .as_ref()
+                .and_then(|build| build.config_file.clone())
+                .filter(|path| path.exists())
+        })
         .or_else(|| find_checkstyle_config(base_paths));
 
     let Some(checkstyle_path) = checkstyle_path else {
-        return Ok((None, vec![], FileSuppressionsConfig::new()));
+        return Ok(LoadedConfig {
+            lintal_path,
+            build,
+            ..LoadedConfig::default()
+        });
     };
 
     if !checkstyle_path.exists() {
         anyhow::bail!("Checkstyle config not found: {}", checkstyle_path.display());
     }
 
-    let checkstyle = CheckstyleConfig::from_file(&checkstyle_path)
+    let mut checkstyle = CheckstyleConfig::from_file(&checkstyle_path)
         .with_context(|| format!("Failed to parse {}", checkstyle_path.display()))?;
 
     eprintln!("Loaded config from: {}", checkstyle_path.display());
 
+    // Expand ${...} placeholders in every module property
+    let resolver = property_resolver(
+        config_args,
+        lintal.as_ref(),
+        build.as_ref(),
+        &checkstyle_path,
+    );
+    if let Err(errors) = resolver.resolve(&mut checkstyle) {
+        let details: Vec<String> = errors.iter().map(|e| format!("  {e}")).collect();
+        anyhow::bail!(
+            "Unresolved properties in {}:\n{}\nDefine them with -D key=value or in the [properties] table of lintal.toml",
+            checkstyle_path.display(),
+            details.join("\n")
+        );
+    }
+
     // Extract suppression filters from config
     let suppression_filters = extract_suppression_filters(&checkstyle);
+    let nearby_filters = extract_nearby_filters(&checkstyle);
 
-    // Extract file-based suppressions
-    // Use config_loc if provided, otherwise use the directory containing checkstyle.xml
-    let file_suppressions = extract_file_suppressions(&checkstyle, &checkstyle_path, config_loc);
+    // Extract file-based suppressions, which the Maven plugin can also
+    // configure outside checkstyle.xml
+    let (file_suppressions, file_suppressions_path) = extract_file_suppressions(&checkstyle)
+        .or_else(|| {
+            build
+                .as_ref()
+                .and_then(|build| build.suppressions_file.as_deref())
+                .and_then(load_file_suppressions)
+        })
+        .map_or_else(
+            || (FileSuppressionsConfig::new(), None),
+            |(c, p)| (c, Some(p)),
+        );
 
-    Ok((
-        Some(MergedConfig::new(&checkstyle, lintal.as_ref())),
+    Ok(LoadedConfig {
+        merged_config: Some(MergedConfig::new(&checkstyle, lintal.as_ref())),
+        checkstyle: Some(checkstyle),
         suppression_filters,
+        nearby_filters,
         file_suppressions,
-    ))
+        file_suppressions_path,
+        config_path: Some(checkstyle_path),
+        lintal_path,
+        build,
+    })
+}
+
+/// Build the resolver for `${...}` placeholders in checkstyle.xml.
+///
+/// `config_loc` defaults to the build's config directory or the directory
+/// containing checkstyle.xml, and `basedir` to the directory of the build
+/// file or the current directory, as in the Maven and Gradle plugins.
+/// Properties from lintal.toml override those from the build file.
+fn property_resolver(
+    config_args: &ConfigArgs,
+    lintal: Option<&LintalConfig>,
+    build: Option<&BuildPluginConfig>,
+    checkstyle_path: &Path,
+) -> PropertyResolver {
+    let config_loc = config_args
+        .config_loc
+        .clone()
+        .or_else(|| build.and_then(|build| build.config_directory.clone()))
+        .or_else(|| checkstyle_path.parent().map(Path::to_path_buf))
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| PathBuf::from("."));
+    let basedir = build
+        .and_then(|build| build.build_file.parent())
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .map_or_else(
+            || std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            Path::to_path_buf,
+        );
+
+    let mut resolver = PropertyResolver::new()
+        .with_defines(config_args.define.iter().cloned())
+        .with_builtin("config_loc", config_loc.to_string_lossy())
+        .with_builtin("basedir", basedir.to_string_lossy());
+    if let Some(build) = build {
+        resolver = resolver.with_config(&build.properties);
+    }
+    if let Some(lintal) = lintal {
+        resolver = resolver.with_config(&lintal.properties);
+    }
+    resolver
 }
 
 /// Extract suppression filters from checkstyle config.
@@ -764,50 +1612,70 @@ fn extract_suppression_filters(config: &CheckstyleConfig) -> Vec<PlainTextCommen
     filters
 }
 
+/// Extract nearby comment filters from checkstyle config.
+/// `SuppressWithNearbyCommentFilter` lives under TreeWalker, but older configs
+/// also place it directly under Checker.
+fn extract_nearby_filters(config: &CheckstyleConfig) -> Vec<NearbyCommentFilterConfig> {
+    config
+        .modules
+        .iter()
+        .chain(config.rules())
+        .filter(|module| module.name == NEARBY_COMMENT_FILTER)
+        .filter_map(|module| {
+            let filter = create_nearby_filter_from_module(module);
+            if filter.is_none() {
+                eprintln!(
+                    "{}: Invalid commentFormat in {}, skipping",
+                    "Warning".yellow(),
+                    NEARBY_COMMENT_FILTER
+                );
+            }
+            filter
+        })
+        .collect()
+}
+
 /// Extract file-based suppressions from checkstyle config.
-/// Looks for SuppressionFilter module and loads the referenced suppressions.xml file.
+/// Looks for SuppressionFilter module and loads the referenced suppressions.xml file,
+/// returning it together with its path.
 fn extract_file_suppressions(
     config: &CheckstyleConfig,
-    checkstyle_path: &Path,
-    config_loc: Option<&Path>,
-) -> FileSuppressionsConfig {
+) -> Option<(FileSuppressionsConfig, PathBuf)> {
     // Look for SuppressionFilter module
     for module in &config.modules {
         if module.name == "SuppressionFilter"
             && let Some(file_prop) = module.property("file")
+            && let Some(suppressions) = load_file_suppressions(Path::new(file_prop))
         {
-            // Resolve ${config_loc}:
-            // - If --config-loc was provided, use that directory
-            // - Otherwise, use the directory containing checkstyle.xml
-            let config_dir = config_loc
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_else(|| {
-                    checkstyle_path
-                        .parent()
-                        .map(|p| p.to_string_lossy().to_string())
-                        .unwrap_or_else(|| ".".to_string())
-                });
-
-            let resolved_path = file_prop.replace("${config_loc}", &config_dir);
-            let suppressions_path = Path::new(&resolved_path);
-
-            if suppressions_path.exists()
-                && let Ok(xml) = std::fs::read_to_string(suppressions_path)
-            {
-                let config = FileSuppressionsConfig::from_xml(&xml);
-                if !config.is_empty() {
-                    eprintln!(
-                        "Loaded {} file suppression(s) from: {}",
-                        config.len(),
-                        suppressions_path.display()
-                    );
-                }
-                return config;
-            }
+            return Some(suppressions);
         }
     }
 
-    FileSuppressionsConfig::new()
+    None
+}
+
+/// Load the suppressions.xml at `path`, returning it together with its path.
+fn load_file_suppressions(path: &Path) -> Option<(FileSuppressionsConfig, PathBuf)> {
+    if !path.exists() {
+        return None;
+    }
+    let xml = std::fs::read_to_string(path).ok()?;
+    let (config, errors) = FileSuppressionsConfig::parse(&xml);
+    for error in &errors {
+        eprintln!(
+            "{} {}: {error}, entry ignored",
+            "warning:".yellow().bold(),
+            path.display()
+        );
+    }
+    if !config.is_empty() {
+        eprintln!(
+            "Loaded {} file suppression(s) from: {}",
+            config.len(),
+            path.display()
+        );
+    }
+    Some((config, path.to_path_buf()))
 }
 
 /// Create a filter config from a checkstyle module.
@@ -821,8 +1689,24 @@ fn create_filter_from_module(
     PlainTextCommentFilterConfig::new(off_format, on_format, check_format)
 }
 
-/// Find lintal.toml in common locations.
-fn find_lintal_config() -> Option<LintalConfig> {
+/// Create a nearby comment filter config from a checkstyle module.
+fn create_nearby_filter_from_module(
+    module: &lintal_checkstyle::Module,
+) -> Option<NearbyCommentFilterConfig> {
+    let mut filter = NearbyCommentFilterConfig::new(
+        module.property("commentFormat"),
+        module.property("checkFormat"),
+        module.property("messageFormat"),
+        module.property("idFormat"),
+        module.property("influenceFormat"),
+    )?;
+    filter.check_cpp = module.property("checkCPP") != Some("false");
+    filter.check_c = module.property("checkC") != Some("false");
+    Some(filter)
+}
+
+/// Find lintal.toml in common locations, returning it with its path.
+fn find_lintal_config() -> Option<(LintalConfig, PathBuf)> {
     let candidates = ["lintal.toml", ".lintal.toml", "config/lintal.toml"];
     for candidate in candidates {
         let path = Path::new(candidate);
@@ -830,7 +1714,7 @@ fn find_lintal_config() -> Option<LintalConfig> {
             && let Ok(config) = LintalConfig::from_file(path)
         {
             eprintln!("Loaded lintal.toml from: {}", candidate);
-            return Some(config);
+            return Some((config, path.to_path_buf()));
         }
     }
     None
@@ -847,17 +1731,19 @@ fn find_checkstyle_config(base_paths: &[PathBuf]) -> Option<PathBuf> {
     ];
 
     // First try relative to each base path (the directories being checked)
+    // and its ancestors
     for base in base_paths {
-        // If base is a file, use its parent directory
-        let base_dir = if base.is_file() {
-            base.parent().map(|p| p.to_path_buf())
+        // If base is a file (which may not exist, e.g. for --stdin-filename),
+        // start from its parent directory
+        let base_dir = if base.is_dir() {
+            Some(base.as_path())
         } else {
-            Some(base.clone())
+            base.parent()
         };
 
-        if let Some(base_dir) = base_dir {
+        for dir in base_dir.into_iter().flat_map(Path::ancestors) {
             for candidate in &candidates {
-                let path = base_dir.join(candidate);
+                let path = dir.join(candidate);
                 if path.exists() {
                     return Some(path);
                 }
@@ -875,35 +1761,138 @@ fn find_checkstyle_config(base_paths: &[PathBuf]) -> Option<PathBuf> {
     None
 }
 
-/// Create a rule from configuration using the registry.
-fn create_rule_from_config(
-    registry: &RuleRegistry,
-    configured_rule: &ConfiguredRule,
-) -> Option<Box<dyn Rule>> {
-    let props = configured_rule.properties_ref();
+/// Find the Maven or Gradle build configuring checkstyle for the base paths
+/// or the current directory, looking in their ancestors too so that modules
+/// of a multi-module build inherit the settings of its root.
+fn find_build_config(base_paths: &[PathBuf]) -> Option<BuildPluginConfig> {
+    let dirs = base_paths
+        .iter()
+        .filter_map(|base| {
+            if base.is_dir() {
+                Some(base.as_path())
+            } else {
+                base.parent()
+            }
+        })
+        .chain([Path::new("")]);
+
+    for dir in dirs.flat_map(Path::ancestors) {
+        match BuildPluginConfig::from_dir(dir) {
+            Ok(Some(build)) => {
+                eprintln!(
+                    "Loaded checkstyle plugin settings from: {}",
+                    build.build_file.display()
+                );
+                return Some(build);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!(
+                    "{}: Failed to read the build file in {}: {e}",
+                    "Warning".yellow(),
+                    dir.display()
+                );
+            }
+        }
+    }
+    None
+}
+
+/// File names that are treated as configuration wherever they appear.
+const CONFIG_FILE_NAMES: &[&str] = &[
+    "checkstyle.xml",
+    ".checkstyle.xml",
+    "lintal.toml",
+    ".lintal.toml",
+    "suppressions.xml",
+];
+
+/// Returns true if a change to `path` may change the configuration: it has
+/// the name of a configuration file, or it is one of the files `linter` was
+/// loaded from.
+fn is_config_file(path: &Path, linter: Option<&LoadedLinter>) -> bool {
+    if path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| CONFIG_FILE_NAMES.contains(&name) || BUILD_FILE_NAMES.contains(&name))
+    {
+        return true;
+    }
+    linter.is_some_and(|linter| {
+        [
+            &linter.config_path,
+            &linter.file_suppressions_path,
+            &linter.build_file,
+        ]
+        .into_iter()
+        .flatten()
+        .any(|config| same_file(config, path))
+    })
+}
 
-    if let Some(rule) = registry.create_rule(&configured_rule.name, &props) {
-        Some(rule)
-    } else {
-        eprintln!(
-            "{}: Unknown rule '{}', skipping",
-            "Warning".yellow(),
-            configured_rule.name
-        );
-        None
+/// Returns true if `a` and `b` name the same file.
+fn same_file(a: &Path, b: &Path) -> bool {
+    a == b || matches!((a.canonicalize(), b.canonicalize()), (Ok(a), Ok(b)) if a == b)
+}
+
+/// Collect Java files under `paths`, keeping only changed files if `changes`
+/// is given.
+fn collect_changed_java_files(
+    paths: &[PathBuf],
+    linter: &LoadedLinter,
+    changes: Option<&ChangedFiles>,
+) -> Vec<PathBuf> {
+    let mut files = collect_java_files(paths, linter);
+    if let Some(changes) = changes {
+        files.retain(|path| changes.get(path).is_some());
     }
+    files
 }
 
-fn collect_java_files(paths: &[PathBuf]) -> Vec<PathBuf> {
+/// Collect the files under `paths` whose extension the `Checker` and
+/// `TreeWalker` modules accept. Directories containing source roots
+/// configured in the build file are narrowed to those roots, and source
+/// roots the build excludes are skipped.
+fn collect_java_files(paths: &[PathBuf], linter: &LoadedLinter) -> Vec<PathBuf> {
+    let settings = linter.settings();
+    let accepts = |path: &Path| {
+        path.file_name()
+            .is_some_and(|name| settings.accepts(&name.to_string_lossy()))
+    };
+    let build_file = linter
+        .build_file
+        .as_deref()
+        .unwrap_or(Path::new("the build file"))
+        .display();
     let mut files = Vec::new();
     for path in paths {
-        if path.is_file() && path.extension().is_some_and(|e| e == "java") {
+        if path.is_file() && accepts(path) {
             files.push(path.clone());
         } else if path.is_dir() {
-            for entry in WalkDir::new(path)
-                .into_iter()
+            let dirs = source_dirs(path, &linter.source_roots, &linter.excluded_roots);
+            if dirs.search.as_slice() != std::slice::from_ref(path) {
+                eprintln!(
+                    "Searching {} only in {}, the source roots configured in {build_file}",
+                    path.display(),
+                    display_paths(&dirs.search)
+                );
+            }
+            if !dirs.skip.is_empty() {
+                eprintln!(
+                    "Skipping {}, which {build_file} does not check",
+                    display_paths(&dirs.skip)
+                );
+            }
+            for entry in dirs
+                .search
+                .iter()
+                .flat_map(|dir| {
+                    WalkDir::new(dir)
+                        .into_iter()
+                        .filter_entry(|e| !dirs.skip.iter().any(|skip| e.path() == skip))
+                })
                 .filter_map(|e| e.ok())
-                .filter(|e| e.path().extension().is_some_and(|ext| ext == "java"))
+                .filter(|e| e.file_type().is_file() && accepts(e.path()))
             {
                 files.push(entry.path().to_path_buf());
             }
@@ -912,93 +1901,296 @@ fn collect_java_files(paths: &[PathBuf]) -> Vec<PathBuf> {
     files
 }
 
+/// `paths` separated by commas.
+fn display_paths(paths: &[PathBuf]) -> String {
+    let paths: Vec<_> = paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect();
+    paths.join(", ")
+}
+
+/// The directories files are collected from under a directory on the
+/// command line.
+#[derive(Debug, PartialEq, Eq)]
+struct SourceDirs {
+    /// The directories to search.
+    search: Vec<PathBuf>,
+    /// Directories inside them to skip.
+    skip: Vec<PathBuf>,
+}
+
+impl SourceDirs {
+    /// Returns true if files are collected from `path`, a path under the
+    /// directory these were computed for.
+    fn contains(&self, path: &Path) -> bool {
+        self.search.iter().any(|dir| path.starts_with(dir))
+            && !self.skip.iter().any(|dir| path.starts_with(dir))
+    }
+}
+
+/// The directories to search for files under `dir`: the existing source
+/// roots inside it, or `dir` itself if there are none. Excluded roots
+/// inside `dir` are skipped, but not `dir` itself when it was asked for.
+fn source_dirs(dir: &Path, source_roots: &[PathBuf], excluded_roots: &[PathBuf]) -> SourceDirs {
+    let mut search = roots_inside(dir, source_roots);
+    if search.is_empty() {
+        search.push(dir.to_path_buf());
+    }
+    let skip = roots_inside(dir, excluded_roots)
+        .into_iter()
+        .filter(|root| root != dir)
+        .collect();
+    SourceDirs { search, skip }
+}
+
+/// The existing `roots` inside `dir`, with the prefix `dir` was given with.
+fn roots_inside(dir: &Path, roots: &[PathBuf]) -> Vec<PathBuf> {
+    let Ok(canonical_dir) = dir.canonicalize() else {
+        return Vec::new();
+    };
+    roots
+        .iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .filter_map(|root| {
+            let relative = root.strip_prefix(&canonical_dir).ok()?;
+            Some(if relative.as_os_str().is_empty() {
+                dir.to_path_buf()
+            } else {
+                dir.join(relative)
+            })
+        })
+        .collect()
+}
+
+/// Check a single file.
 fn check_file(
-    path: &PathBuf,
-    rules: &[Box<dyn Rule>],
-    dispatch: &DispatchTable,
-    suppression_filters: &[PlainTextCommentFilterConfig],
-    file_suppressions: &FileSuppressionsConfig,
+    path: &Path,
+    linter: &LoadedLinter,
+    cache: Option<&Cache>,
+    changed_lines: Option<&ChangedLines>,
+    fingerprint: bool,
+    keep_source: bool,
 ) -> Result<FileCheckResult> {
-    let source = std::fs::read_to_string(path)
-        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let source = charset::read_source(path, linter.encoding)?;
+    Ok(check_source(
+        path,
+        &source,
+        linter,
+        cache,
+        changed_lines,
+        fingerprint,
+        keep_source,
+    ))
+}
 
-    // Use thread-local parser to avoid repeated initialization
-    let parse_result = PARSER.with(|parser| parser.borrow_mut().parse(&source));
-    let Some(result) = parse_result else {
-        return Ok(FileCheckResult {
-            violations: vec![format!("{}: Failed to parse", path.display())],
-            violation_count: 0,
-            fixable_count: 0,
-        });
+/// Check the source of the file at `path`. With `changed_lines`, only
+/// violations on those lines are reported. With `fingerprint`, the reported
+/// violations are fingerprinted for a baseline. With `keep_source`, the
+/// source is returned with the violations.
+fn check_source(
+    path: &Path,
+    source: &str,
+    linter: &LoadedLinter,
+    cache: Option<&Cache>,
+    changed_lines: Option<&ChangedLines>,
+    fingerprint: bool,
+    keep_source: bool,
+) -> FileCheckResult {
+    let path_str = path.to_string_lossy();
+    let content_hash = cache.map(|_| cache::hash128(source.as_bytes()));
+    let cached = match (cache, content_hash) {
+        (Some(cache), Some(hash)) => cache.get(&path_str, hash),
+        _ => None,
     };
 
-    let ctx = CheckContext::new(&source);
-    let mut suppression_ctx = SuppressionContext::from_source(&source, suppression_filters);
+    let mut messages = if let Some(messages) = cached {
+        messages
+    } else {
+        let Some(diagnostics) = linter.check(source, path) else {
+            return FileCheckResult {
+                path: path.to_path_buf(),
+                messages: FileMessages {
+                    path: linter.report_path(path),
+                    messages: vec![],
+                    source: None,
+                    error: Some("Failed to parse".to_string()),
+                },
+                fingerprints: vec![],
+            };
+        };
 
-    // Parse @SuppressWarnings annotations for additional suppressions
-    let root = CstNode::new(result.tree.root_node(), &source);
-    suppression_ctx.parse_suppress_warnings(&source, &root);
+        let line_index = LineIndex::from_source_text(source);
+        let source_code = SourceCode::new(source, &line_index);
 
-    let mut violation_messages = Vec::new();
-    let mut violation_count = 0;
-    let mut fixable_count = 0;
+        let messages: Vec<Message> = diagnostics
+            .iter()
+            .map(|d| {
+                Message::from_diagnostic(
+                    d.rule,
+                    &d.diagnostic,
+                    &source_code,
+                    linter.settings().tab_width(),
+                )
+            })
+            .collect();
+        if let (Some(cache), Some(hash)) = (cache, content_hash) {
+            cache.insert(&path_str, hash, &messages);
+        }
+        messages
+    };
 
-    let path_str = path.to_string_lossy();
+    if let Some(lines) = changed_lines {
+        messages.retain(|m| lines.intersects(m.location.line, m.end_location.line));
+    }
 
-    // Cache which rules are suppressed for this file (check once, not per-node)
-    let suppressed_rules: Option<Vec<bool>> = if file_suppressions.is_empty() {
-        None
+    let fingerprints = if fingerprint && !messages.is_empty() {
+        fingerprint_messages(source, &messages)
     } else {
-        Some(
-            rules
-                .iter()
-                .map(|rule| file_suppressions.is_suppressed(&path_str, rule.name()))
-                .collect(),
-        )
+        vec![]
     };
 
-    let has_suppressions = suppression_ctx.has_suppressions();
-    for node in TreeWalker::new(root.inner(), &source) {
-        // Quick skip for nodes with no rules
-        let kind_id = node.kind_id();
-        if !dispatch.has_rules_for_kind(kind_id) {
-            continue;
+    FileCheckResult {
+        path: path.to_path_buf(),
+        messages: FileMessages {
+            path: linter.report_path(path),
+            messages,
+            source: keep_source.then(|| source.to_string()),
+            error: None,
+        },
+        fingerprints,
+    }
+}
+
+/// Compute the baseline fingerprint of each message in `source`.
+fn fingerprint_messages(source: &str, messages: &[Message]) -> Vec<Fingerprint> {
+    let Some(result) = PARSER.with(|parser| parser.borrow_mut().parse(source)) else {
+        return vec![];
+    };
+    let root = CstNode::new(result.tree.root_node(), source);
+    messages
+        .iter()
+        .map(|message| Fingerprint::new(message, source, &root))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A result for the file at `path` that is reported as `report_path`,
+    /// with one violation on the field.
+    fn check_result(path: &Path, report_path: &str, source: &str) -> FileCheckResult {
+        let start = u32::try_from(source.find('=').unwrap()).unwrap();
+        let messages = vec![
+            Message::test("WhitespaceAround", "Missing whitespace before `=`").at(
+                start,
+                start + 1,
+                2,
+                10,
+                11,
+            ),
+        ];
+        FileCheckResult {
+            path: path.to_path_buf(),
+            fingerprints: fingerprint_messages(source, &messages),
+            messages: FileMessages {
+                path: PathBuf::from(report_path),
+                messages,
+                source: None,
+                error: None,
+            },
         }
-        for rule_idx in dispatch.rule_indices_for_kind(kind_id) {
-            if suppressed_rules.as_ref().is_some_and(|mask| mask[rule_idx]) {
-                continue;
-            }
-            let rule = &rules[rule_idx];
-            for diagnostic in rule.check(&ctx, &node) {
-                // Skip suppressed diagnostics (comment-based and @SuppressWarnings)
-                if has_suppressions
-                    && suppression_ctx.is_suppressed(rule.name(), diagnostic.range.start())
-                {
-                    continue;
-                }
+    }
 
-                violation_count += 1;
-                if diagnostic.fix.is_some() {
-                    fixable_count += 1;
-                }
+    #[test]
+    fn test_baseline_is_keyed_on_files_not_basedir_paths() {
+        // Paths are reported relative to a basedir that is neither the
+        // working directory nor the baseline's directory
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("project/src");
+        std::fs::create_dir_all(&src).unwrap();
+        let path = src.join("A.java");
+        let source = "class A {\n    int a=1;\n}\n";
+        std::fs::write(&path, source).unwrap();
+        let baseline_path = dir.path().join("baseline.json");
+
+        let count =
+            write_baseline(&baseline_path, &[check_result(&path, "A.java", source)]).unwrap();
+        assert_eq!(count, 1);
+        let written = std::fs::read_to_string(&baseline_path).unwrap();
+        assert!(written.contains("\"project/src/A.java\""), "{written}");
+
+        let mut baseline = Baseline::load(&baseline_path).unwrap();
+        let mut results = vec![check_result(&path, "A.java", source)];
+        apply_baseline(&mut baseline, &mut results);
+        assert!(results[0].messages.messages.is_empty());
+        assert!(baseline.into_fixed().is_empty());
+
+        // Entries of files that were not checked still exist on disk
+        let baseline = Baseline::load(&baseline_path).unwrap();
+        assert!(baseline.into_fixed().is_empty());
+    }
 
-                let source_code = ctx.source_code();
-                let loc = source_code.line_column(diagnostic.range.start());
-                violation_messages.push(format!(
-                    "{}:{}:{}: {} {}",
-                    path.display(),
-                    loc.line.get(),
-                    loc.column.get(),
-                    format!("[{}]", rule.name()).blue(),
-                    diagnostic.kind.body
-                ));
-            }
+    #[test]
+    fn test_default_gradle_source_roots_keep_subprojects() {
+        // The root project applies checkstyle and includes `app`
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("settings.gradle"), "include 'app'\n").unwrap();
+        std::fs::write(root.join("build.gradle"), "plugins { id 'checkstyle' }\n").unwrap();
+        for source_dir in ["src/main/java", "app/src/main/java"] {
+            std::fs::create_dir_all(root.join(source_dir)).unwrap();
         }
+
+        let build = BuildPluginConfig::from_dir(root).unwrap().unwrap();
+        assert!(build.source_roots.is_empty());
+        assert_eq!(
+            source_dirs(root, &build.source_roots, &build.excluded_roots).search,
+            [root]
+        );
+
+        // Configured source sets narrow the search
+        std::fs::write(
+            root.join("build.gradle"),
+            "plugins { id 'checkstyle' }\ncheckstyle { sourceSets = [sourceSets.main] }\n",
+        )
+        .unwrap();
+        let build = BuildPluginConfig::from_dir(root).unwrap().unwrap();
+        assert_eq!(
+            source_dirs(root, &build.source_roots, &build.excluded_roots).search,
+            [root.join("src/main/java")]
+        );
     }
 
-    Ok(FileCheckResult {
-        violations: violation_messages,
-        violation_count,
-        fixable_count,
-    })
+    #[test]
+    fn test_default_maven_source_roots_skip_tests() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("pom.xml"),
+            "<project><build><plugins><plugin>\
+             <artifactId>maven-checkstyle-plugin</artifactId>\
+             </plugin></plugins></build></project>",
+        )
+        .unwrap();
+        for source_dir in ["src/main/java", "src/test/java", "core/src/main/java"] {
+            std::fs::create_dir_all(root.join(source_dir)).unwrap();
+        }
+
+        let build = BuildPluginConfig::from_dir(root).unwrap().unwrap();
+        assert_eq!(
+            source_dirs(root, &build.source_roots, &build.excluded_roots),
+            SourceDirs {
+                search: vec![root.to_path_buf()],
+                skip: vec![root.join("src/test/java")],
+            }
+        );
+
+        // Asking for the test sources checks them
+        let tests = root.join("src/test/java");
+        let dirs = source_dirs(&tests, &build.source_roots, &build.excluded_roots);
+        assert_eq!(dirs.search, [tests]);
+        assert!(dirs.skip.is_empty());
+    }
 }
@@ -0,0 +1,125 @@
+//! Checkstyle-compatible XML output format.
+//!
+//! Mirrors the report written by checkstyle's `XMLLogger`, so tooling that
+//! already consumes checkstyle results (Jenkins, IDE plugins) can read it.
+
+use std::io::Write;
+
+use lintal_linter::RuleRegistry;
+use quick_xml::escape::escape;
+
+use super::{Emitter, FileMessages};
+
+/// Emits a `<checkstyle><file><error/></file></checkstyle>` report.
+pub(crate) struct CheckstyleEmitter;
+
+impl Emitter for CheckstyleEmitter {
+    fn emit(&mut self, writer: &mut dyn Write, files: &[FileMessages]) -> std::io::Result<()> {
+        let registry = RuleRegistry::builtin();
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            writer,
+            r#"<checkstyle version="lintal-{}">"#,
+            env!("CARGO_PKG_VERSION")
+        )?;
+        for file in files {
+            writeln!(
+                writer,
+                r#"<file name="{}">"#,
+                escape(file.path.to_string_lossy().as_ref())
+            )?;
+            for message in &file.messages {
+                writeln!(
+                    writer,
+                    r#"<error line="{}" column="{}" severity="{}" message="{}" source="{}"/>"#,
+                    message.location.line,
+                    message.location.column,
+                    message.severity,
+                    escape(message.message.as_str()),
+                    escape(checkstyle_source(&registry, &message.rule).as_str())
+                )?;
+            }
+            writeln!(writer, "</file>")?;
+        }
+        writeln!(writer, "</checkstyle>")
+    }
+}
+
+/// The fully qualified checkstyle check class for a rule, used as the
+/// `source` attribute. Unknown rules fall back to the rule name.
+fn checkstyle_source(registry: &RuleRegistry, rule: &str) -> String {
+    match registry.metadata(rule) {
+        Some(metadata) => format!("{}.{rule}Check", metadata.category.package()),
+        None => rule.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::output::Message;
+    use lintal_diagnostics::Severity;
+
+    #[test]
+    fn test_checkstyle_xml_report() {
+        let files = vec![
+            FileMessages {
+                path: PathBuf::from("src/A.java"),
+                messages: vec![
+                    Message::test(
+                        "LeftCurly",
+                        "'{' at column 5 should be on the previous line",
+                    )
+                    .at(10, 11, 2, 5, 6)
+                    .severity(Severity::Warning),
+                ],
+                source: None,
+                error: None,
+            },
+            FileMessages {
+                path: PathBuf::from("src/B.java"),
+                messages: vec![],
+                source: None,
+                error: None,
+            },
+        ];
+
+        let mut output = Vec::new();
+        CheckstyleEmitter.emit(&mut output, &files).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains(r#"<file name="src/A.java">"#));
+        assert!(output.contains(
+            r#"<error line="2" column="5" severity="warning" message="&apos;{&apos; at column 5 should be on the previous line" source="com.puppycrawl.tools.checkstyle.checks.blocks.LeftCurlyCheck"/>"#
+        ));
+        // Clean files are still listed, like checkstyle's XMLLogger does.
+        assert!(output.contains("<file name=\"src/B.java\">\n</file>"));
+    }
+
+    #[test]
+    fn test_checkstyle_source() {
+        let registry = RuleRegistry::builtin();
+        assert_eq!(
+            checkstyle_source(&registry, "UpperEll"),
+            "com.puppycrawl.tools.checkstyle.checks.UpperEllCheck"
+        );
+        assert_eq!(
+            checkstyle_source(&registry, "FinalClass"),
+            "com.puppycrawl.tools.checkstyle.checks.design.FinalClassCheck"
+        );
+        assert_eq!(
+            checkstyle_source(&registry, "FinalParameters"),
+            "com.puppycrawl.tools.checkstyle.checks.FinalParametersCheck"
+        );
+        assert_eq!(
+            checkstyle_source(&registry, "Indentation"),
+            "com.puppycrawl.tools.checkstyle.checks.indentation.IndentationCheck"
+        );
+        assert_eq!(
+            checkstyle_source(&registry, "SomethingElse"),
+            "SomethingElse"
+        );
+    }
+}
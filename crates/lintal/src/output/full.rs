@@ -0,0 +1,398 @@
+//! Rich terminal output with source snippets.
+//!
+//! Violations are grouped by file. Each one shows the offending lines with
+//! carets under its range, what the fix does and a preview of the fixed
+//! lines, in the style of rustc's diagnostics.
+
+use std::io::Write;
+
+use colored::{ColoredString, Colorize};
+use lintal_diagnostics::{Applicability, Edit, Severity};
+use lintal_source_file::{LineIndex, OneIndexed, SourceCode};
+use lintal_text_size::{Ranged, TextLen, TextRange, TextSize};
+
+use super::{Emitter, FileMessages, Message};
+
+/// Snippets longer than this show only their first and last lines.
+const MAX_SNIPPET_LINES: usize = 6;
+
+/// Emits each violation with the source lines it refers to, grouped by file.
+///
+/// Files checked without keeping their source fall back to the message only.
+pub(crate) struct FullEmitter {
+    /// Columns per tab stop, the Checker's `tabWidth` that reported columns
+    /// are counted with.
+    pub(crate) tab_width: usize,
+}
+
+impl Emitter for FullEmitter {
+    fn emit(&mut self, writer: &mut dyn Write, files: &[FileMessages]) -> std::io::Result<()> {
+        for file in files.iter().filter(|f| !f.messages.is_empty()) {
+            writeln!(
+                writer,
+                "{}",
+                file.path.display().to_string().bold().underline()
+            )?;
+            let line_index = file.source.as_deref().map(LineIndex::from_source_text);
+            let source_code = file
+                .source
+                .as_deref()
+                .zip(line_index.as_ref())
+                .map(|(source, index)| SourceCode::new(source, index));
+
+            for message in &file.messages {
+                writeln!(writer)?;
+                emit_message(writer, file, message, source_code.as_ref(), self.tab_width)?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
+fn emit_message(
+    writer: &mut dyn Write,
+    file: &FileMessages,
+    message: &Message,
+    source_code: Option<&SourceCode>,
+    tab_width: usize,
+) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "{}{} {}",
+        severity_label(message.severity),
+        format!("[{}]:", message.rule).bold(),
+        message.message.bold()
+    )?;
+
+    // Ranges past the end of the source belong to another version of it
+    let source_code = source_code.filter(|code| message.range.end() <= code.text().text_len());
+    let snippet = source_code.map(|code| Snippet::new(code, message.range, tab_width));
+    let preview = source_code
+        .zip(message.fix.as_ref())
+        .and_then(|(code, fix)| FixPreview::new(code, &fix.edits, tab_width));
+    let last_line = snippet
+        .iter()
+        .map(|s| s.last)
+        .chain(preview.iter().map(|p| p.last_line()))
+        .max()
+        .unwrap_or(message.location.line);
+    let gutter = last_line.to_string().len();
+    let pad = " ".repeat(gutter);
+
+    writeln!(
+        writer,
+        "{pad}{} {}:{}:{}",
+        "-->".blue().bold(),
+        file.path.display(),
+        message.location.line,
+        message.location.column
+    )?;
+    if let Some(snippet) = &snippet {
+        writeln!(writer, "{pad} {}", "|".blue().bold())?;
+        snippet.emit(writer, gutter, message.severity)?;
+    }
+
+    if let Some(help) = help(message) {
+        writeln!(writer, "{pad} {} {help}", "=".blue().bold())?;
+    }
+    if let Some(preview) = &preview {
+        writeln!(writer, "{pad} {}", "|".blue().bold())?;
+        preview.emit(writer, gutter)?;
+        writeln!(writer, "{pad} {}", "|".blue().bold())?;
+    }
+    Ok(())
+}
+
+/// The colored `severity` prefix of a violation.
+fn severity_label(severity: Severity) -> ColoredString {
+    match severity {
+        Severity::Error | Severity::Ignore => "error".red().bold(),
+        Severity::Warning => "warning".yellow().bold(),
+        Severity::Info => "info".cyan().bold(),
+    }
+}
+
+/// The `help` line describing the fix, if the violation has a fix or a
+/// suggestion.
+fn help(message: &Message) -> Option<String> {
+    let label = match message.fix.as_ref().map(|fix| fix.applicability) {
+        Some(Applicability::Safe) => "fix",
+        Some(Applicability::Unsafe) => "unsafe fix",
+        Some(Applicability::DisplayOnly) => "suggested fix",
+        None => "help",
+    };
+    match (&message.suggestion, &message.fix) {
+        (Some(suggestion), _) => Some(format!("{}: {suggestion}", label.green().bold())),
+        (None, Some(_)) => Some(format!("{} available", label.green().bold())),
+        (None, None) => None,
+    }
+}
+
+/// The source lines a violation spans.
+struct Snippet {
+    /// One-indexed number of the last line.
+    last: usize,
+    /// Each shown line with its caret line, or `None` where lines are elided.
+    lines: Vec<Option<(usize, String, String)>>,
+}
+
+impl Snippet {
+    fn new(source_code: &SourceCode, range: TextRange, tab_width: usize) -> Self {
+        let first = source_code.line_index(range.start());
+        let last = last_line(
+            source_code,
+            first,
+            source_code.line_index(range.end()),
+            range.end(),
+        );
+
+        let numbers: Vec<usize> = (first.get()..=last.get()).collect();
+        let shown: Vec<Option<usize>> = if numbers.len() > MAX_SNIPPET_LINES {
+            let head = numbers[..MAX_SNIPPET_LINES / 2].iter().copied().map(Some);
+            let tail = numbers[numbers.len() - MAX_SNIPPET_LINES / 2 + 1..]
+                .iter()
+                .copied()
+                .map(Some);
+            head.chain(std::iter::once(None)).chain(tail).collect()
+        } else {
+            numbers.into_iter().map(Some).collect()
+        };
+
+        let lines = shown
+            .into_iter()
+            .map(|number| {
+                let number = number?;
+                let line = OneIndexed::new(number)?;
+                let line_range = source_code.line_range(line);
+                let text = trim_line_ending(source_code.line_text(line));
+                let line_start = line_range.start();
+                let line_end = line_start + text.text_len();
+
+                let start = range.start().clamp(line_start, line_end) - line_start;
+                let end = range.end().clamp(line_start, line_end) - line_start;
+                let before = &text[..usize::from(start)];
+                let marked = &text[usize::from(start)..usize::from(end)];
+                let width = display_width(&format!("{before}{marked}"), tab_width)
+                    - display_width(before, tab_width);
+                let carets = if width == 0 && line != first {
+                    String::new()
+                } else {
+                    format!(
+                        "{}{}",
+                        " ".repeat(display_width(before, tab_width)),
+                        "^".repeat(width.max(1))
+                    )
+                };
+                Some((number, expand_tabs(text, tab_width), carets))
+            })
+            .collect();
+
+        Self {
+            last: last.get(),
+            lines,
+        }
+    }
+
+    fn emit(
+        &self,
+        writer: &mut dyn Write,
+        gutter: usize,
+        severity: Severity,
+    ) -> std::io::Result<()> {
+        let bar = "|".blue().bold();
+        for line in &self.lines {
+            let Some((number, text, carets)) = line else {
+                writeln!(writer, "{}", "...".blue().bold())?;
+                continue;
+            };
+            writeln!(
+                writer,
+                "{} {bar} {text}",
+                format!("{number:>gutter$}").blue().bold()
+            )?;
+            if !carets.is_empty() {
+                let carets = match severity {
+                    Severity::Error | Severity::Ignore => carets.red().bold(),
+                    Severity::Warning => carets.yellow().bold(),
+                    Severity::Info => carets.cyan().bold(),
+                };
+                writeln!(writer, "{} {bar} {carets}", " ".repeat(gutter))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The lines a fix changes, before and after applying it.
+struct FixPreview {
+    /// One-indexed number of the first changed line.
+    first: usize,
+    removed: Vec<String>,
+    added: Vec<String>,
+}
+
+impl FixPreview {
+    fn new(source_code: &SourceCode, edits: &[Edit], tab_width: usize) -> Option<Self> {
+        let mut edits: Vec<&Edit> = edits.iter().collect();
+        edits.sort_by_key(|edit| edit.start());
+        let start = edits.first()?.start();
+        let end = edits.iter().map(|edit| edit.end()).max()?;
+
+        let first = source_code.line_index(start);
+        let last = last_line(source_code, first, source_code.line_index(end), end);
+        let region = TextRange::new(source_code.line_start(first), source_code.line_end(last));
+        let text = source_code.text();
+        let original = slice(text, region)?;
+
+        let mut fixed = String::with_capacity(original.len());
+        let mut offset = region.start();
+        for edit in edits {
+            // Overlapping edits cannot be previewed
+            if edit.start() < offset {
+                return None;
+            }
+            fixed.push_str(slice(text, TextRange::new(offset, edit.start()))?);
+            fixed.push_str(edit.content().unwrap_or_default());
+            offset = edit.end();
+        }
+        fixed.push_str(slice(text, TextRange::new(offset, region.end()))?);
+
+        Some(Self {
+            first: first.get(),
+            removed: original
+                .lines()
+                .map(|line| expand_tabs(line, tab_width))
+                .collect(),
+            added: fixed
+                .lines()
+                .map(|line| expand_tabs(line, tab_width))
+                .collect(),
+        })
+    }
+
+    /// One-indexed number of the last line shown.
+    fn last_line(&self) -> usize {
+        self.first + self.removed.len().max(self.added.len()).saturating_sub(1)
+    }
+
+    fn emit(&self, writer: &mut dyn Write, gutter: usize) -> std::io::Result<()> {
+        for (i, line) in self.removed.iter().enumerate() {
+            let number = format!("{:>gutter$}", self.first + i).blue().bold();
+            writeln!(writer, "{number} {}", format!("- {line}").red())?;
+        }
+        for (i, line) in self.added.iter().enumerate() {
+            let number = format!("{:>gutter$}", self.first + i).blue().bold();
+            writeln!(writer, "{number} {}", format!("+ {line}").green())?;
+        }
+        Ok(())
+    }
+}
+
+fn trim_line_ending(line: &str) -> &str {
+    line.trim_end_matches(['\n', '\r'])
+}
+
+/// `text` with tabs expanded to the next multiple of `tab_width` columns.
+/// A tab width of 0 is treated as 1.
+fn expand_tabs(text: &str, tab_width: usize) -> String {
+    let tab_width = tab_width.max(1);
+    let mut expanded = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c == '\t' {
+            let width = tab_width - expanded.chars().count() % tab_width;
+            expanded.extend(std::iter::repeat_n(' ', width));
+        } else {
+            expanded.push(c);
+        }
+    }
+    expanded
+}
+
+/// The number of columns `text` takes up once its tabs are expanded.
+fn display_width(text: &str, tab_width: usize) -> usize {
+    expand_tabs(text, tab_width).chars().count()
+}
+
+/// The part of `text` in `range`, if `range` lies on character boundaries.
+fn slice(text: &str, range: TextRange) -> Option<&str> {
+    text.get(std::ops::Range::<usize>::from(range))
+}
+
+/// The last line of a range ending at `end` on line `last` of a range
+/// starting on line `first`. A range ending with a line break does not
+/// extend to the next line.
+fn last_line(
+    source_code: &SourceCode,
+    first: OneIndexed,
+    last: OneIndexed,
+    end: TextSize,
+) -> OneIndexed {
+    if last > first && source_code.line_start(last) == end {
+        OneIndexed::from_zero_indexed(last.to_zero_indexed() - 1)
+    } else {
+        last
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_full_output() {
+        colored::control::set_override(false);
+        // Carets line up with the reported column, counted with tabWidth 8
+        let source = "class A {\n\tlong a = 1l;\n}\n";
+        let files = vec![FileMessages {
+            path: PathBuf::from("src/A.java"),
+            messages: vec![
+                Message::test("UpperEll", "Should use uppercase 'L'.")
+                    .at(20, 22, 2, 18, 20)
+                    .suggestion("Replace 'l' with 'L'")
+                    .fix(
+                        Applicability::Safe,
+                        vec![Edit::replacement(
+                            "1L".to_string(),
+                            TextSize::new(20),
+                            TextSize::new(22),
+                        )],
+                    )
+                    .severity(Severity::Warning),
+            ],
+            source: Some(source.to_string()),
+            error: None,
+        }];
+
+        let mut output = Vec::new();
+        FullEmitter { tab_width: 8 }
+            .emit(&mut output, &files)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\
+src/A.java
+
+warning[UpperEll]: Should use uppercase 'L'.
+ --> src/A.java:2:18
+  |
+2 |         long a = 1l;
+  |                  ^^
+  = fix: Replace 'l' with 'L'
+  |
+2 -         long a = 1l;
+2 +         long a = 1L;
+  |
+
+"
+        );
+    }
+
+    #[test]
+    fn test_zero_tab_width() {
+        assert_eq!(expand_tabs("\ta\tb", 0), " a b");
+        assert_eq!(display_width("\t\t", 0), 2);
+    }
+}
@@ -0,0 +1,87 @@
+//! GitHub Actions workflow command output format.
+//!
+//! Each violation becomes an `::error`, `::warning` or `::notice` command,
+//! which GitHub shows as an annotation on the changed file in pull requests.
+
+use std::io::Write;
+
+use lintal_diagnostics::Severity;
+
+use super::{Emitter, FileMessages};
+
+/// Emits one workflow annotation command per violation.
+pub(crate) struct GithubEmitter;
+
+impl Emitter for GithubEmitter {
+    fn emit(&mut self, writer: &mut dyn Write, files: &[FileMessages]) -> std::io::Result<()> {
+        for file in files {
+            let path = file.path.to_string_lossy().replace('\\', "/");
+            for message in &file.messages {
+                writeln!(
+                    writer,
+                    "::{} title={},file={},line={},col={},endLine={},endColumn={}::{}",
+                    command(message.severity),
+                    escape_property(&format!("lintal ({})", message.rule)),
+                    escape_property(&path),
+                    message.location.line,
+                    message.location.column,
+                    message.end_location.line,
+                    message.end_location.column,
+                    escape_data(&format!("[{}] {}", message.rule, message.message)),
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The workflow command for a severity.
+fn command(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info | Severity::Ignore => "notice",
+    }
+}
+
+/// Escape the message of a workflow command.
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a `key=value` property of a workflow command.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::output::Message;
+
+    #[test]
+    fn test_github_annotations() {
+        let files = vec![FileMessages {
+            path: PathBuf::from("src/A,B.java"),
+            messages: vec![
+                Message::test("UpperEll", "Should use uppercase 'L'.\n100% sure")
+                    .at(30, 32, 3, 12, 14)
+                    .severity(Severity::Warning),
+            ],
+            source: None,
+            error: None,
+        }];
+
+        let mut output = Vec::new();
+        GithubEmitter.emit(&mut output, &files).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "::warning title=lintal (UpperEll),file=src/A%2CB.java,line=3,col=12,endLine=3,endColumn=14::[UpperEll] Should use uppercase 'L'.%0A100%25 sure\n"
+        );
+    }
+}
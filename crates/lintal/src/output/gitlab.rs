@@ -0,0 +1,121 @@
+//! GitLab Code Quality output format.
+//!
+//! GitLab compares the report of a merge request with that of its target
+//! branch by fingerprint, so fingerprints leave out line and column numbers:
+//! a violation keeps its identity when unrelated edits move it around. The
+//! two reports may come from different lintal builds, so fingerprints use a
+//! stable hash.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use lintal_diagnostics::Severity;
+use serde_json::{Value, json};
+
+use super::{Emitter, FileMessages, Message};
+use crate::hash::stable_hash128;
+
+/// Emits a JSON array of Code Quality issues.
+pub(crate) struct GitlabEmitter;
+
+impl Emitter for GitlabEmitter {
+    fn emit(&mut self, writer: &mut dyn Write, files: &[FileMessages]) -> std::io::Result<()> {
+        let mut issues: Vec<Value> = Vec::new();
+        for file in files {
+            let path = file.path.to_string_lossy().replace('\\', "/");
+            // Identical violations in a file are told apart by their order
+            let mut occurrences: HashMap<(&str, &str), usize> = HashMap::new();
+            for message in &file.messages {
+                let occurrence = occurrences
+                    .entry((message.rule.as_str(), message.message.as_str()))
+                    .or_default();
+                issues.push(issue_to_json(&path, message, *occurrence));
+                *occurrence += 1;
+            }
+        }
+
+        serde_json::to_writer_pretty(&mut *writer, &issues)?;
+        writeln!(writer)
+    }
+}
+
+fn issue_to_json(path: &str, message: &Message, occurrence: usize) -> Value {
+    let fingerprint = stable_hash128(
+        format!(
+            "{path}\0{}\0{}\0{}\0{occurrence}",
+            message.rule, message.code, message.message
+        )
+        .as_bytes(),
+    );
+    json!({
+        "type": "issue",
+        "check_name": message.rule,
+        "description": format!("[{}] {}", message.rule, message.message),
+        "categories": ["Style"],
+        "severity": gitlab_severity(message.severity),
+        "fingerprint": format!("{fingerprint:032x}"),
+        "location": {
+            "path": path,
+            "positions": {
+                "begin": {
+                    "line": message.location.line,
+                    "column": message.location.column,
+                },
+                "end": {
+                    "line": message.end_location.line,
+                    "column": message.end_location.column,
+                },
+            },
+        },
+    })
+}
+
+/// The Code Quality severity for a severity.
+fn gitlab_severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "major",
+        Severity::Warning => "minor",
+        Severity::Info | Severity::Ignore => "info",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn message(line: usize) -> Message {
+        Message::test("UpperEll", "Should use uppercase 'L'.").at(30, 32, line, 12, 14)
+    }
+
+    fn emit(messages: Vec<Message>) -> Vec<Value> {
+        let files = vec![FileMessages {
+            path: PathBuf::from("src/A.java"),
+            messages,
+            source: None,
+            error: None,
+        }];
+        let mut output = Vec::new();
+        GitlabEmitter.emit(&mut output, &files).unwrap();
+        serde_json::from_slice(&output).unwrap()
+    }
+
+    #[test]
+    fn test_gitlab_issues() {
+        let issues = emit(vec![message(3), message(7)]);
+        assert_eq!(issues[0]["check_name"], "UpperEll");
+        assert_eq!(issues[0]["severity"], "major");
+        assert_eq!(issues[0]["location"]["path"], "src/A.java");
+        assert_eq!(issues[1]["location"]["positions"]["begin"]["line"], 7);
+        // Reports from other lintal builds must produce the same fingerprints
+        assert_eq!(issues[0]["fingerprint"], "4aefafe8b020044c26e21896dcc33c68");
+        // Identical violations still get distinct fingerprints
+        assert_ne!(issues[0]["fingerprint"], issues[1]["fingerprint"]);
+
+        // Moving violations to other lines keeps their fingerprints
+        let moved = emit(vec![message(5), message(9)]);
+        assert_eq!(issues[0]["fingerprint"], moved[0]["fingerprint"]);
+        assert_eq!(issues[1]["fingerprint"], moved[1]["fingerprint"]);
+    }
+}
@@ -0,0 +1,53 @@
+//! JSON output format.
+
+use std::io::Write;
+
+use serde_json::{Value, json};
+
+use super::{Emitter, FileMessages, Location, Message, applicability_name};
+
+/// Emits a JSON array with one object per violation.
+pub(crate) struct JsonEmitter;
+
+impl Emitter for JsonEmitter {
+    fn emit(&mut self, writer: &mut dyn Write, files: &[FileMessages]) -> std::io::Result<()> {
+        let values: Vec<Value> = files
+            .iter()
+            .flat_map(|file| {
+                let filename = file.path.to_string_lossy();
+                file.messages
+                    .iter()
+                    .map(move |message| message_to_json(&filename, message))
+            })
+            .collect();
+
+        serde_json::to_writer_pretty(&mut *writer, &values)?;
+        writeln!(writer)
+    }
+}
+
+fn message_to_json(filename: &str, message: &Message) -> Value {
+    json!({
+        "filename": filename,
+        "rule": message.rule,
+        "code": message.code,
+        "message": message.message,
+        "severity": message.severity.as_str(),
+        "range": {
+            "start": u32::from(message.range.start()),
+            "end": u32::from(message.range.end()),
+        },
+        "location": location_to_json(message.location),
+        "end_location": location_to_json(message.end_location),
+        "fix": message.fix.as_ref().map(|fix| json!({
+            "applicability": applicability_name(fix.applicability),
+        })),
+    })
+}
+
+fn location_to_json(location: Location) -> Value {
+    json!({
+        "line": location.line,
+        "column": location.column,
+    })
+}
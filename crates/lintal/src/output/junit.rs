@@ -0,0 +1,147 @@
+//! JUnit XML output format.
+//!
+//! Every checked file is a test case that fails if the file has violations,
+//! or errors if it could not be checked, so CI systems that read test
+//! reports list lintal results next to tests.
+
+use std::fmt::Write as _;
+use std::io::Write;
+
+use quick_xml::escape::escape;
+
+use super::{Emitter, FileMessages};
+
+/// Emits a `<testsuites>` report with one test case per checked file.
+pub(crate) struct JunitEmitter;
+
+impl Emitter for JunitEmitter {
+    fn emit(&mut self, writer: &mut dyn Write, files: &[FileMessages]) -> std::io::Result<()> {
+        let tests = files.len();
+        let errors = files.iter().filter(|f| f.error.is_some()).count();
+        let failures = files
+            .iter()
+            .filter(|f| f.error.is_none() && !f.messages.is_empty())
+            .count();
+
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            writer,
+            r#"<testsuites name="lintal" tests="{tests}" failures="{failures}" errors="{errors}">"#
+        )?;
+        writeln!(
+            writer,
+            r#"  <testsuite name="lintal" tests="{tests}" failures="{failures}" errors="{errors}" skipped="0">"#
+        )?;
+        for file in files {
+            let path = file.path.to_string_lossy();
+            let path = escape(path.as_ref());
+            if let Some(error) = &file.error {
+                writeln!(
+                    writer,
+                    r#"    <testcase name="{path}" classname="lintal" file="{path}">"#
+                )?;
+                writeln!(
+                    writer,
+                    r#"      <error message="{}" type="lintal"/>"#,
+                    escape(error.as_str())
+                )?;
+                writeln!(writer, "    </testcase>")?;
+                continue;
+            }
+            if file.messages.is_empty() {
+                writeln!(
+                    writer,
+                    r#"    <testcase name="{path}" classname="lintal" file="{path}"/>"#
+                )?;
+                continue;
+            }
+
+            let mut details = String::new();
+            for message in &file.messages {
+                let _ = writeln!(
+                    details,
+                    "{}:{}:{}: {}: [{}] {}",
+                    file.path.display(),
+                    message.location.line,
+                    message.location.column,
+                    message.severity,
+                    message.rule,
+                    message.message
+                );
+            }
+            let count = file.messages.len();
+            writeln!(
+                writer,
+                r#"    <testcase name="{path}" classname="lintal" file="{path}">"#
+            )?;
+            writeln!(
+                writer,
+                r#"      <failure message="{count} violation{}" type="lintal">{}</failure>"#,
+                if count == 1 { "" } else { "s" },
+                escape(details.as_str())
+            )?;
+            writeln!(writer, "    </testcase>")?;
+        }
+        writeln!(writer, "  </testsuite>")?;
+        writeln!(writer, "</testsuites>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::output::Message;
+    use lintal_diagnostics::Severity;
+
+    #[test]
+    fn test_junit_report() {
+        let files = vec![
+            FileMessages {
+                path: PathBuf::from("src/A.java"),
+                messages: vec![
+                    Message::test(
+                        "LeftCurly",
+                        "'{' at column 5 should be on the previous line",
+                    )
+                    .at(10, 11, 2, 5, 6)
+                    .severity(Severity::Warning),
+                ],
+                source: None,
+                error: None,
+            },
+            FileMessages {
+                path: PathBuf::from("src/B.java"),
+                messages: vec![],
+                source: None,
+                error: None,
+            },
+            FileMessages {
+                path: PathBuf::from("src/C.java"),
+                messages: vec![],
+                source: None,
+                error: Some("Failed to parse".to_string()),
+            },
+        ];
+
+        let mut output = Vec::new();
+        JunitEmitter.emit(&mut output, &files).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains(r#"<testsuites name="lintal" tests="3" failures="1" errors="1">"#));
+        assert!(output.contains(
+            r#"<failure message="1 violation" type="lintal">src/A.java:2:5: warning: [LeftCurly] &apos;{&apos; at column 5 should be on the previous line
+</failure>"#
+        ));
+        assert!(
+            output
+                .contains(r#"<testcase name="src/B.java" classname="lintal" file="src/B.java"/>"#)
+        );
+        assert!(output.contains(
+            r#"<testcase name="src/C.java" classname="lintal" file="src/C.java">
+      <error message="Failed to parse" type="lintal"/>
+    </testcase>"#
+        ));
+    }
+}
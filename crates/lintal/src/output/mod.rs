@@ -0,0 +1,256 @@
+//! Output formats for `lintal check`.
+//!
+//! Each file's diagnostics are converted into [`Message`]s, which carry
+//! everything most output formats need without access to the source text.
+//! An [`Emitter`] then renders the collected messages for all files; formats
+//! that show source lines ask for the source to be kept alongside them.
+
+mod checkstyle;
+mod full;
+mod github;
+mod gitlab;
+mod json;
+mod junit;
+mod sarif;
+mod statistics;
+mod text;
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use clap::ValueEnum;
+use lintal::expanded_column;
+use lintal_diagnostics::{Applicability, Diagnostic, Edit, Severity};
+use lintal_source_file::SourceCode;
+use lintal_text_size::TextRange;
+
+pub(crate) use checkstyle::CheckstyleEmitter;
+pub(crate) use full::FullEmitter;
+pub(crate) use github::GithubEmitter;
+pub(crate) use gitlab::GitlabEmitter;
+pub(crate) use json::JsonEmitter;
+pub(crate) use junit::JunitEmitter;
+pub(crate) use sarif::SarifEmitter;
+pub(crate) use statistics::{Statistics, StatisticsEmitter};
+pub(crate) use text::TextEmitter;
+
+/// Output format for `lintal check`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// One colored `path:line:col: [Rule] message` line per violation.
+    #[default]
+    Text,
+    /// Source lines with carets, fix titles and a preview of each fix,
+    /// grouped by file.
+    Full,
+    /// A JSON array with one object per violation.
+    Json,
+    /// Checkstyle-compatible XML, as produced by checkstyle's XMLLogger.
+    Checkstyle,
+    /// SARIF 2.1.0.
+    Sarif,
+    /// GitHub Actions workflow commands, shown as annotations.
+    Github,
+    /// GitLab Code Quality JSON.
+    Gitlab,
+    /// JUnit XML with one test case per checked file.
+    Junit,
+}
+
+impl OutputFormat {
+    /// Create the emitter for this format. Source lines are shown with tabs
+    /// expanded to `tab_width` columns, as in reported columns.
+    pub(crate) fn emitter(self, tab_width: usize) -> Box<dyn Emitter> {
+        match self {
+            OutputFormat::Text => Box::new(TextEmitter),
+            OutputFormat::Full => Box::new(FullEmitter { tab_width }),
+            OutputFormat::Json => Box::new(JsonEmitter),
+            OutputFormat::Checkstyle => Box::new(CheckstyleEmitter),
+            OutputFormat::Sarif => Box::new(SarifEmitter),
+            OutputFormat::Github => Box::new(GithubEmitter),
+            OutputFormat::Gitlab => Box::new(GitlabEmitter),
+            OutputFormat::Junit => Box::new(JunitEmitter),
+        }
+    }
+
+    /// Returns true if the emitter needs [`FileMessages::source`].
+    pub(crate) fn needs_source(self) -> bool {
+        self == OutputFormat::Full
+    }
+
+    /// Returns true if a violation summary follows the output.
+    pub(crate) fn has_summary(self) -> bool {
+        matches!(self, OutputFormat::Text | OutputFormat::Full)
+    }
+}
+
+/// Renders the messages of all checked files.
+pub(crate) trait Emitter {
+    fn emit(&mut self, writer: &mut dyn Write, files: &[FileMessages]) -> std::io::Result<()>;
+}
+
+/// The messages reported for a single checked file.
+#[derive(Debug, Clone)]
+pub(crate) struct FileMessages {
+    pub(crate) path: PathBuf,
+    pub(crate) messages: Vec<Message>,
+    /// The checked source, if kept for a format that
+    /// [needs it](OutputFormat::needs_source).
+    pub(crate) source: Option<String>,
+    /// Set if the file could not be checked (e.g. it failed to parse).
+    pub(crate) error: Option<String>,
+}
+
+/// A one-indexed line and column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Location {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+/// A diagnostic resolved against its source file, ready for output.
+#[derive(Debug, Clone)]
+pub(crate) struct Message {
+    /// The rule (checkstyle module) name.
+    pub(crate) rule: String,
+    /// The [`DiagnosticKind`](lintal_diagnostics::DiagnosticKind) code.
+    pub(crate) code: String,
+    /// The message body.
+    pub(crate) message: String,
+    /// Byte range of the violation in the source.
+    pub(crate) range: TextRange,
+    /// Start position of the violation.
+    pub(crate) location: Location,
+    /// End position of the violation.
+    pub(crate) end_location: Location,
+    /// What the fix does, whether or not the violation has one.
+    pub(crate) suggestion: Option<String>,
+    /// The fix, if the violation has one.
+    pub(crate) fix: Option<MessageFix>,
+    /// The severity of the violation.
+    pub(crate) severity: Severity,
+}
+
+impl Message {
+    /// Create a message from a diagnostic produced by `rule`. Columns count
+    /// tabs up to the next multiple of `tab_width`, as in checkstyle.
+    pub(crate) fn from_diagnostic(
+        rule: &str,
+        diagnostic: &Diagnostic,
+        source_code: &SourceCode,
+        tab_width: usize,
+    ) -> Self {
+        let start = source_code.line_column(diagnostic.range.start());
+        let end = source_code.line_column(diagnostic.range.end());
+        Self {
+            rule: rule.to_string(),
+            code: diagnostic.kind.code.clone(),
+            message: diagnostic.kind.body.clone(),
+            range: diagnostic.range,
+            location: Location {
+                line: start.line.get(),
+                column: expanded_column(source_code, diagnostic.range.start(), tab_width),
+            },
+            end_location: Location {
+                line: end.line.get(),
+                column: expanded_column(source_code, diagnostic.range.end(), tab_width),
+            },
+            suggestion: diagnostic.kind.suggestion.clone(),
+            fix: diagnostic.fix.as_ref().map(|fix| MessageFix {
+                applicability: fix.applicability(),
+                edits: fix.edits().to_vec(),
+            }),
+            severity: diagnostic.severity,
+        }
+    }
+
+    /// Returns true if the violation has a fix.
+    pub(crate) fn fixable(&self) -> bool {
+        self.fix.is_some()
+    }
+}
+
+#[cfg(test)]
+impl Message {
+    /// A message for tests: an error of `rule` at line 1, column 1, without
+    /// a fix. The other methods adjust it.
+    pub(crate) fn test(rule: &str, message: &str) -> Self {
+        Self {
+            rule: rule.to_string(),
+            code: format!("{rule}Violation"),
+            message: message.to_string(),
+            range: TextRange::default(),
+            location: Location { line: 1, column: 1 },
+            end_location: Location { line: 1, column: 1 },
+            suggestion: None,
+            fix: None,
+            severity: Severity::Error,
+        }
+    }
+
+    pub(crate) fn code(self, code: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            ..self
+        }
+    }
+
+    /// Place the message at bytes `start..end` of the source, which are
+    /// columns `column..end_column` of `line`.
+    pub(crate) fn at(
+        self,
+        start: u32,
+        end: u32,
+        line: usize,
+        column: usize,
+        end_column: usize,
+    ) -> Self {
+        Self {
+            range: TextRange::new(start.into(), end.into()),
+            location: Location { line, column },
+            end_location: Location {
+                line,
+                column: end_column,
+            },
+            ..self
+        }
+    }
+
+    pub(crate) fn suggestion(self, suggestion: &str) -> Self {
+        Self {
+            suggestion: Some(suggestion.to_string()),
+            ..self
+        }
+    }
+
+    pub(crate) fn fix(self, applicability: Applicability, edits: Vec<Edit>) -> Self {
+        Self {
+            fix: Some(MessageFix {
+                applicability,
+                edits,
+            }),
+            ..self
+        }
+    }
+
+    pub(crate) fn severity(self, severity: Severity) -> Self {
+        Self { severity, ..self }
+    }
+}
+
+/// The fix of a [`Message`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MessageFix {
+    pub(crate) applicability: Applicability,
+    /// The edits, as byte ranges into the checked source.
+    pub(crate) edits: Vec<Edit>,
+}
+
+/// Lowercase name of an applicability, as used in structured output.
+pub(crate) fn applicability_name(applicability: Applicability) -> &'static str {
+    match applicability {
+        Applicability::Safe => "safe",
+        Applicability::Unsafe => "unsafe",
+        Applicability::DisplayOnly => "display-only",
+    }
+}
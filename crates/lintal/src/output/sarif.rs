@@ -0,0 +1,91 @@
+//! SARIF 2.1.0 output format.
+
+use std::collections::BTreeSet;
+use std::io::Write;
+
+use lintal_diagnostics::Severity;
+use serde_json::{Value, json};
+
+use super::{Emitter, FileMessages, Message, applicability_name};
+
+/// Emits a SARIF 2.1.0 log with a single run.
+pub(crate) struct SarifEmitter;
+
+impl Emitter for SarifEmitter {
+    fn emit(&mut self, writer: &mut dyn Write, files: &[FileMessages]) -> std::io::Result<()> {
+        let rules: BTreeSet<&str> = files
+            .iter()
+            .flat_map(|file| file.messages.iter().map(|message| message.rule.as_str()))
+            .collect();
+
+        let results: Vec<Value> = files
+            .iter()
+            .flat_map(|file| {
+                let uri = file.path.to_string_lossy().replace('\\', "/");
+                file.messages
+                    .iter()
+                    .map(move |message| result_to_json(&uri, message))
+            })
+            .collect();
+
+        let log = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "lintal",
+                        "informationUri": "https://github.com/eleventy7/lintal",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules
+                            .iter()
+                            .map(|rule| json!({
+                                "id": rule,
+                                "name": rule,
+                                "shortDescription": { "text": format!("Checkstyle {rule} check") },
+                            }))
+                            .collect::<Vec<_>>(),
+                    },
+                },
+                "results": results,
+            }],
+        });
+
+        serde_json::to_writer_pretty(&mut *writer, &log)?;
+        writeln!(writer)
+    }
+}
+
+fn result_to_json(uri: &str, message: &Message) -> Value {
+    json!({
+        "ruleId": message.rule,
+        "level": sarif_level(message.severity),
+        "message": { "text": message.message },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": uri },
+                "region": {
+                    "startLine": message.location.line,
+                    "startColumn": message.location.column,
+                    "endLine": message.end_location.line,
+                    "endColumn": message.end_location.column,
+                },
+            },
+        }],
+        "properties": {
+            "code": message.code,
+            "fixable": message.fixable(),
+            "applicability": message.fix.as_ref().map(|fix| applicability_name(fix.applicability)),
+        },
+    })
+}
+
+/// The SARIF `level` for a severity.
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+        Severity::Ignore => "none",
+    }
+}
@@ -0,0 +1,295 @@
+//! Aggregated violation counts, for `lintal check --statistics`.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+
+use colored::Colorize;
+use lintal_diagnostics::{Applicability, Severity};
+use serde_json::{Value, json};
+
+use super::{Emitter, FileMessages, Message};
+
+/// Number of files listed in the table of files with the most violations.
+const TOP_FILES: usize = 10;
+
+/// Violation and fix counts for a set of violations.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Counts {
+    pub(crate) violations: usize,
+    pub(crate) errors: usize,
+    pub(crate) warnings: usize,
+    pub(crate) info: usize,
+    /// Violations that `lintal fix` fixes.
+    pub(crate) safe_fixes: usize,
+    /// Violations that `lintal fix --unsafe` fixes.
+    pub(crate) unsafe_fixes: usize,
+}
+
+impl Counts {
+    fn add(&mut self, message: &Message) {
+        self.violations += 1;
+        match message.severity {
+            Severity::Error | Severity::Ignore => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+            Severity::Info => self.info += 1,
+        }
+        match message.fix.as_ref().map(|fix| fix.applicability) {
+            Some(Applicability::Safe) => self.safe_fixes += 1,
+            Some(Applicability::Unsafe) => self.unsafe_fixes += 1,
+            Some(Applicability::DisplayOnly) | None => {}
+        }
+    }
+
+    /// Violations with a safe or unsafe fix.
+    pub(crate) fn fixable(&self) -> usize {
+        self.safe_fixes + self.unsafe_fixes
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "violations": self.violations,
+            "errors": self.errors,
+            "warnings": self.warnings,
+            "info": self.info,
+            "fixable": {
+                "safe": self.safe_fixes,
+                "unsafe": self.unsafe_fixes,
+            },
+        })
+    }
+}
+
+/// Violation counts of the checked files, in total, per rule and per file.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Statistics {
+    pub(crate) total: Counts,
+    /// Counts per rule, by descending number of violations.
+    pub(crate) rules: Vec<(String, Counts)>,
+    /// Files with violations, by descending number of violations.
+    pub(crate) files: Vec<(PathBuf, usize)>,
+}
+
+impl Statistics {
+    pub(crate) fn new(files: &[FileMessages]) -> Self {
+        let mut total = Counts::default();
+        let mut rules: BTreeMap<&str, Counts> = BTreeMap::new();
+        for message in files.iter().flat_map(|f| &f.messages) {
+            total.add(message);
+            rules.entry(&message.rule).or_default().add(message);
+        }
+
+        let mut rules: Vec<(String, Counts)> = rules
+            .into_iter()
+            .map(|(rule, counts)| (rule.to_string(), counts))
+            .collect();
+        // Stable, so ties stay in rule name order
+        rules.sort_by(|a, b| b.1.violations.cmp(&a.1.violations));
+
+        let mut files: Vec<(PathBuf, usize)> = files
+            .iter()
+            .filter(|f| !f.messages.is_empty())
+            .map(|f| (f.path.clone(), f.messages.len()))
+            .collect();
+        files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        Self {
+            total,
+            rules,
+            files,
+        }
+    }
+}
+
+/// Emits [`Statistics`] as a table, or as JSON.
+pub(crate) struct StatisticsEmitter {
+    pub(crate) json: bool,
+}
+
+impl Emitter for StatisticsEmitter {
+    fn emit(&mut self, writer: &mut dyn Write, files: &[FileMessages]) -> std::io::Result<()> {
+        let statistics = Statistics::new(files);
+        if self.json {
+            serde_json::to_writer_pretty(&mut *writer, &statistics_to_json(&statistics))?;
+            writeln!(writer)
+        } else {
+            emit_table(writer, &statistics)
+        }
+    }
+}
+
+/// Statistics as JSON, listing every file with violations where the table
+/// only shows the first [`TOP_FILES`].
+fn statistics_to_json(statistics: &Statistics) -> Value {
+    let rules: Vec<Value> = statistics
+        .rules
+        .iter()
+        .map(|(rule, counts)| {
+            let mut value = counts.to_json();
+            value["rule"] = json!(rule);
+            value
+        })
+        .collect();
+    let files: Vec<Value> = statistics
+        .files
+        .iter()
+        .map(|(path, violations)| {
+            json!({
+                "path": path.to_string_lossy(),
+                "violations": violations,
+            })
+        })
+        .collect();
+    json!({
+        "total": statistics.total.to_json(),
+        "rules": rules,
+        "files": files,
+    })
+}
+
+fn emit_table(writer: &mut dyn Write, statistics: &Statistics) -> std::io::Result<()> {
+    if statistics.rules.is_empty() {
+        return Ok(());
+    }
+
+    let width = statistics
+        .rules
+        .iter()
+        .map(|(rule, _)| rule.len())
+        .chain(["Rule".len(), "Total".len()])
+        .max()
+        .unwrap_or_default();
+    writeln!(
+        writer,
+        "{}",
+        format!("{:<width$}  Violations  Safe fixes  Unsafe fixes", "Rule").bold()
+    )?;
+    let row = |writer: &mut dyn Write, name: &str, counts: &Counts| {
+        writeln!(
+            writer,
+            "{name:<width$}  {:>10}  {:>10}  {:>12}",
+            counts.violations, counts.safe_fixes, counts.unsafe_fixes
+        )
+    };
+    for (rule, counts) in &statistics.rules {
+        row(writer, rule, counts)?;
+    }
+    row(writer, "Total", &statistics.total)?;
+
+    writeln!(writer)?;
+    writeln!(writer, "{}", "Files with the most violations".bold())?;
+    for (path, violations) in statistics.files.iter().take(TOP_FILES) {
+        writeln!(writer, "{violations:>10}  {}", path.display())?;
+    }
+    if statistics.files.len() > TOP_FILES {
+        writeln!(
+            writer,
+            "{}",
+            format!(
+                "{:>10}  ... and {} more",
+                "",
+                statistics.files.len() - TOP_FILES
+            )
+            .dimmed()
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(rule: &str, applicability: Option<Applicability>) -> Message {
+        let message = Message::test(rule, "");
+        match applicability {
+            Some(applicability) => message.fix(applicability, vec![]),
+            None => message,
+        }
+    }
+
+    fn files() -> Vec<FileMessages> {
+        vec![
+            FileMessages {
+                path: PathBuf::from("A.java"),
+                messages: vec![message("UpperEll", Some(Applicability::Safe))],
+                source: None,
+                error: None,
+            },
+            FileMessages {
+                path: PathBuf::from("B.java"),
+                messages: vec![
+                    message("FinalClass", Some(Applicability::Unsafe)),
+                    message("UpperEll", Some(Applicability::Safe)),
+                    message("MagicNumber", None),
+                ],
+                source: None,
+                error: None,
+            },
+            FileMessages {
+                path: PathBuf::from("C.java"),
+                messages: vec![],
+                source: None,
+                error: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_statistics() {
+        let statistics = Statistics::new(&files());
+        assert_eq!(statistics.total.violations, 4);
+        assert_eq!(statistics.total.errors, 4);
+        assert_eq!(statistics.total.fixable(), 3);
+
+        let rules: Vec<(&str, usize, usize, usize)> = statistics
+            .rules
+            .iter()
+            .map(|(rule, c)| (rule.as_str(), c.violations, c.safe_fixes, c.unsafe_fixes))
+            .collect();
+        assert_eq!(
+            rules,
+            [
+                ("UpperEll", 2, 2, 0),
+                ("FinalClass", 1, 0, 1),
+                ("MagicNumber", 1, 0, 0),
+            ]
+        );
+        assert_eq!(
+            statistics.files,
+            [(PathBuf::from("B.java"), 3), (PathBuf::from("A.java"), 1)]
+        );
+    }
+
+    #[test]
+    fn test_statistics_output() {
+        colored::control::set_override(false);
+        let mut output = Vec::new();
+        StatisticsEmitter { json: false }
+            .emit(&mut output, &files())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\
+Rule         Violations  Safe fixes  Unsafe fixes
+UpperEll              2           2             0
+FinalClass            1           0             1
+MagicNumber           1           0             0
+Total                 4           2             1
+
+Files with the most violations
+         3  B.java
+         1  A.java
+"
+        );
+
+        let mut output = Vec::new();
+        StatisticsEmitter { json: true }
+            .emit(&mut output, &files())
+            .unwrap();
+        let value: Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(value["total"]["fixable"]["unsafe"], 1);
+        assert_eq!(value["rules"][0]["rule"], "UpperEll");
+        assert_eq!(value["files"][0]["path"], "B.java");
+    }
+}
@@ -0,0 +1,38 @@
+//! The default human-readable output format.
+
+use std::io::Write;
+
+use colored::Colorize;
+use lintal_diagnostics::Severity;
+
+use super::{Emitter, FileMessages};
+
+/// Emits one `path:line:col: [Rule] message` line per violation.
+///
+/// Violations below error severity are prefixed with their severity.
+pub(crate) struct TextEmitter;
+
+impl Emitter for TextEmitter {
+    fn emit(&mut self, writer: &mut dyn Write, files: &[FileMessages]) -> std::io::Result<()> {
+        for file in files {
+            for message in &file.messages {
+                let severity = match message.severity {
+                    Severity::Error | Severity::Ignore => String::new(),
+                    Severity::Warning => format!("{} ", "warning:".yellow()),
+                    Severity::Info => format!("{} ", "info:".cyan()),
+                };
+                writeln!(
+                    writer,
+                    "{}:{}:{}: {}{} {}",
+                    file.path.display(),
+                    message.location.line,
+                    message.location.column,
+                    severity,
+                    format!("[{}]", message.rule).blue(),
+                    message.message
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
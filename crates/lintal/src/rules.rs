@@ -0,0 +1,309 @@
+//! `lintal rules` and `lintal rule <name>`: describe the supported rules.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use clap::ValueEnum;
+use colored::Colorize;
+use lintal::Linter;
+use lintal_checkstyle::DEFAULT_TAB_WIDTH;
+use lintal_diagnostics::{Applicability, FixAvailability};
+use lintal_linter::{PropertyKind, PropertySpec, RuleMetadata, RuleRegistry};
+use lintal_source_file::{LineIndex, SourceCode};
+use serde_json::{Value, json};
+
+use crate::format_diff;
+use crate::output::Message;
+
+/// The file name the example of a rule is checked as.
+const EXAMPLE_PATH: &str = "Example.java";
+
+/// Output format for `lintal rules` and `lintal rule`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub(crate) enum RulesFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// JSON.
+    Json,
+}
+
+/// Run `lintal rules`.
+pub(crate) fn list(format: RulesFormat) -> Result<()> {
+    let registry = RuleRegistry::builtin();
+    let mut rules = registry.rules();
+    rules.sort_by_key(|(name, metadata)| (metadata.category, *name));
+
+    match format {
+        RulesFormat::Json => {
+            let values: Vec<Value> = rules
+                .iter()
+                .map(|(name, metadata)| rule_to_json(name, metadata))
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&values)?);
+        }
+        RulesFormat::Text => {
+            let width = rules.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+            println!(
+                "{}",
+                format!(
+                    "{:width$}  {:10}  {:9}  DESCRIPTION",
+                    "RULE", "CATEGORY", "FIX"
+                )
+                .bold()
+            );
+            for (name, metadata) in &rules {
+                println!(
+                    "{name:width$}  {:10}  {:9}  {}",
+                    metadata.category.as_str(),
+                    fix_name(metadata.fix),
+                    metadata.description
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run `lintal rule <name>`.
+pub(crate) fn show(name: &str, format: RulesFormat) -> Result<()> {
+    let registry = RuleRegistry::builtin();
+    let rules = registry.rules();
+    // Fall back to a case-insensitive match, for `lintal rule whitespacearound`
+    let found = rules
+        .iter()
+        .find(|(module, _)| *module == name)
+        .or_else(|| {
+            rules
+                .iter()
+                .find(|(module, _)| module.eq_ignore_ascii_case(name))
+        });
+    let Some(&(name, metadata)) = found else {
+        bail!("Unknown rule '{name}'; run `lintal rules` to list the supported rules");
+    };
+    let example = run_example(&registry, name, metadata);
+
+    match format {
+        RulesFormat::Json => {
+            let mut value = rule_to_json(name, metadata);
+            value["example"] = json!({
+                "source": metadata.example,
+                "violations": example.messages.iter().map(|message| json!({
+                    "line": message.location.line,
+                    "column": message.location.column,
+                    "message": message.message,
+                })).collect::<Vec<_>>(),
+                "fixed": example.fixed,
+            });
+            println!("{}", serde_json::to_string_pretty(&value)?);
+        }
+        RulesFormat::Text => print!("{}", format_rule(name, metadata, &example)),
+    }
+    Ok(())
+}
+
+/// What the rule reports for its example, and the fixed example.
+struct Example {
+    messages: Vec<Message>,
+    /// `None` if nothing could be fixed.
+    fixed: Option<String>,
+}
+
+/// Check and fix the example of a rule with its default properties,
+/// applying unsafe fixes too.
+fn run_example(registry: &RuleRegistry, name: &str, metadata: &RuleMetadata) -> Example {
+    let source = metadata.example;
+    let Some(rule) = registry.create_rule(name, &Default::default()) else {
+        return Example {
+            messages: vec![],
+            fixed: None,
+        };
+    };
+    let linter = Linter::for_rule(rule);
+
+    let line_index = LineIndex::from_source_text(source);
+    let source_code = SourceCode::new(source, &line_index);
+    let messages = linter
+        .check(source, Path::new(EXAMPLE_PATH))
+        .unwrap_or_default()
+        .iter()
+        .map(|d| Message::from_diagnostic(d.rule, &d.diagnostic, &source_code, DEFAULT_TAB_WIDTH))
+        .collect();
+
+    let fixed = linter
+        .fix(source, Path::new(EXAMPLE_PATH), Applicability::Unsafe)
+        .map(|outcome| outcome.source)
+        .filter(|fixed| fixed != source);
+
+    Example { messages, fixed }
+}
+
+/// Render the details of a rule as text.
+fn format_rule(name: &str, metadata: &RuleMetadata, example: &Example) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{} ({})\n\n{}\n",
+        name.bold(),
+        metadata.category.as_str(),
+        metadata.description
+    );
+    let _ = writeln!(
+        out,
+        "{} {}\n",
+        "Fix:".bold(),
+        match metadata.fix {
+            FixAvailability::Always => "always available",
+            FixAvailability::Sometimes => "sometimes available",
+            FixAvailability::None => "not available",
+        }
+    );
+
+    if metadata.properties.is_empty() {
+        let _ = writeln!(out, "{} none\n", "Properties:".bold());
+    } else {
+        let _ = writeln!(out, "{}", "Properties:".bold());
+        for property in metadata.properties {
+            format_property(&mut out, property);
+        }
+        out.push('\n');
+    }
+
+    if !metadata.compatibility.is_empty() {
+        let _ = writeln!(out, "{}", "Differences from checkstyle:".bold());
+        for note in metadata.compatibility {
+            let _ = writeln!(out, "  - {note}");
+        }
+        out.push('\n');
+    }
+
+    let _ = writeln!(out, "{}", "Configuration:".bold());
+    let _ = writeln!(out, "  <module name=\"{name}\">");
+    let configurable = metadata
+        .properties
+        .iter()
+        .filter(|property| property.supported && !matches!(property.kind, PropertyKind::Tokens(_)));
+    for property in configurable {
+        let _ = writeln!(
+            out,
+            "      <property name=\"{}\" value=\"{}\"/>",
+            property.name,
+            xml_escape(property.default)
+        );
+    }
+    let _ = writeln!(out, "  </module>\n");
+
+    let _ = writeln!(out, "{}", "Example:".bold());
+    for line in metadata.example.lines() {
+        let _ = writeln!(out, "  {line}");
+    }
+    out.push('\n');
+    for message in &example.messages {
+        let _ = writeln!(
+            out,
+            "  {}:{}:{}: {}",
+            EXAMPLE_PATH, message.location.line, message.location.column, message.message
+        );
+    }
+    if let Some(fixed) = &example.fixed {
+        let _ = writeln!(out, "\n{}", "Fixed:".bold());
+        out.push_str(&format_diff(
+            Path::new(EXAMPLE_PATH),
+            metadata.example,
+            fixed,
+        ));
+    }
+    out
+}
+
+fn format_property(out: &mut String, property: &PropertySpec) {
+    let header = match property.kind {
+        PropertyKind::Enum(values) => format!(
+            "one of {}, default: {}",
+            values.join(", "),
+            property.default
+        ),
+        // Token lists are too long for the header line
+        PropertyKind::Tokens(_) | PropertyKind::Set(_) => property.kind.name().to_string(),
+        kind if property.default.is_empty() => kind.name().to_string(),
+        kind => format!("{}, default: {}", kind.name(), property.default),
+    };
+    let _ = writeln!(out, "  {} ({header})", property.name.bold());
+    let _ = writeln!(out, "      {}", property.description);
+    if let PropertyKind::Tokens(values) | PropertyKind::Set(values) = property.kind {
+        let _ = writeln!(out, "      Default: {}", property.default);
+        let _ = writeln!(out, "      Accepts: {}", values.join(", "));
+    }
+    if !property.supported {
+        let _ = writeln!(out, "      {}", "Ignored by lintal".yellow());
+    }
+}
+
+fn rule_to_json(name: &str, metadata: &RuleMetadata) -> Value {
+    json!({
+        "name": name,
+        "category": metadata.category.as_str(),
+        "description": metadata.description,
+        "fix": fix_name(metadata.fix),
+        "properties": metadata.properties.iter().map(|property| json!({
+            "name": property.name,
+            "type": property.kind.name(),
+            "values": property.kind.values(),
+            "default": property.default,
+            "description": property.description,
+            "supported": property.supported,
+        })).collect::<Vec<_>>(),
+        "compatibility": metadata.compatibility,
+    })
+}
+
+fn fix_name(fix: FixAvailability) -> &'static str {
+    match fix {
+        FixAvailability::Always => "always",
+        FixAvailability::Sometimes => "sometimes",
+        FixAvailability::None => "none",
+    }
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_is_fixed() {
+        colored::control::set_override(false);
+        let registry = RuleRegistry::builtin();
+        let metadata = registry.metadata("WhitespaceAround").unwrap();
+
+        let example = run_example(&registry, "WhitespaceAround", metadata);
+        assert_eq!(example.messages.len(), 2);
+        assert_eq!(
+            example.fixed.as_deref(),
+            Some("class Example {\n    int count = 1;\n}\n")
+        );
+
+        let text = format_rule("WhitespaceAround", metadata, &example);
+        assert!(text.contains("      <property name=\"allowEmptyLambdas\" value=\"false\"/>\n"));
+        assert!(text.contains("  Example.java:2:14: Missing whitespace before `=`\n"));
+        assert!(text.contains("-    int count=1;\n+    int count = 1;\n"));
+    }
+
+    #[test]
+    fn test_unfixable_example() {
+        let registry = RuleRegistry::builtin();
+        let metadata = registry.metadata("MemberName").unwrap();
+
+        let example = run_example(&registry, "MemberName", metadata);
+        assert_eq!(example.messages.len(), 1);
+        assert!(example.fixed.is_none());
+    }
+}
@@ -0,0 +1,211 @@
+//! An open text document, kept parsed as the editor sends changes.
+
+use lintal_java_parser::JavaParser;
+use lintal_source_file::{LineIndex, OneIndexed, PositionEncoding, SourceLocation};
+use lintal_text_size::{TextLen, TextRange, TextSize};
+use lsp_types::{Position, Range, TextDocumentContentChangeEvent};
+use tree_sitter::{InputEdit, Point, Tree};
+
+/// The text of an open document and its syntax tree.
+///
+/// Positions exchanged with the client are in UTF-16 code units.
+pub(super) struct Document {
+    text: String,
+    version: i32,
+    index: LineIndex,
+    /// `None` if the text could not be parsed.
+    tree: Option<Tree>,
+}
+
+impl Document {
+    /// Create a document and parse it from scratch.
+    pub(super) fn new(text: String, version: i32, parser: &mut JavaParser) -> Self {
+        let index = LineIndex::from_source_text(&text);
+        let tree = parser.parse(&text).map(|result| result.tree);
+        Self {
+            text,
+            version,
+            index,
+            tree,
+        }
+    }
+
+    pub(super) fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub(super) fn version(&self) -> i32 {
+        self.version
+    }
+
+    pub(super) fn tree(&self) -> Option<&Tree> {
+        self.tree.as_ref()
+    }
+
+    /// Apply the client's changes, in order, and re-parse.
+    ///
+    /// Ranged changes are also applied to the syntax tree so that the new
+    /// text is parsed incrementally, reusing the unchanged parts of the old
+    /// tree. Replacing the whole text parses from scratch.
+    pub(super) fn apply_changes(
+        &mut self,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        version: i32,
+        parser: &mut JavaParser,
+    ) {
+        let mut tree = self.tree.take();
+        for change in changes {
+            let Some(range) = change.range else {
+                self.text = change.text;
+                self.index = LineIndex::from_source_text(&self.text);
+                tree = None;
+                continue;
+            };
+
+            let range = self.text_range(range);
+            let start_position = self.point(range.start());
+            let old_end_position = self.point(range.end());
+            self.text.replace_range(
+                usize::from(range.start())..usize::from(range.end()),
+                &change.text,
+            );
+            self.index = LineIndex::from_source_text(&self.text);
+            let new_end = range.start() + change.text.text_len();
+
+            if let Some(tree) = &mut tree {
+                tree.edit(&InputEdit {
+                    start_byte: range.start().to_usize(),
+                    old_end_byte: range.end().to_usize(),
+                    new_end_byte: new_end.to_usize(),
+                    start_position,
+                    old_end_position,
+                    new_end_position: self.point(new_end),
+                });
+            }
+        }
+
+        let result = match &tree {
+            Some(old_tree) => parser.parse_with_old_tree(&self.text, old_tree),
+            None => parser.parse(&self.text),
+        };
+        self.tree = result.map(|result| result.tree);
+        self.version = version;
+    }
+
+    /// The byte offset of a client position, clamped to the text.
+    pub(super) fn offset(&self, position: Position) -> TextSize {
+        let line = position.line as usize;
+        if line >= self.index.line_count() {
+            return self.text.text_len();
+        }
+        self.index.offset(
+            SourceLocation {
+                line: OneIndexed::from_zero_indexed(line),
+                character_offset: OneIndexed::from_zero_indexed(position.character as usize),
+            },
+            &self.text,
+            PositionEncoding::Utf16,
+        )
+    }
+
+    /// The client position of a byte offset.
+    pub(super) fn position(&self, offset: TextSize) -> Position {
+        let location = self
+            .index
+            .source_location(offset, &self.text, PositionEncoding::Utf16);
+        Position {
+            line: u32::try_from(location.line.to_zero_indexed()).unwrap_or(u32::MAX),
+            character: u32::try_from(location.character_offset.to_zero_indexed())
+                .unwrap_or(u32::MAX),
+        }
+    }
+
+    /// The client range of a byte range.
+    pub(super) fn range(&self, range: TextRange) -> Range {
+        Range {
+            start: self.position(range.start()),
+            end: self.position(range.end()),
+        }
+    }
+
+    /// The byte range of a client range.
+    pub(super) fn text_range(&self, range: Range) -> TextRange {
+        let start = self.offset(range.start);
+        TextRange::new(start, self.offset(range.end).max(start))
+    }
+
+    /// The whole text as a client range.
+    pub(super) fn full_range(&self) -> Range {
+        self.range(TextRange::up_to(self.text.text_len()))
+    }
+
+    /// The tree-sitter point (row and byte column) of a byte offset.
+    fn point(&self, offset: TextSize) -> Point {
+        let line = self.index.line_index(offset);
+        let line_start = self.index.line_start(line, &self.text);
+        Point {
+            row: line.to_zero_indexed(),
+            column: (offset - line_start).to_usize(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(start: (u32, u32), end: (u32, u32), text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: Some(Range {
+                start: Position::new(start.0, start.1),
+                end: Position::new(end.0, end.1),
+            }),
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_incremental_parse_matches_fresh_parse() {
+        let mut parser = JavaParser::new();
+        let mut document =
+            Document::new("class A {\n    int x=1;\n}\n".to_string(), 1, &mut parser);
+
+        document.apply_changes(
+            vec![
+                change((1, 9), (1, 10), " = "),
+                change((2, 0), (2, 0), "    void run() {}\n"),
+            ],
+            2,
+            &mut parser,
+        );
+        assert_eq!(
+            document.text(),
+            "class A {\n    int x = 1;\n    void run() {}\n}\n"
+        );
+        assert_eq!(document.version(), 2);
+
+        let fresh = parser.parse(document.text()).unwrap().tree;
+        assert_eq!(
+            document.tree().unwrap().root_node().to_sexp(),
+            fresh.root_node().to_sexp()
+        );
+    }
+
+    #[test]
+    fn test_utf16_positions() {
+        let mut parser = JavaParser::new();
+        let document = Document::new("// 😀 é\nclass A {}\n".to_string(), 1, &mut parser);
+
+        // The emoji is two UTF-16 code units but four bytes.
+        let offset = document.offset(Position::new(0, 6));
+        assert_eq!(&document.text()[offset.to_usize()..], "é\nclass A {}\n");
+        assert_eq!(document.position(offset), Position::new(0, 6));
+
+        // Positions past the end are clamped.
+        assert_eq!(
+            document.offset(Position::new(9, 0)),
+            document.text().text_len()
+        );
+    }
+}
@@ -0,0 +1,706 @@
+//! `lintal server`: a language server over stdio.
+//!
+//! Open documents are linted on every change and their diagnostics
+//! published to the client. Each [`Fix`](lintal_diagnostics::Fix) is offered
+//! as a quick fix, and a `source.fixAll.lintal` action applies every safe fix
+//! from rules in fix mode, as `lintal fix` would. Configuration is loaded
+//! relative to the workspace root and reloaded whenever checkstyle.xml,
+//! lintal.toml or the suppressions file changes.
+
+mod document;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use lintal::RuleDiagnostic;
+use lintal_checkstyle::RuleMode;
+use lintal_diagnostics::{Applicability, Severity};
+use lintal_java_parser::JavaParser;
+use lintal_text_size::Ranged;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument, DidOpenTextDocument,
+    DidSaveTextDocument, Notification as _, PublishDiagnostics, ShowMessage,
+};
+use lsp_types::request::{CodeActionRequest, RegisterCapability, Request as _};
+use lsp_types::{
+    CodeAction, CodeActionDisabled, CodeActionKind, CodeActionOptions, CodeActionOrCommand,
+    CodeActionParams, CodeActionProviderCapability, DiagnosticSeverity,
+    DidChangeWatchedFilesRegistrationOptions, FileSystemWatcher, GlobPattern, InitializeParams,
+    MessageType, NumberOrString, PositionEncodingKind, PublishDiagnosticsParams, Registration,
+    RegistrationParams, ServerCapabilities, ShowMessageParams, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextDocumentSyncOptions, TextDocumentSyncSaveOptions, TextEdit, Uri,
+    WorkspaceEdit,
+};
+use serde_json::json;
+
+use crate::output::applicability_name;
+use crate::{ConfigArgs, LoadedLinter, load_rules};
+use document::Document;
+
+/// The kind of the "fix all" source action.
+const FIX_ALL: CodeActionKind = CodeActionKind::new("source.fixAll.lintal");
+
+/// Run the language server over stdio until the client shuts it down.
+pub(crate) fn run(config_args: &ConfigArgs) -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+    serve(&connection, config_args)?;
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Initialize the session on `connection` and handle messages until the
+/// client shuts the server down.
+fn serve(connection: &Connection, config_args: &ConfigArgs) -> Result<()> {
+    let capabilities = ServerCapabilities {
+        position_encoding: Some(PositionEncodingKind::UTF16),
+        text_document_sync: Some(TextDocumentSyncCapability::Options(
+            TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::INCREMENTAL),
+                save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                ..TextDocumentSyncOptions::default()
+            },
+        )),
+        code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+            code_action_kinds: Some(vec![CodeActionKind::QUICKFIX, FIX_ALL]),
+            ..CodeActionOptions::default()
+        })),
+        ..ServerCapabilities::default()
+    };
+    let params = connection.initialize(serde_json::to_value(capabilities)?)?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+
+    let mut server = Server::new(connection, config_args, workspace_root(&params));
+    if params
+        .capabilities
+        .workspace
+        .and_then(|workspace| workspace.did_change_watched_files)
+        .and_then(|watched| watched.dynamic_registration)
+        .unwrap_or(false)
+    {
+        server.watch_config_files()?;
+    }
+    server.run()
+}
+
+/// The root directory of the client's workspace, if it has one on disk.
+#[allow(deprecated)]
+fn workspace_root(params: &InitializeParams) -> Option<PathBuf> {
+    params
+        .workspace_folders
+        .as_ref()
+        .and_then(|folders| folders.first())
+        .map(|folder| &folder.uri)
+        .or(params.root_uri.as_ref())
+        .and_then(uri_to_path)
+}
+
+/// The file system path of a `file:` URI.
+fn uri_to_path(uri: &Uri) -> Option<PathBuf> {
+    if !uri
+        .scheme()
+        .is_some_and(|scheme| scheme.as_str().eq_ignore_ascii_case("file"))
+    {
+        return None;
+    }
+    let path = uri.path().as_estr().decode().into_string_lossy();
+    // Windows paths come through as `/C:/...`
+    if cfg!(windows) && path.get(2..3) == Some(":") {
+        return Some(PathBuf::from(&path[1..]));
+    }
+    Some(PathBuf::from(path.as_ref()))
+}
+
+/// An open document together with its latest diagnostics.
+struct OpenDocument {
+    document: Document,
+    diagnostics: Vec<RuleDiagnostic>,
+}
+
+struct Server<'a> {
+    connection: &'a Connection,
+    config_args: ConfigArgs,
+    /// The workspace root, used to find configuration.
+    root: Option<PathBuf>,
+    /// `None` if no configuration has loaded successfully yet.
+    linter: Option<LoadedLinter>,
+    parser: JavaParser,
+    documents: HashMap<Uri, OpenDocument>,
+    next_request_id: i32,
+}
+
+impl<'a> Server<'a> {
+    fn new(connection: &'a Connection, config_args: &ConfigArgs, root: Option<PathBuf>) -> Self {
+        // Configuration is discovered relative to the working directory, as
+        // if lintal were run from the workspace root, so explicit paths have
+        // to be resolved before changing it.
+        let absolute = |path: &Option<PathBuf>| {
+            path.as_deref()
+                .map(|path| std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()))
+        };
+        let config_args = ConfigArgs {
+            config: absolute(&config_args.config),
+            config_loc: absolute(&config_args.config_loc),
+            define: config_args.define.clone(),
+        };
+        if let Some(root) = &root
+            && let Err(err) = std::env::set_current_dir(root)
+        {
+            eprintln!("Failed to change to {}: {err}", root.display());
+        }
+
+        let mut server = Self {
+            connection,
+            config_args,
+            root,
+            linter: None,
+            parser: JavaParser::new(),
+            documents: HashMap::new(),
+            next_request_id: 0,
+        };
+        server.reload_config();
+        server
+    }
+
+    fn run(&mut self) -> Result<()> {
+        for message in &self.connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    self.handle_request(request)?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&mut self, request: Request) -> Result<()> {
+        let response = match request.method.as_str() {
+            CodeActionRequest::METHOD => {
+                let (id, params) =
+                    request.extract::<CodeActionParams>(CodeActionRequest::METHOD)?;
+                Response::new_ok(id, self.code_actions(&params))
+            }
+            _ => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unhandled method {}", request.method),
+            ),
+        };
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = notification_params::<DidOpenTextDocument>(notification)?;
+                let item = params.text_document;
+                let document = Document::new(item.text, item.version, &mut self.parser);
+                self.documents.insert(
+                    item.uri.clone(),
+                    OpenDocument {
+                        document,
+                        diagnostics: Vec::new(),
+                    },
+                );
+                self.publish(&item.uri)?;
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = notification_params::<DidChangeTextDocument>(notification)?;
+                let uri = params.text_document.uri;
+                if let Some(open) = self.documents.get_mut(&uri) {
+                    open.document.apply_changes(
+                        params.content_changes,
+                        params.text_document.version,
+                        &mut self.parser,
+                    );
+                    self.publish(&uri)?;
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = notification_params::<DidCloseTextDocument>(notification)?;
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                self.send_diagnostics(uri, Vec::new(), None)?;
+            }
+            DidSaveTextDocument::METHOD => {
+                let params = notification_params::<DidSaveTextDocument>(notification)?;
+                if self.is_config_file(&params.text_document.uri) {
+                    self.reload_config();
+                    self.publish_all()?;
+                }
+            }
+            DidChangeWatchedFiles::METHOD => {
+                let params = notification_params::<DidChangeWatchedFiles>(notification)?;
+                if params
+                    .changes
+                    .iter()
+                    .any(|change| self.is_config_file(&change.uri))
+                {
+                    self.reload_config();
+                    self.publish_all()?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Ask the client to tell us about changes to configuration files.
+    fn watch_config_files(&mut self) -> Result<()> {
+        let watchers = ["**/*.xml", "**/*.toml"]
+            .into_iter()
+            .map(|pattern| FileSystemWatcher {
+                glob_pattern: GlobPattern::String(pattern.to_string()),
+                kind: None,
+            })
+            .collect();
+        let params = RegistrationParams {
+            registrations: vec![Registration {
+                id: "lintal-config-files".to_string(),
+                method: DidChangeWatchedFiles::METHOD.to_string(),
+                register_options: Some(serde_json::to_value(
+                    DidChangeWatchedFilesRegistrationOptions { watchers },
+                )?),
+            }],
+        };
+        self.next_request_id += 1;
+        let request = Request::new(
+            RequestId::from(self.next_request_id),
+            RegisterCapability::METHOD.to_string(),
+            params,
+        );
+        self.connection.sender.send(request.into())?;
+        Ok(())
+    }
+
+    /// Returns true if `uri` is a configuration file whose changes require
+    /// reloading the linter.
+    fn is_config_file(&self, uri: &Uri) -> bool {
+        uri_to_path(uri).is_some_and(|path| {
+            crate::is_config_file(&path, self.linter.as_ref())
+                || self
+                    .config_args
+                    .config
+                    .as_deref()
+                    .is_some_and(|config| crate::same_file(config, &path))
+        })
+    }
+
+    /// Load the configuration again. On failure the previous configuration
+    /// stays in effect and the error is shown to the user.
+    fn reload_config(&mut self) {
+        let base_paths: Vec<PathBuf> = self.root.iter().cloned().collect();
+        match load_rules(&self.config_args, &base_paths) {
+            Ok((linter, _)) => self.linter = Some(linter),
+            Err(err) => {
+                let message = format!("lintal: failed to load configuration: {err:#}");
+                eprintln!("{message}");
+                let params = ShowMessageParams {
+                    typ: MessageType::ERROR,
+                    message,
+                };
+                // The client may already be gone; nothing else to report to
+                let _ = self
+                    .connection
+                    .sender
+                    .send(Notification::new(ShowMessage::METHOD.to_string(), params).into());
+            }
+        }
+    }
+
+    /// Lint every open document again and publish the results.
+    fn publish_all(&mut self) -> Result<()> {
+        let uris: Vec<Uri> = self.documents.keys().cloned().collect();
+        for uri in uris {
+            self.publish(&uri)?;
+        }
+        Ok(())
+    }
+
+    /// Lint the open document at `uri` and publish its diagnostics.
+    fn publish(&mut self, uri: &Uri) -> Result<()> {
+        let Some(open) = self.documents.get_mut(uri) else {
+            return Ok(());
+        };
+        open.diagnostics = match (&self.linter, open.document.tree()) {
+            (Some(linter), Some(tree)) => {
+                linter.check_tree(open.document.text(), &document_path(uri), tree)
+            }
+            _ => Vec::new(),
+        };
+        let diagnostics = open
+            .diagnostics
+            .iter()
+            .map(|d| lsp_diagnostic(&open.document, d))
+            .collect();
+        let version = open.document.version();
+        self.send_diagnostics(uri.clone(), diagnostics, Some(version))
+    }
+
+    fn send_diagnostics(
+        &self,
+        uri: Uri,
+        diagnostics: Vec<lsp_types::Diagnostic>,
+        version: Option<i32>,
+    ) -> Result<()> {
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version,
+        };
+        self.connection
+            .sender
+            .send(Notification::new(PublishDiagnostics::METHOD.to_string(), params).into())?;
+        Ok(())
+    }
+
+    /// Quick fixes for the diagnostics in the requested range, followed by
+    /// the "fix all" action.
+    fn code_actions(&self, params: &CodeActionParams) -> Vec<CodeActionOrCommand> {
+        let uri = &params.text_document.uri;
+        let Some(open) = self.documents.get(uri) else {
+            return Vec::new();
+        };
+        let document = &open.document;
+        let wanted = |kind: &CodeActionKind| {
+            params.context.only.as_ref().is_none_or(|only| {
+                only.iter().any(|requested| {
+                    kind.as_str() == requested.as_str()
+                        || kind
+                            .as_str()
+                            .strip_prefix(requested.as_str())
+                            .is_some_and(|rest| rest.starts_with('.'))
+                })
+            })
+        };
+
+        let mut actions = Vec::new();
+        if wanted(&CodeActionKind::QUICKFIX) {
+            let range = document.text_range(params.range);
+            for d in &open.diagnostics {
+                let Some(fix) = &d.diagnostic.fix else {
+                    continue;
+                };
+                let diagnostic_range = d.diagnostic.range;
+                if diagnostic_range.start() > range.end() || range.start() > diagnostic_range.end()
+                {
+                    continue;
+                }
+                let applicability = fix.applicability();
+                let edits = fix
+                    .edits()
+                    .iter()
+                    .map(|edit| TextEdit {
+                        range: document.range(edit.range()),
+                        new_text: edit.content().unwrap_or_default().to_string(),
+                    })
+                    .collect();
+                let title = match applicability {
+                    Applicability::Safe => format!("Fix {}: {}", d.rule, d.diagnostic.kind.body),
+                    _ => format!(
+                        "Fix {}: {} ({})",
+                        d.rule,
+                        d.diagnostic.kind.body,
+                        applicability_name(applicability)
+                    ),
+                };
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![lsp_diagnostic(document, d)]),
+                    edit: Some(workspace_edit(uri, edits)),
+                    is_preferred: Some(applicability == Applicability::Safe),
+                    disabled: (applicability == Applicability::DisplayOnly).then(|| {
+                        CodeActionDisabled {
+                            reason: "This fix is shown for reference only".to_string(),
+                        }
+                    }),
+                    data: Some(json!({ "applicability": applicability_name(applicability) })),
+                    ..CodeAction::default()
+                }));
+            }
+        }
+
+        if wanted(&FIX_ALL)
+            && let Some(action) = self.fix_all(uri, open)
+        {
+            actions.push(CodeActionOrCommand::CodeAction(action));
+        }
+        actions
+    }
+
+    /// Apply every safe fix from rules in fix mode, as `lintal fix` does,
+    /// replacing the whole document.
+    fn fix_all(&self, uri: &Uri, open: &OpenDocument) -> Option<CodeAction> {
+        let linter = self.linter.as_ref()?;
+        let fixable = |mode: RuleMode| mode == RuleMode::Fix;
+        let has_fixes = open.diagnostics.iter().any(|d| {
+            fixable(d.mode)
+                && d.diagnostic
+                    .fix
+                    .as_ref()
+                    .is_some_and(|fix| fix.applies(Applicability::Safe))
+        });
+        if !has_fixes {
+            return None;
+        }
+
+        let document = &open.document;
+        let outcome = linter.fix(document.text(), &document_path(uri), Applicability::Safe)?;
+        if outcome.fixed == 0 || outcome.source == document.text() {
+            return None;
+        }
+
+        let edit = TextEdit {
+            range: document.full_range(),
+            new_text: outcome.source,
+        };
+        Some(CodeAction {
+            title: "Fix all lintal violations".to_string(),
+            kind: Some(FIX_ALL),
+            edit: Some(workspace_edit(uri, vec![edit])),
+            data: Some(json!({ "applicability": applicability_name(Applicability::Safe) })),
+            ..CodeAction::default()
+        })
+    }
+}
+
+/// Deserialize the parameters of a notification of type `N`.
+fn notification_params<N: lsp_types::notification::Notification>(
+    notification: Notification,
+) -> Result<N::Params> {
+    Ok(notification.extract(N::METHOD)?)
+}
+
+/// The path used to match suppressions for the document at `uri`.
+fn document_path(uri: &Uri) -> PathBuf {
+    uri_to_path(uri).unwrap_or_else(|| PathBuf::from(uri.as_str()))
+}
+
+fn workspace_edit(uri: &Uri, edits: Vec<TextEdit>) -> WorkspaceEdit {
+    WorkspaceEdit {
+        changes: Some(HashMap::from([(uri.clone(), edits)])),
+        ..WorkspaceEdit::default()
+    }
+}
+
+fn lsp_diagnostic(document: &Document, d: &RuleDiagnostic) -> lsp_types::Diagnostic {
+    let severity = match d.diagnostic.severity {
+        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Info => DiagnosticSeverity::INFORMATION,
+        Severity::Ignore => DiagnosticSeverity::HINT,
+    };
+    lsp_types::Diagnostic {
+        range: document.range(d.diagnostic.range),
+        severity: Some(severity),
+        code: Some(NumberOrString::String(d.rule.to_string())),
+        source: Some("lintal".to_string()),
+        message: d.diagnostic.kind.body.clone(),
+        data: d
+            .diagnostic
+            .fix
+            .as_ref()
+            .map(|fix| json!({ "fix": applicability_name(fix.applicability()) })),
+        ..lsp_types::Diagnostic::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::time::Duration;
+
+    use serde_json::Value;
+
+    use super::*;
+
+    const CONFIG: &str = r#"<?xml version="1.0"?>
+<module name="Checker">
+  <module name="TreeWalker">
+    <module name="WhitespaceAround"/>
+  </module>
+</module>
+"#;
+
+    const EMPTY_CONFIG: &str = r#"<?xml version="1.0"?>
+<module name="Checker">
+  <module name="TreeWalker"/>
+</module>
+"#;
+
+    const URI: &str = "file:///project/src/A.java";
+
+    /// A scripted client talking to a server on another thread.
+    struct Client {
+        connection: Connection,
+        server: std::thread::JoinHandle<Result<()>>,
+        next_id: i32,
+    }
+
+    impl Client {
+        fn start(config: &Path) -> Self {
+            let (client, server) = Connection::memory();
+            let config_args = ConfigArgs {
+                config: Some(config.to_path_buf()),
+                config_loc: None,
+                define: Vec::new(),
+            };
+            let server = std::thread::spawn(move || serve(&server, &config_args));
+            let mut client = Self {
+                connection: client,
+                server,
+                next_id: 0,
+            };
+            client.request("initialize", json!({ "capabilities": {} }));
+            client.notify("initialized", json!({}));
+            client
+        }
+
+        fn request(&mut self, method: &str, params: Value) -> Value {
+            self.next_id += 1;
+            let id = RequestId::from(self.next_id);
+            let request = Request::new(id.clone(), method.to_string(), params);
+            self.connection.sender.send(request.into()).unwrap();
+            loop {
+                if let Message::Response(response) = self.recv()
+                    && response.id == id
+                {
+                    return response.result.unwrap_or(Value::Null);
+                }
+            }
+        }
+
+        fn notify(&self, method: &str, params: Value) {
+            let notification = Notification::new(method.to_string(), params);
+            self.connection.sender.send(notification.into()).unwrap();
+        }
+
+        /// Wait for the next published diagnostics.
+        fn diagnostics(&self) -> Value {
+            loop {
+                if let Message::Notification(notification) = self.recv()
+                    && notification.method == PublishDiagnostics::METHOD
+                {
+                    return notification.params;
+                }
+            }
+        }
+
+        fn recv(&self) -> Message {
+            self.connection
+                .receiver
+                .recv_timeout(Duration::from_secs(30))
+                .expect("server did not respond")
+        }
+
+        fn shutdown(mut self) {
+            self.request("shutdown", Value::Null);
+            self.notify("exit", Value::Null);
+            self.server.join().unwrap().unwrap();
+        }
+    }
+
+    fn open(client: &Client, text: &str) {
+        client.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": { "uri": URI, "languageId": "java", "version": 1, "text": text }
+            }),
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_and_code_actions() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("checkstyle.xml");
+        std::fs::write(&config, CONFIG).unwrap();
+        let mut client = Client::start(&config);
+
+        open(&client, "class A {\n    int x=1;\n}\n");
+        let published = client.diagnostics();
+        assert_eq!(published["version"], 1);
+        let diagnostics = published["diagnostics"].as_array().unwrap();
+        assert!(!diagnostics.is_empty());
+        assert_eq!(diagnostics[0]["source"], "lintal");
+        assert_eq!(diagnostics[0]["code"], "WhitespaceAround");
+        assert_eq!(diagnostics[0]["range"]["start"]["line"], 1);
+
+        let actions = client.request(
+            "textDocument/codeAction",
+            json!({
+                "textDocument": { "uri": URI },
+                "range": { "start": { "line": 1, "character": 0 }, "end": { "line": 1, "character": 12 } },
+                "context": { "diagnostics": [] }
+            }),
+        );
+        let actions = actions.as_array().unwrap();
+        let quick_fix = &actions[0];
+        assert_eq!(quick_fix["kind"], "quickfix");
+        assert_eq!(quick_fix["data"]["applicability"], "safe");
+        assert_eq!(quick_fix["isPreferred"], true);
+        let fix_all = actions.last().unwrap();
+        assert_eq!(fix_all["kind"], "source.fixAll.lintal");
+        assert_eq!(
+            fix_all["edit"]["changes"][URI][0]["newText"],
+            "class A {\n    int x = 1;\n}\n"
+        );
+
+        // Only the requested kinds are returned.
+        let actions = client.request(
+            "textDocument/codeAction",
+            json!({
+                "textDocument": { "uri": URI },
+                "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 0, "character": 0 } },
+                "context": { "diagnostics": [], "only": ["source.fixAll"] }
+            }),
+        );
+        assert_eq!(actions.as_array().unwrap().len(), 1);
+
+        client.notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": URI, "version": 2 },
+                "contentChanges": [{
+                    "range": { "start": { "line": 1, "character": 9 }, "end": { "line": 1, "character": 10 } },
+                    "text": " = "
+                }]
+            }),
+        );
+        let published = client.diagnostics();
+        assert_eq!(published["version"], 2);
+        assert_eq!(published["diagnostics"], json!([]));
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn test_reload_on_config_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("checkstyle.xml");
+        std::fs::write(&config, CONFIG).unwrap();
+        let client = Client::start(&config);
+
+        open(&client, "class A {\n    int x=1;\n}\n");
+        assert_ne!(client.diagnostics()["diagnostics"], json!([]));
+
+        std::fs::write(&config, EMPTY_CONFIG).unwrap();
+        let config_uri = format!("file://{}", config.display());
+        client.notify(
+            "workspace/didChangeWatchedFiles",
+            json!({ "changes": [{ "uri": config_uri, "type": 2 }] }),
+        );
+        assert_eq!(client.diagnostics()["diagnostics"], json!([]));
+
+        client.shutdown();
+    }
+}
@@ -0,0 +1,452 @@
+//! `--watch` for `lintal check` and `lintal fix`.
+//!
+//! The input paths are watched recursively, together with the directories
+//! holding the configuration files. After the first full run, only the Java
+//! files that changed are checked or fixed again, with the loaded rules kept
+//! in memory. A change to checkstyle.xml, lintal.toml or the suppressions
+//! file reloads the configuration and processes every file again.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, channel};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher as _};
+use rayon::prelude::*;
+
+use crate::output::FileMessages;
+use crate::{
+    CheckOptions, ConfigArgs, FileFixResult, FixOptions, LoadedLinter, cache, check_file,
+    collect_java_files, fix_file, is_config_file, load_check_rules, load_fix_rules,
+    print_check_summary, report_file_errors, report_fix_results, source_dirs,
+};
+
+/// How long to wait for more events after one arrives, so that a burst of
+/// writes (a save-all, a branch switch) is handled as a single change.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Run `lintal check --watch` until interrupted.
+pub(crate) fn check(
+    paths: &[PathBuf],
+    config_args: &ConfigArgs,
+    options: &CheckOptions,
+) -> Result<()> {
+    let load = || load_check_rules(config_args, paths, options).map(|(linter, _)| linter);
+    let mut linter = load()?;
+    let mut watcher = Watcher::new(paths)?;
+    watcher.watch_config(&linter)?;
+
+    // The latest messages of every file, so that each run can report all of
+    // them while only checking the files that changed
+    let mut results: BTreeMap<PathBuf, FileMessages> = BTreeMap::new();
+    let mut files = collect_java_files(paths, &linter);
+    loop {
+        let checked: Vec<(PathBuf, Option<FileMessages>)> = files
+            .par_iter()
+            .map(|path| {
+                if !path.exists() || linter.is_file_suppressed(path) {
+                    return (path.clone(), None);
+                }
+                let keep_source = options.output_format.needs_source();
+                match check_file(path, &linter, None, None, false, keep_source) {
+                    Ok(result) => {
+                        if let Some(error) = &result.messages.error {
+                            eprintln!("{}: {}", path.display(), error);
+                        }
+                        (path.clone(), Some(result.messages))
+                    }
+                    Err(error) => {
+                        eprintln!("{error:#}");
+                        (path.clone(), None)
+                    }
+                }
+            })
+            .collect();
+        for (path, messages) in checked {
+            watcher.inputs.record(&path);
+            match messages {
+                Some(messages) => results.insert(path, messages),
+                None => results.remove(&path),
+            };
+        }
+
+        let report: Vec<FileMessages> = results.values().cloned().collect();
+        let stdout = std::io::stdout();
+        let mut writer = std::io::BufWriter::new(stdout.lock());
+        options
+            .emitter(linter.settings().tab_width())
+            .emit(&mut writer, &report)?;
+        std::io::Write::flush(&mut writer)?;
+        drop(writer);
+        eprintln!("Checked {} files", files.len());
+        if options.has_summary() {
+            print_check_summary(&report);
+        }
+
+        eprintln!("{}", "Watching for changes...".dimmed());
+        let changes = watcher.wait(&linter)?;
+        files = match changes.rerun(load) {
+            Rerun::Reloaded(reloaded) => {
+                linter = reloaded;
+                watcher.watch_config(&linter)?;
+                results.clear();
+                collect_java_files(paths, &linter)
+            }
+            Rerun::Files(files) => files.into_iter().collect(),
+        };
+    }
+}
+
+/// Run `lintal fix --watch` until interrupted.
+pub(crate) fn fix(paths: &[PathBuf], config_args: &ConfigArgs, options: &FixOptions) -> Result<()> {
+    let load = || load_fix_rules(config_args, paths, options, true);
+    let (mut linter, mut applicability) = load()?;
+    let mut watcher = Watcher::new(paths)?;
+    watcher.watch_config(&linter)?;
+
+    let mut files = collect_java_files(paths, &linter);
+    loop {
+        let outcomes: Vec<Result<FileFixResult>> = files
+            .par_iter()
+            .filter(|path| path.exists() && !linter.is_file_suppressed(path))
+            .map(|path| fix_file(path, &linter, applicability, options, None))
+            .collect();
+        let (results, failed) = report_file_errors(outcomes);
+        report_fix_results(results, failed, options, linter.encoding)?;
+        // The files just written must not trigger another run
+        for path in &files {
+            watcher.inputs.record(path);
+        }
+
+        eprintln!("{}", "Watching for changes...".dimmed());
+        let changes = watcher.wait(&linter)?;
+        files = match changes.rerun(load) {
+            Rerun::Reloaded(reloaded) => {
+                (linter, applicability) = reloaded;
+                watcher.watch_config(&linter)?;
+                collect_java_files(paths, &linter)
+            }
+            Rerun::Files(files) => files.into_iter().collect(),
+        };
+    }
+}
+
+/// Load the configuration again, keeping the current one if that fails.
+fn reload<T>(load: impl FnOnce() -> Result<T>) -> Option<T> {
+    eprintln!("Configuration changed, reloading");
+    load()
+        .inspect_err(|error| {
+            eprintln!(
+                "{}",
+                format!("Failed to reload configuration: {error:#}").red()
+            );
+        })
+        .ok()
+}
+
+/// What changed since the last run.
+struct Changes {
+    /// Java files that were created, modified or removed, as they would be
+    /// found under the input paths.
+    files: BTreeSet<PathBuf>,
+    /// Set if a configuration file changed.
+    config: bool,
+}
+
+impl Changes {
+    /// Decide what to process next: every file with the configuration
+    /// reloaded by `load` if it changed, otherwise the changed files. If
+    /// reloading fails, the current configuration is kept.
+    fn rerun<T>(self, load: impl FnOnce() -> Result<T>) -> Rerun<T> {
+        match self.config.then(|| reload(load)).flatten() {
+            Some(reloaded) => Rerun::Reloaded(reloaded),
+            None => Rerun::Files(self.files),
+        }
+    }
+}
+
+/// What the next run processes.
+enum Rerun<T> {
+    /// Every file, with this reloaded configuration.
+    Reloaded(T),
+    /// Only these changed files.
+    Files(BTreeSet<PathBuf>),
+}
+
+/// Watches the input paths and the configuration files.
+struct Watcher {
+    watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    inputs: Inputs,
+    /// Directories watched for configuration files.
+    config_dirs: HashSet<PathBuf>,
+}
+
+impl Watcher {
+    fn new(paths: &[PathBuf]) -> Result<Self> {
+        let (sender, events) = channel();
+        let mut watcher =
+            notify::recommended_watcher(sender).context("Failed to start watching files")?;
+
+        let inputs = Inputs::new(paths)?;
+        for (path, canonical) in &inputs.roots {
+            // Editors often save by replacing a file, which ends a watch on
+            // the file itself, so single files are watched through their
+            // directory
+            let (target, mode) = if canonical.is_dir() {
+                (canonical.as_path(), RecursiveMode::Recursive)
+            } else {
+                (
+                    canonical.parent().unwrap_or(canonical),
+                    RecursiveMode::NonRecursive,
+                )
+            };
+            watcher
+                .watch(target, mode)
+                .with_context(|| format!("Failed to watch {}", path.display()))?;
+        }
+
+        Ok(Self {
+            watcher,
+            events,
+            inputs,
+            config_dirs: HashSet::new(),
+        })
+    }
+
+    /// Also watch the directories holding the files `linter` was loaded
+    /// from, and the working directory, where lintal.toml is found.
+    fn watch_config(&mut self, linter: &LoadedLinter) -> Result<()> {
+        let dirs: Vec<PathBuf> = [&linter.config_path, &linter.file_suppressions_path]
+            .into_iter()
+            .flatten()
+            .filter_map(|file| Some(file.canonicalize().ok()?.parent()?.to_path_buf()))
+            .chain(std::env::current_dir().ok())
+            .collect();
+        for dir in dirs {
+            let covered = self
+                .inputs
+                .roots
+                .iter()
+                .any(|(_, root)| root.is_dir() && dir.starts_with(root));
+            if covered || !self.config_dirs.insert(dir.clone()) {
+                continue;
+            }
+            self.watcher
+                .watch(&dir, RecursiveMode::NonRecursive)
+                .with_context(|| format!("Failed to watch {}", dir.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Block until a Java file under the input paths or a configuration
+    /// file changes.
+    fn wait(&mut self, linter: &LoadedLinter) -> Result<Changes> {
+        loop {
+            let mut changes = Changes {
+                files: BTreeSet::new(),
+                config: false,
+            };
+            let event = self.events.recv().context("Stopped watching files")?;
+            self.collect(event, linter, &mut changes);
+            while let Ok(event) = self.events.recv_timeout(DEBOUNCE) {
+                self.collect(event, linter, &mut changes);
+            }
+
+            // Saving without edits, or the writes of a previous fix, leave
+            // the contents unchanged
+            changes.files.retain(|path| self.inputs.record(path));
+            if changes.config || !changes.files.is_empty() {
+                return Ok(changes);
+            }
+        }
+    }
+
+    fn collect(&self, event: notify::Result<Event>, linter: &LoadedLinter, changes: &mut Changes) {
+        let event = match event {
+            Ok(event) => event,
+            Err(error) => {
+                eprintln!("{}", format!("Warning: {error}").yellow());
+                return;
+            }
+        };
+        if event.kind.is_access() {
+            return;
+        }
+        for path in event.paths {
+            if is_config_file(&path, Some(linter)) {
+                changes.config = true;
+            } else if path
+                .file_name()
+                .is_some_and(|name| linter.settings().accepts(&name.to_string_lossy()))
+                && let Some(path) =
+                    self.inputs
+                        .input_path(&path, &linter.source_roots, &linter.excluded_roots)
+            {
+                changes.files.insert(path);
+            }
+        }
+    }
+}
+
+/// The input paths, and the contents of the files under them as last seen.
+struct Inputs {
+    /// Each input path as given, with its canonical form.
+    roots: Vec<(PathBuf, PathBuf)>,
+    /// The content hash of each file as last seen.
+    hashes: HashMap<PathBuf, u128>,
+}
+
+impl Inputs {
+    fn new(paths: &[PathBuf]) -> Result<Self> {
+        let roots = paths
+            .iter()
+            .map(|path| {
+                let canonical = path
+                    .canonicalize()
+                    .with_context(|| format!("Failed to watch {}", path.display()))?;
+                Ok((path.clone(), canonical))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            roots,
+            hashes: HashMap::new(),
+        })
+    }
+
+    /// Map a watched path back onto the input path it was found under.
+    /// Paths in directories that the source roots of the build leave out,
+    /// and that a full run would not collect, have none.
+    fn input_path(
+        &self,
+        path: &Path,
+        source_roots: &[PathBuf],
+        excluded_roots: &[PathBuf],
+    ) -> Option<PathBuf> {
+        self.roots.iter().find_map(|(given, canonical)| {
+            if canonical.is_dir() {
+                let relative = path.strip_prefix(canonical).ok()?;
+                let path = given.join(relative);
+                source_dirs(given, source_roots, excluded_roots)
+                    .contains(&path)
+                    .then_some(path)
+            } else {
+                (path == canonical).then(|| given.clone())
+            }
+        })
+    }
+
+    /// Remember the current contents of `path`, returning true if they
+    /// differ from when it was last recorded.
+    fn record(&mut self, path: &Path) -> bool {
+        match std::fs::read(path) {
+            Ok(contents) => {
+                let hash = cache::hash128(&contents);
+                self.hashes.insert(path.to_path_buf(), hash) != Some(hash)
+            }
+            Err(_) => self.hashes.remove(path).is_some(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(config: bool) -> Changes {
+        Changes {
+            files: BTreeSet::from([PathBuf::from("src/A.java")]),
+            config,
+        }
+    }
+
+    #[test]
+    fn test_input_path_maps_back_to_given_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/pkg")).unwrap();
+        std::fs::create_dir(dir.path().join("other")).unwrap();
+        std::fs::write(dir.path().join("B.java"), "class B {}\n").unwrap();
+
+        // Given in a form that differs from the canonical one
+        let src = dir.path().join("other/../src");
+        let file = dir.path().join("other/../B.java");
+        let inputs = Inputs::new(&[src.clone(), file.clone()]).unwrap();
+        let canonical = dir.path().canonicalize().unwrap();
+
+        assert_eq!(
+            inputs.input_path(&canonical.join("src/pkg/A.java"), &[], &[]),
+            Some(src.join("pkg/A.java"))
+        );
+        assert_eq!(
+            inputs.input_path(&canonical.join("B.java"), &[], &[]),
+            Some(file)
+        );
+        assert_eq!(inputs.input_path(&canonical.join("C.java"), &[], &[]), None);
+        assert_eq!(
+            inputs.input_path(&canonical.join("other/D.java"), &[], &[]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_input_path_honors_source_roots() {
+        let dir = tempfile::tempdir().unwrap();
+        for source_dir in ["src/main/java", "src/test/java", "scripts"] {
+            std::fs::create_dir_all(dir.path().join(source_dir)).unwrap();
+        }
+        let inputs = Inputs::new(&[dir.path().to_path_buf()]).unwrap();
+        let canonical = dir.path().canonicalize().unwrap();
+        let input_path = |path: &str, source_roots: &[PathBuf], excluded_roots: &[PathBuf]| {
+            inputs.input_path(&canonical.join(path), source_roots, excluded_roots)
+        };
+
+        // Configured roots narrow the directory, as in a full run
+        let main = [dir.path().join("src/main/java")];
+        assert_eq!(
+            input_path("src/main/java/A.java", &main, &[]),
+            Some(dir.path().join("src/main/java/A.java"))
+        );
+        assert_eq!(input_path("scripts/B.java", &main, &[]), None);
+
+        // Excluded roots are skipped
+        let tests = [dir.path().join("src/test/java")];
+        assert_eq!(input_path("src/test/java/ATest.java", &[], &tests), None);
+        assert!(input_path("scripts/B.java", &[], &tests).is_some());
+    }
+
+    #[test]
+    fn test_record_skips_unchanged_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("A.java");
+        std::fs::write(&path, "class A {}\n").unwrap();
+        let mut inputs = Inputs::new(&[dir.path().to_path_buf()]).unwrap();
+
+        assert!(inputs.record(&path));
+        // A save without edits, or lintal's own write recorded after a fix
+        assert!(!inputs.record(&path));
+        std::fs::write(&path, "class A {}\n").unwrap();
+        assert!(!inputs.record(&path));
+
+        std::fs::write(&path, "class A { }\n").unwrap();
+        assert!(inputs.record(&path));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(inputs.record(&path));
+        assert!(!inputs.record(&path));
+    }
+
+    #[test]
+    fn test_rerun_reloads_only_on_config_changes() {
+        let rerun = changes(false).rerun(|| -> Result<()> { panic!("reloaded") });
+        assert!(matches!(rerun, Rerun::Files(files) if files.len() == 1));
+
+        let rerun = changes(true).rerun(|| Ok(42));
+        assert!(matches!(rerun, Rerun::Reloaded(42)));
+
+        // A broken configuration keeps the current one
+        let rerun = changes(true).rerun(|| -> Result<()> { anyhow::bail!("invalid") });
+        assert!(matches!(rerun, Rerun::Files(files) if files.len() == 1));
+    }
+}
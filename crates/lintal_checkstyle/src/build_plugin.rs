@@ -0,0 +1,914 @@
+//! Checkstyle settings from Maven and Gradle build files.
+//!
+//! Projects that run checkstyle through `maven-checkstyle-plugin` or
+//! Gradle's `checkstyle` plugin declare where checkstyle.xml lives in the
+//! build file rather than keeping it in a standard location. The build files
+//! are read as text; no build tool is run, so only literal values (and the
+//! project directory placeholders) are understood.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use quick_xml::de::from_str;
+use serde::Deserialize;
+
+use crate::CheckstyleError;
+
+/// Names of the build files that may configure checkstyle, in the order they
+/// are looked for in a directory.
+pub const BUILD_FILE_NAMES: &[&str] = &["pom.xml", "build.gradle.kts", "build.gradle"];
+
+/// The property `maven-checkstyle-plugin` sets to the suppressions file
+/// unless `suppressionsFileExpression` names another.
+const MAVEN_SUPPRESSIONS_PROPERTY: &str = "checkstyle.suppressions.file";
+
+/// Checkstyle settings of a project's build.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildPluginConfig {
+    /// The pom.xml or build.gradle(.kts) the settings were read from.
+    pub build_file: PathBuf,
+    /// The configured checkstyle.xml, unless it is a URL or a classpath
+    /// resource.
+    pub config_file: Option<PathBuf>,
+    /// The suppressions file, unless it is a URL or a classpath resource.
+    pub suppressions_file: Option<PathBuf>,
+    /// Gradle's `configDirectory`, which is the value of `${config_loc}`.
+    pub config_directory: Option<PathBuf>,
+    /// Values for `${...}` placeholders in checkstyle.xml.
+    pub properties: HashMap<String, String>,
+    /// The source directories checkstyle is configured to check. Empty if
+    /// the build leaves them to the plugin's defaults, which cover only the
+    /// project itself and not its modules or subprojects.
+    pub source_roots: Vec<PathBuf>,
+    /// Source directories of the project that checkstyle skips: Maven's
+    /// test sources, unless `includeTestSourceDirectory` is set.
+    pub excluded_roots: Vec<PathBuf>,
+}
+
+impl BuildPluginConfig {
+    /// Read the checkstyle settings of the build file in `dir`. Returns
+    /// `None` if there is no build file or it does not use checkstyle.
+    pub fn from_dir(dir: &Path) -> Result<Option<Self>, CheckstyleError> {
+        for name in BUILD_FILE_NAMES {
+            let path = dir.join(name);
+            if !path.is_file() {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path)?;
+            return if *name == "pom.xml" {
+                Self::from_pom(&path, &content)
+            } else {
+                Ok(Self::from_gradle(&path, &content, &gradle_root_dir(dir)))
+            };
+        }
+        Ok(None)
+    }
+
+    /// Read the `maven-checkstyle-plugin` settings of the pom.xml at `path`.
+    pub fn from_pom(path: &Path, xml: &str) -> Result<Option<Self>, CheckstyleError> {
+        let pom: Pom = from_str(xml)?;
+        let project_dir = parent_dir(path);
+        let build = pom.build.unwrap_or_default();
+
+        // Settings in <build><plugins> win over <pluginManagement>, which
+        // only provides defaults
+        let plugins: Vec<&Plugin> = build
+            .plugins
+            .iter()
+            .chain(build.plugin_management.iter().map(|m| &m.plugins))
+            .chain(pom.reporting.iter().map(|r| &r.plugins))
+            .flat_map(|plugins| &plugins.plugins)
+            .filter(|plugin| plugin.artifact_id.trim() == "maven-checkstyle-plugin")
+            .collect();
+        if plugins.is_empty() {
+            return Ok(None);
+        }
+        let configurations: Vec<&PluginConfiguration> = plugins
+            .iter()
+            .flat_map(|plugin| {
+                plugin.configuration.iter().chain(
+                    plugin
+                        .executions
+                        .iter()
+                        .flat_map(|e| &e.executions)
+                        .filter_map(|e| e.configuration.as_ref()),
+                )
+            })
+            .collect();
+        let setting = |get: fn(&PluginConfiguration) -> Option<&String>| {
+            configurations
+                .iter()
+                .find_map(|c| get(c))
+                .map(|value| expand_maven(value.trim(), &project_dir, &pom.properties))
+        };
+
+        let mut properties: HashMap<String, String> = setting(|c| c.property_expansion.as_ref())
+            .map(|text| parse_properties(&text))
+            .unwrap_or_default();
+        let suppressions_file = setting(|c| c.suppressions_location.as_ref())
+            .and_then(|location| local_path(&project_dir, &location));
+        if let Some(file) = &suppressions_file {
+            let name = setting(|c| c.suppressions_file_expression.as_ref())
+                .unwrap_or_else(|| MAVEN_SUPPRESSIONS_PROPERTY.to_string());
+            properties.insert(name, file.to_string_lossy().into_owned());
+        }
+
+        let directories = |get: fn(&PluginConfiguration) -> Option<&Directories>| {
+            configurations.iter().find_map(|c| get(c)).map(|dirs| {
+                dirs.directories
+                    .iter()
+                    .map(|dir| expand_maven(dir.trim(), &project_dir, &pom.properties))
+                    .collect::<Vec<_>>()
+            })
+        };
+        let mut source_roots = directories(|c| c.source_directories.as_ref())
+            .or_else(|| setting(|c| c.source_directory.as_ref()).map(|dir| vec![dir]))
+            .or_else(|| build.source_directory.clone().map(|dir| vec![dir]))
+            .unwrap_or_default();
+        let include_tests = setting(|c| c.include_test_source_directory.as_ref())
+            .is_some_and(|value| value == "true");
+        let test_roots = directories(|c| c.test_source_directories.as_ref())
+            .or_else(|| build.test_source_directory.clone().map(|dir| vec![dir]))
+            .unwrap_or_else(|| vec!["src/test/java".to_string()]);
+        // Without configured directories every file under the given paths is
+        // checked, so that modules are not left out, except for test sources
+        let mut excluded_roots = Vec::new();
+        if !include_tests {
+            excluded_roots = test_roots;
+        } else if !source_roots.is_empty() {
+            source_roots.extend(test_roots);
+        }
+
+        Ok(Some(Self {
+            build_file: path.to_path_buf(),
+            // Without configLocation the plugin uses sun_checks.xml from the
+            // checkstyle jar
+            config_file: setting(|c| c.config_location.as_ref())
+                .and_then(|location| local_path(&project_dir, &location)),
+            suppressions_file,
+            config_directory: None,
+            properties,
+            source_roots: source_roots
+                .iter()
+                .map(|dir| resolve(&project_dir, dir))
+                .collect(),
+            excluded_roots: excluded_roots
+                .iter()
+                .map(|dir| resolve(&project_dir, dir))
+                .collect(),
+        }))
+    }
+
+    /// Read the `checkstyle` plugin settings of the Gradle build script at
+    /// `path`, a Groovy or Kotlin script of a project under `root_dir`.
+    pub fn from_gradle(path: &Path, script: &str, root_dir: &Path) -> Option<Self> {
+        let tokens = tokenize(script);
+        let project_dir = parent_dir(path);
+        let dirs = GradleDirs {
+            project: &project_dir,
+            root: root_dir,
+        };
+
+        let applied = tokens.iter().enumerate().any(|(i, token)| match token {
+            Token::Ident(name) => name == "checkstyle",
+            // id 'checkstyle', id("checkstyle"), apply plugin: 'checkstyle'
+            Token::Str(value) => {
+                let is_id = |token: &Token| matches!(token, Token::Ident(id) if id == "id");
+                value == "checkstyle"
+                    && match &tokens[..i] {
+                        [.., previous] if is_id(previous) => true,
+                        [.., call, Token::Punct('(')] => is_id(call),
+                        [.., Token::Ident(plugin), Token::Punct(':')] => plugin == "plugin",
+                        _ => false,
+                    }
+            }
+            Token::Punct(_) => false,
+        });
+        if !applied {
+            return None;
+        }
+
+        let mut config_file = None;
+        let mut config_directory = None;
+        let mut properties = HashMap::new();
+        let mut source_sets = None;
+        for block in checkstyle_blocks(&tokens) {
+            for (i, token) in block.iter().enumerate() {
+                let Token::Ident(name) = token else {
+                    continue;
+                };
+                let rest = &block[i + 1..];
+                match name.strip_suffix(".set").unwrap_or(name) {
+                    "configFile" | "config" => {
+                        config_file = gradle_path(rest, &dirs).or(config_file);
+                    }
+                    "configDirectory" | "configDir" => {
+                        config_directory = gradle_path(rest, &dirs).or(config_directory);
+                    }
+                    "configProperties" | "configProperties.put" | "configProperties.putAll" => {
+                        properties.extend(gradle_properties(rest, &dirs));
+                    }
+                    "sourceSets" => source_sets = gradle_source_sets(rest).or(source_sets),
+                    _ => {}
+                }
+            }
+        }
+
+        // The plugin's defaults
+        let config_directory =
+            config_directory.unwrap_or_else(|| root_dir.join("config").join("checkstyle"));
+        let config_file = config_file.unwrap_or_else(|| config_directory.join("checkstyle.xml"));
+        Some(Self {
+            build_file: path.to_path_buf(),
+            config_file: Some(config_file),
+            suppressions_file: None,
+            config_directory: Some(config_directory),
+            properties,
+            source_roots: source_sets
+                .iter()
+                .flatten()
+                .map(|name| project_dir.join("src").join(name).join("java"))
+                .collect(),
+            excluded_roots: Vec::new(),
+        })
+    }
+}
+
+/// The subset of a pom.xml that configures checkstyle.
+#[derive(Debug, Default, Deserialize)]
+struct Pom {
+    #[serde(default)]
+    properties: HashMap<String, String>,
+    build: Option<Build>,
+    reporting: Option<Reporting>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Build {
+    source_directory: Option<String>,
+    test_source_directory: Option<String>,
+    plugins: Option<Plugins>,
+    plugin_management: Option<PluginManagement>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginManagement {
+    #[serde(default)]
+    plugins: Plugins,
+}
+
+#[derive(Debug, Deserialize)]
+struct Reporting {
+    #[serde(default)]
+    plugins: Plugins,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Plugins {
+    #[serde(default, rename = "plugin")]
+    plugins: Vec<Plugin>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Plugin {
+    #[serde(default)]
+    artifact_id: String,
+    configuration: Option<PluginConfiguration>,
+    executions: Option<Executions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Executions {
+    #[serde(default, rename = "execution")]
+    executions: Vec<Execution>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Execution {
+    configuration: Option<PluginConfiguration>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PluginConfiguration {
+    config_location: Option<String>,
+    suppressions_location: Option<String>,
+    suppressions_file_expression: Option<String>,
+    property_expansion: Option<String>,
+    include_test_source_directory: Option<String>,
+    source_directory: Option<String>,
+    source_directories: Option<Directories>,
+    test_source_directories: Option<Directories>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Directories {
+    #[serde(default, rename = "$value")]
+    directories: Vec<String>,
+}
+
+/// The directory containing `path`, `.` for a bare file name.
+fn parent_dir(path: &Path) -> PathBuf {
+    path.parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .map_or_else(|| PathBuf::from("."), Path::to_path_buf)
+}
+
+/// The root of the Gradle build containing `dir`: the nearest directory with
+/// a settings script, or `dir` itself.
+fn gradle_root_dir(dir: &Path) -> PathBuf {
+    dir.ancestors()
+        .find(|dir| {
+            ["settings.gradle", "settings.gradle.kts"]
+                .iter()
+                .any(|name| dir.join(name).is_file())
+        })
+        .map_or_else(
+            || dir.to_path_buf(),
+            // The current directory is the empty ancestor of a relative path
+            |root| {
+                if root.as_os_str().is_empty() {
+                    PathBuf::from(".")
+                } else {
+                    root.to_path_buf()
+                }
+            },
+        )
+}
+
+/// Resolve a plugin location against the project directory. URLs and
+/// classpath resources have no local path.
+fn local_path(project_dir: &Path, location: &str) -> Option<PathBuf> {
+    if location.is_empty() || location.contains("://") || location.starts_with("classpath:") {
+        return None;
+    }
+    let location = location.strip_prefix("file:").unwrap_or(location);
+    Some(resolve(project_dir, location))
+}
+
+/// Resolve `path` against the project directory, unless it already starts
+/// with it because it was written with `${project.basedir}`.
+fn resolve(project_dir: &Path, path: &str) -> PathBuf {
+    if Path::new(path).starts_with(project_dir) {
+        PathBuf::from(path)
+    } else {
+        project_dir.join(path)
+    }
+}
+
+/// Expand the project directory and pom.xml properties in a Maven value.
+/// Other placeholders are left for checkstyle's own property expansion.
+fn expand_maven(value: &str, project_dir: &Path, properties: &HashMap<String, String>) -> String {
+    expand_maven_nested(value, project_dir, properties, 0)
+}
+
+/// Properties referring to other properties are expanded up to this depth,
+/// which also stops cycles.
+const MAX_PROPERTY_DEPTH: usize = 8;
+
+fn expand_maven_nested(
+    value: &str,
+    project_dir: &Path,
+    properties: &HashMap<String, String>,
+    depth: usize,
+) -> String {
+    expand(value, false, |name| match name {
+        "basedir" | "project.basedir" => Some(project_dir.to_string_lossy().into_owned()),
+        _ if depth < MAX_PROPERTY_DEPTH => properties
+            .get(name)
+            .map(|value| expand_maven_nested(value.trim(), project_dir, properties, depth + 1)),
+        _ => None,
+    })
+}
+
+/// Replace the `${name}` placeholders in `value` that `lookup` knows, and
+/// `$name` ones too if `bare` is set.
+fn expand(value: &str, bare: bool, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, len) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 0),
+            }
+        } else if bare {
+            let end = after
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(after.len());
+            (&after[..end], end)
+        } else {
+            ("", 0)
+        };
+        match lookup(name).filter(|_| len > 0) {
+            Some(value) => {
+                expanded.push_str(&value);
+                rest = &after[len..];
+            }
+            None => {
+                expanded.push('$');
+                rest = after;
+            }
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Parse the `key=value` lines of a Java properties text.
+fn parse_properties(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with(['#', '!']))
+        .filter_map(|line| {
+            let (key, value) = line.split_once(['=', ':'])?;
+            Some((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// A token of a Groovy or Kotlin build script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// An identifier, including dotted member accesses like `rootProject.file`.
+    Ident(String),
+    /// The contents of a string literal.
+    Str(String),
+    Punct(char),
+}
+
+/// Split a build script into tokens, dropping comments and whitespace.
+fn tokenize(script: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = script.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        match c {
+            _ if c.is_whitespace() => {}
+            '/' if chars.peek().is_some_and(|&(_, next)| next == '/') => {
+                while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+            }
+            '/' if chars.peek().is_some_and(|&(_, next)| next == '*') => {
+                chars.next();
+                let mut previous = ' ';
+                for (_, c) in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            '"' | '\'' | '`' => {
+                let mut value = String::new();
+                while let Some((_, next)) = chars.next() {
+                    match next {
+                        '\\' => value.extend(chars.next().map(|(_, c)| c)),
+                        _ if next == c => break,
+                        _ => value.push(next),
+                    }
+                }
+                // Kotlin quotes keywords like `checkstyle` in backticks
+                tokens.push(if c == '`' {
+                    Token::Ident(value)
+                } else {
+                    Token::Str(value)
+                });
+            }
+            _ if c.is_alphanumeric() || c == '_' || c == '$' || c == '.' => {
+                let mut ident = c.to_string();
+                while let Some((_, c)) =
+                    chars.next_if(|&(_, c)| c.is_alphanumeric() || c == '_' || c == '$' || c == '.')
+                {
+                    ident.push(c);
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            _ => tokens.push(Token::Punct(c)),
+        }
+    }
+    tokens
+}
+
+/// The bodies of the blocks that configure checkstyle: the `checkstyle`
+/// extension and blocks configuring `Checkstyle` tasks.
+fn checkstyle_blocks(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut blocks = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let Token::Ident(name) = token else {
+            continue;
+        };
+        let open = match name.as_str() {
+            "checkstyle" => (tokens.get(i + 1) == Some(&Token::Punct('{'))).then_some(i + 1),
+            // tasks.withType(Checkstyle) { ... }, tasks.withType<Checkstyle> { ... }
+            "Checkstyle" => tokens[i + 1..]
+                .iter()
+                .take(6)
+                .position(|t| *t == Token::Punct('{'))
+                .map(|offset| i + 1 + offset),
+            _ => None,
+        };
+        if let Some(open) = open
+            && let Some(len) = group_len(&tokens[open..])
+        {
+            blocks.push(&tokens[open + 1..open + len - 1]);
+        }
+    }
+    blocks
+}
+
+/// The number of tokens up to and including the bracket closing the one
+/// `tokens` starts with.
+fn group_len(tokens: &[Token]) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Punct('{' | '[' | '(') => depth += 1,
+            Token::Punct('}' | ']' | ')') => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The directories Gradle paths are resolved against.
+struct GradleDirs<'a> {
+    project: &'a Path,
+    root: &'a Path,
+}
+
+impl GradleDirs<'_> {
+    /// Expand the project directory variables in a Groovy or Kotlin string.
+    fn expand(&self, value: &str) -> String {
+        let project = self.project.to_string_lossy();
+        let root = self.root.to_string_lossy();
+        // Both languages interpolate `$name` and `${name}`
+        expand(value, true, |name| match name {
+            "rootDir" | "project.rootDir" | "rootProject.projectDir" => Some(root.to_string()),
+            "projectDir" | "project.projectDir" => Some(project.to_string()),
+            _ => None,
+        })
+    }
+}
+
+/// The file or directory a Gradle assignment like `configFile = file("x")`
+/// refers to, given the tokens after the property name.
+fn gradle_path(tokens: &[Token], dirs: &GradleDirs) -> Option<PathBuf> {
+    let mut base = dirs.project;
+    for token in tokens.iter().take(8) {
+        match token {
+            Token::Punct('=' | '(') => {}
+            Token::Ident(name) if name.starts_with("rootProject.") => base = dirs.root,
+            Token::Ident(_) => {}
+            Token::Str(value) => return Some(base.join(dirs.expand(value))),
+            Token::Punct(_) => return None,
+        }
+    }
+    None
+}
+
+/// The literal entries of a `configProperties` map, given the tokens after
+/// the property name.
+fn gradle_properties(tokens: &[Token], dirs: &GradleDirs) -> Vec<(String, String)> {
+    // configProperties["key"] = "value", and configProperties.put("key", "value")
+    match tokens {
+        [
+            Token::Punct('['),
+            Token::Str(key),
+            Token::Punct(']'),
+            Token::Punct('='),
+            rest @ ..,
+        ]
+        | [
+            Token::Punct('('),
+            Token::Str(key),
+            Token::Punct(','),
+            rest @ ..,
+        ] => {
+            return gradle_value(rest, dirs)
+                .map(|value| vec![(key.clone(), value)])
+                .unwrap_or_default();
+        }
+        _ => {}
+    }
+
+    // configProperties = [key: "value"], configProperties = mapOf("key" to "value")
+    let Some(open) = tokens
+        .iter()
+        .take(4)
+        .position(|t| matches!(t, Token::Punct('[' | '(')))
+    else {
+        return Vec::new();
+    };
+    let Some(len) = group_len(&tokens[open..]) else {
+        return Vec::new();
+    };
+    let entries = &tokens[open + 1..open + len - 1];
+    let mut properties = Vec::new();
+    for (i, token) in entries.iter().enumerate() {
+        let (Token::Str(key) | Token::Ident(key)) = token else {
+            continue;
+        };
+        let separator = entries.get(i + 1);
+        if (separator == Some(&Token::Punct(':'))
+            || separator == Some(&Token::Ident("to".to_string())))
+            && let Some(value) = gradle_value(&entries[i + 2..], dirs)
+        {
+            properties.push((key.clone(), value));
+        }
+    }
+    properties
+}
+
+/// The value of a `configProperties` entry: a string, or a file whose path
+/// is used.
+fn gradle_value(tokens: &[Token], dirs: &GradleDirs) -> Option<String> {
+    match tokens.first()? {
+        Token::Str(value) => Some(dirs.expand(value)),
+        Token::Ident(name) if name.ends_with("file") => {
+            gradle_path(tokens, dirs).map(|path| path.to_string_lossy().into_owned())
+        }
+        _ => None,
+    }
+}
+
+/// The names of the source sets assigned to the `sourceSets` property,
+/// given the tokens after the property name.
+fn gradle_source_sets(tokens: &[Token]) -> Option<Vec<String>> {
+    let open = tokens
+        .iter()
+        .take(3)
+        .position(|t| matches!(t, Token::Punct('[' | '(')))?;
+    let len = group_len(&tokens[open..])?;
+    let mut names = Vec::new();
+    let group = &tokens[open + 1..open + len - 1];
+    for (i, token) in group.iter().enumerate() {
+        match token {
+            // sourceSets.main, project.sourceSets.main.get()
+            Token::Ident(path) => {
+                if let Some((_, rest)) = path.split_once("sourceSets.")
+                    && let Some(name) = rest.split('.').next()
+                    && !matches!(name, "getByName" | "named" | "")
+                {
+                    names.push(name.to_string());
+                }
+            }
+            // sourceSets.getByName("main"), sourceSets.named("main")
+            Token::Str(name) if i >= 2 => {
+                if matches!(&group[i - 2], Token::Ident(call) if call.ends_with("getByName") || call.ends_with("named"))
+                {
+                    names.push(name.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+    (!names.is_empty()).then_some(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pom(plugin: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+    <properties>
+        <checkstyle.dir>${{project.basedir}}/build-tools</checkstyle.dir>
+    </properties>
+    <build>
+        <plugins>
+            <plugin>
+                <groupId>org.apache.maven.plugins</groupId>
+                <artifactId>maven-compiler-plugin</artifactId>
+            </plugin>
+            {plugin}
+        </plugins>
+    </build>
+</project>"#
+        )
+    }
+
+    #[test]
+    fn test_pom_plugin_configuration() {
+        let xml = pom(r#"<plugin>
+                <artifactId>maven-checkstyle-plugin</artifactId>
+                <configuration>
+                    <configLocation>${checkstyle.dir}/checkstyle.xml</configLocation>
+                    <suppressionsLocation>config/suppressions.xml</suppressionsLocation>
+                    <propertyExpansion>
+                        # Shared settings
+                        header.year=2024
+                        cache.file = ${project.build.directory}/checkstyle-cache
+                    </propertyExpansion>
+                    <includeTestSourceDirectory>true</includeTestSourceDirectory>
+                </configuration>
+            </plugin>"#);
+        let config = BuildPluginConfig::from_pom(Path::new("app/pom.xml"), &xml)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            config.config_file,
+            Some(PathBuf::from("app/build-tools/checkstyle.xml"))
+        );
+        assert_eq!(
+            config.suppressions_file,
+            Some(PathBuf::from("app/config/suppressions.xml"))
+        );
+        assert_eq!(config.properties["header.year"], "2024");
+        assert_eq!(
+            config.properties["cache.file"],
+            "${project.build.directory}/checkstyle-cache"
+        );
+        assert_eq!(
+            config.properties[MAVEN_SUPPRESSIONS_PROPERTY],
+            "app/config/suppressions.xml"
+        );
+        // Default source directories are left to the files lintal is given
+        assert!(config.source_roots.is_empty());
+        assert!(config.excluded_roots.is_empty());
+    }
+
+    #[test]
+    fn test_pom_defaults() {
+        // Execution configuration and plugin management are read too
+        let xml = pom(r#"<plugin>
+                <artifactId>maven-checkstyle-plugin</artifactId>
+                <executions>
+                    <execution>
+                        <configuration>
+                            <sourceDirectories>
+                                <sourceDirectory>src/main/java</sourceDirectory>
+                                <sourceDirectory>src/generated/java</sourceDirectory>
+                            </sourceDirectories>
+                        </configuration>
+                    </execution>
+                </executions>
+            </plugin>"#);
+        let config = BuildPluginConfig::from_pom(Path::new("pom.xml"), &xml)
+            .unwrap()
+            .unwrap();
+        // sun_checks.xml is a classpath resource
+        assert_eq!(config.config_file, None);
+        assert_eq!(config.suppressions_file, None);
+        assert_eq!(
+            config.source_roots,
+            [
+                PathBuf::from("./src/main/java"),
+                PathBuf::from("./src/generated/java")
+            ]
+        );
+
+        let xml = pom(r#"<plugin>
+                <artifactId>maven-checkstyle-plugin</artifactId>
+                <configuration>
+                    <sourceDirectory>src/java</sourceDirectory>
+                    <includeTestSourceDirectory>true</includeTestSourceDirectory>
+                </configuration>
+            </plugin>"#);
+        let config = BuildPluginConfig::from_pom(Path::new("pom.xml"), &xml)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            config.source_roots,
+            [
+                PathBuf::from("./src/java"),
+                PathBuf::from("./src/test/java")
+            ]
+        );
+
+        let xml = pom(r#"<plugin>
+                <artifactId>maven-checkstyle-plugin</artifactId>
+                <configuration>
+                    <configLocation>https://example.com/checkstyle.xml</configLocation>
+                </configuration>
+            </plugin>"#);
+        let config = BuildPluginConfig::from_pom(Path::new("pom.xml"), &xml)
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.config_file, None);
+        // Test sources are only checked with includeTestSourceDirectory
+        assert!(config.source_roots.is_empty());
+        assert_eq!(config.excluded_roots, [PathBuf::from("./src/test/java")]);
+
+        assert_eq!(
+            BuildPluginConfig::from_pom(Path::new("pom.xml"), &pom("")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pom_skips_test_sources_by_default() {
+        let xml = r#"<project>
+    <build>
+        <testSourceDirectory>src/it/java</testSourceDirectory>
+        <plugins>
+            <plugin>
+                <artifactId>maven-checkstyle-plugin</artifactId>
+            </plugin>
+        </plugins>
+    </build>
+</project>"#;
+        let config = BuildPluginConfig::from_pom(Path::new("app/pom.xml"), xml)
+            .unwrap()
+            .unwrap();
+        assert!(config.source_roots.is_empty());
+        assert_eq!(config.excluded_roots, [PathBuf::from("app/src/it/java")]);
+    }
+
+    #[test]
+    fn test_gradle_groovy() {
+        let script = r#"
+plugins {
+    id 'java'
+    id 'checkstyle'
+}
+
+checkstyle {
+    toolVersion = '10.12.0'
+    // configFile = file('old.xml')
+    configFile = rootProject.file("config/style/checkstyle.xml")
+    configProperties = [
+        'suppressionFile': "${rootDir}/config/style/suppressions.xml",
+        headerFile: file('HEADER'),
+    ]
+    sourceSets = [sourceSets.main]
+}
+"#;
+        let config = BuildPluginConfig::from_gradle(
+            Path::new("root/app/build.gradle"),
+            script,
+            Path::new("root"),
+        )
+        .unwrap();
+        assert_eq!(
+            config.config_file,
+            Some(PathBuf::from("root/config/style/checkstyle.xml"))
+        );
+        assert_eq!(
+            config.config_directory,
+            Some(PathBuf::from("root/config/checkstyle"))
+        );
+        assert_eq!(
+            config.properties["suppressionFile"],
+            "root/config/style/suppressions.xml"
+        );
+        assert_eq!(config.properties["headerFile"], "root/app/HEADER");
+        assert_eq!(
+            config.source_roots,
+            [PathBuf::from("root/app/src/main/java")]
+        );
+    }
+
+    #[test]
+    fn test_gradle_kotlin() {
+        let script = r#"
+plugins {
+    java
+    checkstyle
+}
+
+configure<CheckstyleExtension> {
+    configDirectory.set(file("checks"))
+}
+
+tasks.withType<Checkstyle> {
+    configProperties = mapOf("org.checkstyle.google.suppressionfilter.config" to "$projectDir/checks/suppressions.xml")
+    configProperties["tabWidth"] = "4"
+}
+"#;
+        let config =
+            BuildPluginConfig::from_gradle(Path::new("build.gradle.kts"), script, Path::new("."))
+                .unwrap();
+        // `configure<CheckstyleExtension>` is not read, so the defaults apply
+        assert_eq!(
+            config.config_file,
+            Some(PathBuf::from("./config/checkstyle/checkstyle.xml"))
+        );
+        assert_eq!(
+            config.properties["org.checkstyle.google.suppressionfilter.config"],
+            "./checks/suppressions.xml"
+        );
+        assert_eq!(config.properties["tabWidth"], "4");
+        assert!(config.source_roots.is_empty());
+
+        // Builds without the checkstyle plugin have no settings
+        assert_eq!(
+            BuildPluginConfig::from_gradle(
+                Path::new("build.gradle"),
+                "plugins { id 'java' }",
+                Path::new(".")
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! Settings of the root `Checker` module that apply to every file.
+
+use std::path::{Path, PathBuf};
+
+use crate::CheckstyleConfig;
+
+/// Checkstyle's default `tabWidth`.
+pub const DEFAULT_TAB_WIDTH: usize = 8;
+
+/// Extensions `TreeWalker` checks files with, unless configured.
+const DEFAULT_TREE_WALKER_EXTENSIONS: &str = "java";
+
+/// Settings from the `Checker` module (and `TreeWalker`, for file
+/// extensions).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckerSettings {
+    /// The `tabWidth` configured on `Checker`, if any. Rules that measure
+    /// indentation inherit it unless they set their own.
+    pub tab_width: Option<usize>,
+    /// Extensions of the files that checks run on, each with a leading dot.
+    /// Empty if files of every extension are checked.
+    pub file_extensions: Vec<String>,
+    /// The name of the encoding of source files.
+    pub charset: String,
+    /// Directory that file paths are made relative to, in output and when
+    /// matching suppressions.
+    pub basedir: Option<PathBuf>,
+}
+
+impl Default for CheckerSettings {
+    fn default() -> Self {
+        Self {
+            tab_width: None,
+            file_extensions: parse_extensions(DEFAULT_TREE_WALKER_EXTENSIONS),
+            charset: "UTF-8".to_string(),
+            basedir: None,
+        }
+    }
+}
+
+impl CheckerSettings {
+    /// Read the settings from checkstyle.xml, using checkstyle's defaults
+    /// for properties that are absent or invalid.
+    pub fn new(config: &CheckstyleConfig) -> Self {
+        let defaults = Self::default();
+        let property = |name: &str| {
+            config
+                .properties
+                .iter()
+                .find(|p| p.name == name)
+                .map(|p| p.value.trim())
+                .filter(|value| !value.is_empty())
+        };
+
+        // `Checker` filters files before `TreeWalker` sees them, so checks
+        // run on files whose extension both accept
+        let tree_walker = config
+            .tree_walker()
+            .and_then(|tw| tw.property("fileExtensions"))
+            .map_or(defaults.file_extensions, parse_extensions);
+        let file_extensions = match property("fileExtensions").map(parse_extensions) {
+            Some(checker) if tree_walker.is_empty() => checker,
+            Some(checker) => tree_walker
+                .into_iter()
+                .filter(|ext| checker.contains(ext))
+                .collect(),
+            None => tree_walker,
+        };
+
+        Self {
+            tab_width: property("tabWidth")
+                .and_then(|value| value.parse().ok())
+                .filter(|&width| width > 0),
+            file_extensions,
+            charset: property("charset").map_or(defaults.charset, str::to_string),
+            basedir: property("basedir").map(PathBuf::from),
+        }
+    }
+
+    /// The width of a tab when computing columns.
+    pub fn tab_width(&self) -> usize {
+        self.tab_width.unwrap_or(DEFAULT_TAB_WIDTH)
+    }
+
+    /// Returns true if checks run on a file with this name.
+    pub fn accepts(&self, file_name: &str) -> bool {
+        self.file_extensions.is_empty()
+            || self
+                .file_extensions
+                .iter()
+                .any(|ext| file_name.ends_with(ext.as_str()))
+    }
+
+    /// `path` relative to `basedir`, as checkstyle reports it and matches it
+    /// against suppressions. Paths outside of `basedir`, or without one, are
+    /// left as they are.
+    pub fn relative_path(&self, path: &Path) -> PathBuf {
+        self.basedir
+            .as_deref()
+            .and_then(|basedir| {
+                let basedir = std::path::absolute(basedir).ok()?;
+                let absolute = std::path::absolute(path).ok()?;
+                Some(absolute.strip_prefix(basedir).ok()?.to_path_buf())
+            })
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+}
+
+/// Parse a comma-separated list of extensions, with or without their dot.
+fn parse_extensions(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|ext| !ext.is_empty())
+        .map(|ext| {
+            if ext.starts_with('.') {
+                ext.to_string()
+            } else {
+                format!(".{ext}")
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(checker: &str, tree_walker: &str) -> CheckerSettings {
+        let xml = format!(
+            r#"<module name="Checker">{checker}<module name="TreeWalker">{tree_walker}</module></module>"#
+        );
+        CheckerSettings::new(&CheckstyleConfig::parse(&xml).unwrap())
+    }
+
+    #[test]
+    fn test_defaults() {
+        let settings = parse("", "");
+        assert_eq!(settings, CheckerSettings::default());
+        assert_eq!(settings.tab_width(), DEFAULT_TAB_WIDTH);
+        assert!(settings.accepts("Foo.java"));
+        assert!(!settings.accepts("pom.xml"));
+    }
+
+    #[test]
+    fn test_checker_properties() {
+        let settings = parse(
+            r#"<property name="tabWidth" value="4"/>
+               <property name="charset" value="ISO-8859-1"/>
+               <property name="basedir" value="/project"/>"#,
+            "",
+        );
+        assert_eq!(settings.tab_width(), 4);
+        assert_eq!(settings.charset, "ISO-8859-1");
+        assert_eq!(settings.basedir, Some(PathBuf::from("/project")));
+        assert_eq!(
+            settings.relative_path(Path::new("/project/src/A.java")),
+            Path::new("src/A.java")
+        );
+        assert_eq!(
+            settings.relative_path(Path::new("/elsewhere/A.java")),
+            Path::new("/elsewhere/A.java")
+        );
+    }
+
+    #[test]
+    fn test_tab_width_below_one_is_invalid() {
+        let settings = parse(r#"<property name="tabWidth" value="0"/>"#, "");
+        assert_eq!(settings.tab_width, None);
+        assert_eq!(settings.tab_width(), DEFAULT_TAB_WIDTH);
+    }
+
+    #[test]
+    fn test_file_extensions_must_be_accepted_by_both_modules() {
+        // Checker also lists extensions for non-Java checks
+        let settings = parse(
+            r#"<property name="fileExtensions" value="java, properties, xml"/>"#,
+            "",
+        );
+        assert_eq!(settings.file_extensions, [".java"]);
+
+        let settings = parse(
+            r#"<property name="fileExtensions" value="java,jav"/>"#,
+            r#"<property name="fileExtensions" value=".java, .jav"/>"#,
+        );
+        assert_eq!(settings.file_extensions, [".java", ".jav"]);
+        assert!(settings.accepts("Foo.jav"));
+
+        let settings = parse("", r#"<property name="fileExtensions" value="jav"/>"#);
+        assert!(settings.accepts("Foo.jav"));
+        assert!(!settings.accepts("Foo.java"));
+    }
+}
@@ -3,15 +3,22 @@
 //! This crate provides parsers for:
 //! - checkstyle.xml (the source of truth for rules)
 //! - lintal.toml (optional overlay for fix behavior)
+//! - the checkstyle plugin settings of pom.xml and build.gradle(.kts)
 //!
 //! The [MergedConfig] combines both sources, with checkstyle.xml defining
 //! *what* rules run and lintal.toml defining *how* violations are handled.
 
+mod build_plugin;
+mod checker;
 mod lintal_config;
 mod merged_config;
+mod properties;
 
+pub use build_plugin::{BUILD_FILE_NAMES, BuildPluginConfig};
+pub use checker::{CheckerSettings, DEFAULT_TAB_WIDTH};
 pub use lintal_config::{FixConfig, LintalConfig, LintalConfigError, RuleMode};
 pub use merged_config::{ConfigError, ConfigLoader, ConfiguredRule, MergedConfig};
+pub use properties::{PropertyError, PropertyResolver};
 
 use quick_xml::de::from_str;
 use serde::Deserialize;
@@ -34,6 +41,9 @@ pub struct Property {
     pub name: String,
     #[serde(rename = "@value")]
     pub value: String,
+    /// Fallback used when `value` contains an unresolvable `${...}` placeholder.
+    #[serde(default, rename = "@default")]
+    pub default: Option<String>,
 }
 
 /// A checkstyle module (rule or container).
@@ -15,6 +15,9 @@
 //!
 //! [checkstyle]
 //! config = "config/checkstyle/checkstyle.xml"
+//!
+//! [properties]
+//! "checkstyle.suppressions.file" = "config/checkstyle/suppressions.xml"
 //! ```
 
 use serde::Deserialize;
@@ -44,6 +47,18 @@ pub enum RuleMode {
     Disabled,
 }
 
+impl RuleMode {
+    /// The name of the mode, as written in lintal.toml.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RuleMode::Fix => "fix",
+            RuleMode::Check => "check",
+            RuleMode::Suggest => "suggest",
+            RuleMode::Disabled => "disabled",
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for RuleMode {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -92,6 +107,10 @@ pub struct LintalConfig {
     /// Reference to checkstyle.xml.
     #[serde(default)]
     pub checkstyle: CheckstyleReference,
+
+    /// Values for `${...}` placeholders in checkstyle.xml.
+    #[serde(default)]
+    pub properties: HashMap<String, String>,
 }
 
 impl LintalConfig {
@@ -205,4 +224,26 @@ Rule4 = "off"
         assert_eq!(config.rule_mode("Rule3"), RuleMode::Disabled);
         assert_eq!(config.rule_mode("Rule4"), RuleMode::Disabled);
     }
+
+    #[test]
+    fn test_properties() {
+        let toml = r#"
+[properties]
+"checkstyle.suppressions.file" = "config/suppressions.xml"
+basedir = "."
+"#;
+
+        let config = LintalConfig::parse(toml).unwrap();
+        assert_eq!(
+            config
+                .properties
+                .get("checkstyle.suppressions.file")
+                .map(String::as_str),
+            Some("config/suppressions.xml")
+        );
+        assert_eq!(
+            config.properties.get("basedir").map(String::as_str),
+            Some(".")
+        );
+    }
 }
@@ -6,7 +6,11 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use crate::{CheckstyleConfig, CheckstyleError, LintalConfig, LintalConfigError, RuleMode};
+use lintal_diagnostics::Severity;
+
+use crate::{
+    CheckerSettings, CheckstyleConfig, CheckstyleError, LintalConfig, LintalConfigError, RuleMode,
+};
 
 /// Error during config loading.
 #[derive(Debug)]
@@ -52,6 +56,9 @@ pub struct ConfiguredRule {
     pub properties: HashMap<String, String>,
     /// How to handle violations (from lintal.toml).
     pub mode: RuleMode,
+    /// Severity of violations, from the module's `severity` property or
+    /// inherited from `TreeWalker` and `Checker`.
+    pub severity: Severity,
 }
 
 impl ConfiguredRule {
@@ -69,8 +76,11 @@ impl ConfiguredRule {
     }
 
     /// Check if this rule is enabled.
+    ///
+    /// Rules with `ignore` severity are treated as disabled, since checkstyle
+    /// never reports their violations.
     pub fn is_enabled(&self) -> bool {
-        self.mode != RuleMode::Disabled
+        self.mode != RuleMode::Disabled && self.severity != Severity::Ignore
     }
 
     /// Check if this rule should auto-fix.
@@ -86,6 +96,8 @@ pub struct MergedConfig {
     pub rules: Vec<ConfiguredRule>,
     /// Whether to apply unsafe fixes.
     pub unsafe_fixes: bool,
+    /// Settings of the `Checker` module.
+    pub checker: CheckerSettings,
 }
 
 impl MergedConfig {
@@ -93,6 +105,22 @@ impl MergedConfig {
     pub fn new(checkstyle: &CheckstyleConfig, lintal: Option<&LintalConfig>) -> Self {
         let lintal = lintal.cloned().unwrap_or_default();
 
+        // Checkstyle inherits severity from the enclosing module.
+        let checker_severity = parse_severity(
+            checkstyle
+                .properties
+                .iter()
+                .find(|p| p.name == "severity")
+                .map(|p| p.value.as_str()),
+            Severity::default(),
+        );
+        let tree_walker_severity = parse_severity(
+            checkstyle
+                .tree_walker()
+                .and_then(|tw| tw.property("severity")),
+            checker_severity,
+        );
+
         let rules = checkstyle
             .rules()
             .iter()
@@ -104,12 +132,14 @@ impl MergedConfig {
                     .map(|(k, v)| (k.to_string(), v.to_string()))
                     .collect(),
                 mode: lintal.rule_mode(&module.name),
+                severity: parse_severity(module.property("severity"), tree_walker_severity),
             })
             .collect();
 
         Self {
             rules,
             unsafe_fixes: lintal.fix.unsafe_fixes,
+            checker: CheckerSettings::new(checkstyle),
         }
     }
 
@@ -129,6 +159,12 @@ impl MergedConfig {
     }
 }
 
+/// Parse a `severity` property, falling back to `inherited` if it is unset or
+/// not a valid level.
+fn parse_severity(value: Option<&str>, inherited: Severity) -> Severity {
+    value.and_then(|v| v.parse().ok()).unwrap_or(inherited)
+}
+
 /// Builder for loading configuration from files.
 pub struct ConfigLoader {
     checkstyle_path: Option<std::path::PathBuf>,
@@ -312,4 +348,40 @@ NeedBraces = "disabled"
         let enabled: Vec<_> = merged.enabled_rules().collect();
         assert_eq!(enabled.len(), 2);
     }
+
+    #[test]
+    fn test_severity_inheritance() {
+        let checkstyle = CheckstyleConfig::parse(
+            r#"<?xml version="1.0"?>
+<module name="Checker">
+    <property name="severity" value="warning"/>
+    <module name="TreeWalker">
+        <module name="WhitespaceAround"/>
+        <module name="LeftCurly">
+            <property name="severity" value="error"/>
+        </module>
+        <module name="NeedBraces">
+            <property name="severity" value="ignore"/>
+        </module>
+    </module>
+</module>"#,
+        )
+        .unwrap();
+
+        let merged = MergedConfig::new(&checkstyle, None);
+        let severity = |name| merged.get_rule(name).unwrap().severity;
+
+        assert_eq!(severity("WhitespaceAround"), Severity::Warning);
+        assert_eq!(severity("LeftCurly"), Severity::Error);
+        assert_eq!(severity("NeedBraces"), Severity::Ignore);
+        assert!(!merged.get_rule("NeedBraces").unwrap().is_enabled());
+    }
+
+    #[test]
+    fn test_severity_defaults_to_error() {
+        let merged = MergedConfig::new(&sample_checkstyle(), None);
+        for rule in &merged.rules {
+            assert_eq!(rule.severity, Severity::Error);
+        }
+    }
 }
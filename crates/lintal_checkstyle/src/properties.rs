@@ -0,0 +1,331 @@
+//! Expansion of `${...}` placeholders in checkstyle.xml property values.
+//!
+//! Maven and Gradle normally supply these values when they run checkstyle.
+//! lintal resolves them from, in order of priority:
+//!
+//! 1. `-D key=value` command line definitions
+//! 2. the `[properties]` table in lintal.toml
+//! 3. environment variables, either named exactly like the property or in
+//!    upper case with `.` and `-` replaced by `_` (`CHECKSTYLE_SUPPRESSIONS_FILE`)
+//! 4. built-in values such as `config_loc` and `basedir`
+//!
+//! Like checkstyle, `${name:fallback}` uses `fallback` when `name` is not
+//! defined, a `<property>` element's `default` attribute is used when its
+//! value cannot be resolved, and `$$` is an escaped `$`.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::{CheckstyleConfig, Module, Property};
+
+/// A placeholder that could not be resolved.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PropertyError {
+    #[error("Property ${{{name}}} is not defined (in {module}.{property})")]
+    Undefined {
+        /// The placeholder name.
+        name: String,
+        /// The module containing the property.
+        module: String,
+        /// The property whose value contains the placeholder.
+        property: String,
+    },
+    #[error("Unterminated ${{ in {module}.{property}: {value}")]
+    Unterminated {
+        /// The module containing the property.
+        module: String,
+        /// The property whose value contains the placeholder.
+        property: String,
+        /// The raw property value.
+        value: String,
+    },
+}
+
+/// Resolves `${...}` placeholders from layered property sources.
+#[derive(Debug, Clone, Default)]
+pub struct PropertyResolver {
+    defines: HashMap<String, String>,
+    config: HashMap<String, String>,
+    builtins: HashMap<String, String>,
+    use_env: bool,
+}
+
+/// Why a single placeholder failed to expand.
+enum ExpandError {
+    Undefined(String),
+    Unterminated,
+}
+
+impl PropertyResolver {
+    /// Create a resolver that consults environment variables.
+    pub fn new() -> Self {
+        Self {
+            use_env: true,
+            ..Self::default()
+        }
+    }
+
+    /// Add command line definitions, which take priority over everything else.
+    #[must_use]
+    pub fn with_defines(mut self, defines: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.defines.extend(defines);
+        self
+    }
+
+    /// Add values from lintal.toml.
+    #[must_use]
+    pub fn with_config(mut self, properties: &HashMap<String, String>) -> Self {
+        self.config
+            .extend(properties.iter().map(|(k, v)| (k.clone(), v.clone())));
+        self
+    }
+
+    /// Add a built-in value, used only when no other source defines `name`.
+    #[must_use]
+    pub fn with_builtin(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.builtins.insert(name.into(), value.into());
+        self
+    }
+
+    /// Enable or disable environment variable lookup.
+    #[must_use]
+    pub fn with_env(mut self, use_env: bool) -> Self {
+        self.use_env = use_env;
+        self
+    }
+
+    /// Look up the value of a property.
+    pub fn lookup(&self, name: &str) -> Option<String> {
+        if let Some(value) = self.defines.get(name).or_else(|| self.config.get(name)) {
+            return Some(value.clone());
+        }
+        if self.use_env {
+            let normalized = name.to_ascii_uppercase().replace(['.', '-'], "_");
+            if let Ok(value) = std::env::var(name).or_else(|_| std::env::var(normalized)) {
+                return Some(value);
+            }
+        }
+        self.builtins.get(name).cloned()
+    }
+
+    /// Expand all placeholders in every property of `config`.
+    ///
+    /// All failures are collected so they can be reported together.
+    pub fn resolve(&self, config: &mut CheckstyleConfig) -> Result<(), Vec<PropertyError>> {
+        let mut errors = Vec::new();
+        self.resolve_properties(&config.name, &mut config.properties, &mut errors);
+        for module in &mut config.modules {
+            self.resolve_module(module, &mut errors);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn resolve_module(&self, module: &mut Module, errors: &mut Vec<PropertyError>) {
+        self.resolve_properties(&module.name, &mut module.properties, errors);
+        for child in &mut module.modules {
+            self.resolve_module(child, errors);
+        }
+    }
+
+    fn resolve_properties(
+        &self,
+        module: &str,
+        properties: &mut [Property],
+        errors: &mut Vec<PropertyError>,
+    ) {
+        for property in properties {
+            let expanded = match self.expand(&property.value) {
+                Ok(value) => Ok(value),
+                Err(err) => match property.default.as_deref().map(|d| self.expand(d)) {
+                    Some(Ok(value)) => Ok(value),
+                    _ => Err(err),
+                },
+            };
+            match expanded {
+                Ok(value) => property.value = value,
+                Err(ExpandError::Undefined(name)) => errors.push(PropertyError::Undefined {
+                    name,
+                    module: module.to_string(),
+                    property: property.name.clone(),
+                }),
+                Err(ExpandError::Unterminated) => errors.push(PropertyError::Unterminated {
+                    module: module.to_string(),
+                    property: property.name.clone(),
+                    value: property.value.clone(),
+                }),
+            }
+        }
+    }
+
+    /// Expand the placeholders in a single value.
+    fn expand(&self, value: &str) -> Result<String, ExpandError> {
+        let mut result = String::with_capacity(value.len());
+        let mut rest = value;
+
+        while let Some(pos) = rest.find('$') {
+            result.push_str(&rest[..pos]);
+            let after = &rest[pos + 1..];
+
+            if let Some(after) = after.strip_prefix('$') {
+                result.push('$');
+                rest = after;
+            } else if let Some(after) = after.strip_prefix('{') {
+                let end = after.find('}').ok_or(ExpandError::Unterminated)?;
+                let placeholder = &after[..end];
+                let (name, fallback) = match placeholder.split_once(':') {
+                    Some((name, fallback)) => (name, Some(fallback)),
+                    None => (placeholder, None),
+                };
+                match self.lookup(name) {
+                    Some(resolved) => result.push_str(&resolved),
+                    None => match fallback {
+                        Some(fallback) => result.push_str(fallback),
+                        None => return Err(ExpandError::Undefined(name.to_string())),
+                    },
+                }
+                rest = &after[end + 1..];
+            } else {
+                result.push('$');
+                rest = after;
+            }
+        }
+
+        result.push_str(rest);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver() -> PropertyResolver {
+        PropertyResolver::new()
+            .with_env(false)
+            .with_builtin("config_loc", "config/checkstyle")
+            .with_builtin("basedir", "/project")
+    }
+
+    fn resolve(
+        xml: &str,
+        resolver: &PropertyResolver,
+    ) -> Result<CheckstyleConfig, Vec<PropertyError>> {
+        let mut config = CheckstyleConfig::parse(xml).unwrap();
+        resolver.resolve(&mut config).map(|()| config)
+    }
+
+    #[test]
+    fn test_expands_nested_module_properties() {
+        let config = resolve(
+            r#"<module name="Checker">
+    <property name="basedir" value="${basedir}"/>
+    <module name="SuppressionFilter">
+        <property name="file" value="${config_loc}/suppressions.xml"/>
+    </module>
+    <module name="TreeWalker">
+        <module name="LineLength">
+            <property name="max" value="${line.max}"/>
+        </module>
+    </module>
+</module>"#,
+            &resolver().with_defines([("line.max".to_string(), "120".to_string())]),
+        )
+        .unwrap();
+
+        assert_eq!(config.properties[0].value, "/project");
+        assert_eq!(
+            config.modules[0].property("file"),
+            Some("config/checkstyle/suppressions.xml")
+        );
+        assert_eq!(config.rules()[0].property("max"), Some("120"));
+    }
+
+    #[test]
+    fn test_source_priority() {
+        let mut lintal = HashMap::new();
+        lintal.insert("config_loc".to_string(), "from-toml".to_string());
+        lintal.insert("basedir".to_string(), "from-toml".to_string());
+        let resolver = resolver()
+            .with_config(&lintal)
+            .with_defines([("basedir".to_string(), "from-cli".to_string())]);
+
+        assert_eq!(resolver.lookup("basedir").as_deref(), Some("from-cli"));
+        assert_eq!(resolver.lookup("config_loc").as_deref(), Some("from-toml"));
+    }
+
+    #[test]
+    fn test_inline_fallback_and_default_attribute() {
+        let config = resolve(
+            r#"<module name="Checker">
+    <module name="TreeWalker">
+        <module name="LineLength">
+            <property name="max" value="${line.max:100}"/>
+            <property name="ignorePattern" value="${ignore.pattern}" default="^import"/>
+        </module>
+    </module>
+</module>"#,
+            &resolver(),
+        )
+        .unwrap();
+
+        let module = config.rules()[0];
+        assert_eq!(module.property("max"), Some("100"));
+        assert_eq!(module.property("ignorePattern"), Some("^import"));
+    }
+
+    #[test]
+    fn test_escaped_dollar() {
+        let config = resolve(
+            r#"<module name="Checker">
+    <property name="pattern" value="^$${literal}$"/>
+</module>"#,
+            &resolver(),
+        )
+        .unwrap();
+
+        assert_eq!(config.properties[0].value, "^${literal}$");
+    }
+
+    #[test]
+    fn test_unresolved_placeholders_are_errors() {
+        let errors = resolve(
+            r#"<module name="Checker">
+    <module name="SuppressionFilter">
+        <property name="file" value="${checkstyle.suppressions.file}"/>
+    </module>
+    <module name="TreeWalker">
+        <module name="LineLength">
+            <property name="max" value="${line.max"/>
+        </module>
+    </module>
+</module>"#,
+            &resolver(),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                PropertyError::Undefined {
+                    name: "checkstyle.suppressions.file".to_string(),
+                    module: "SuppressionFilter".to_string(),
+                    property: "file".to_string(),
+                },
+                PropertyError::Unterminated {
+                    module: "LineLength".to_string(),
+                    property: "max".to_string(),
+                    value: "${line.max".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "Property ${checkstyle.suppressions.file} is not defined (in SuppressionFilter.file)"
+        );
+    }
+}
@@ -2,7 +2,7 @@
 
 use lintal_text_size::TextRange;
 
-use crate::Fix;
+use crate::{Fix, Severity};
 
 /// Indicates whether a fix is available for a violation.
 #[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
@@ -16,6 +16,35 @@ pub enum FixAvailability {
     None,
 }
 
+impl FixAvailability {
+    /// The availability of fixes for a rule that reports violations with
+    /// each of these availabilities: always or never if they all agree,
+    /// sometimes otherwise.
+    pub const fn of(violations: &[FixAvailability]) -> Self {
+        let mut always = true;
+        let mut never = true;
+        let mut i = 0;
+        while i < violations.len() {
+            match violations[i] {
+                FixAvailability::Always => never = false,
+                FixAvailability::Sometimes => {
+                    always = false;
+                    never = false;
+                }
+                FixAvailability::None => always = false,
+            }
+            i += 1;
+        }
+        if never {
+            FixAvailability::None
+        } else if always {
+            FixAvailability::Always
+        } else {
+            FixAvailability::Sometimes
+        }
+    }
+}
+
 /// A trait for violations that can be reported as diagnostics.
 pub trait Violation: std::fmt::Debug + Clone + Send + Sync {
     /// The availability of a fix for this violation.
@@ -37,6 +66,8 @@ pub struct DiagnosticKind {
     pub code: String,
     /// The message body.
     pub body: String,
+    /// What the fix for the violation does, from [`Violation::fix_title`].
+    pub suggestion: Option<String>,
 }
 
 /// A diagnostic representing a violation found in source code.
@@ -48,6 +79,8 @@ pub struct Diagnostic {
     pub range: TextRange,
     /// The optional fix for the violation.
     pub fix: Option<Fix>,
+    /// The severity of the violation.
+    pub severity: Severity,
 }
 
 impl Diagnostic {
@@ -62,9 +95,11 @@ impl Diagnostic {
                     .unwrap_or("Unknown")
                     .to_string(),
                 body: violation.message(),
+                suggestion: violation.fix_title(),
             },
             range,
             fix: None,
+            severity: Severity::default(),
         }
     }
 
@@ -75,6 +110,13 @@ impl Diagnostic {
         self
     }
 
+    /// Set the severity of this diagnostic.
+    #[must_use]
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
     /// Set the fix for this diagnostic.
     pub fn set_fix(&mut self, fix: Fix) {
         self.fix = Some(fix);
@@ -6,9 +6,11 @@
 pub use diagnostic::{Diagnostic, DiagnosticKind, FixAvailability, Violation};
 pub use edit::Edit;
 pub use fix::{Applicability, Fix, IsolationLevel};
+pub use severity::{ParseSeverityError, Severity};
 pub use source_map::{SourceMap, SourceMarker};
 
 mod diagnostic;
 mod edit;
 mod fix;
+mod severity;
 mod source_map;
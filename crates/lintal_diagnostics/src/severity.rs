@@ -0,0 +1,75 @@
+//! Severity levels for diagnostics.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The severity of a diagnostic, matching checkstyle's `SeverityLevel`.
+///
+/// Levels are ordered from least to most severe, so thresholds can be
+/// compared directly. Checkstyle only fails a run on [`Severity::Error`].
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Severity {
+    /// The violation is not reported.
+    Ignore,
+    /// Informational; reported but never fails the run.
+    Info,
+    /// Reported but does not fail the run by default.
+    Warning,
+    /// Reported and fails the run (checkstyle's default).
+    #[default]
+    Error,
+}
+
+impl Severity {
+    /// The lowercase name used by checkstyle configuration and output.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Severity::Ignore => "ignore",
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing an unknown severity level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSeverityError(String);
+
+impl fmt::Display for ParseSeverityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid severity '{}', expected ignore, info, warning, or error",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseSeverityError {}
+
+impl FromStr for Severity {
+    type Err = ParseSeverityError;
+
+    /// Parse a severity case-insensitively, as checkstyle does.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "ignore" => Ok(Severity::Ignore),
+            "info" => Ok(Severity::Info),
+            "warning" => Ok(Severity::Warning),
+            "error" => Ok(Severity::Error),
+            _ => Err(ParseSeverityError(s.to_string())),
+        }
+    }
+}
@@ -1,11 +1,23 @@
 //! Java linter with auto-fix support.
 
+pub mod metadata;
 pub mod registry;
 pub mod rules;
 pub mod suppression;
+pub mod syntax;
+pub mod xpath;
 
+pub use metadata::{
+    CHECKER_PROPERTIES, COMMON_PROPERTIES, PropertyIssue, PropertyIssueKind, PropertyKind,
+    PropertySpec, RuleCategory, RuleMetadata, TREE_WALKER_PROPERTIES, validate_properties,
+};
 pub use registry::{FromConfig, Properties, RuleRegistry};
-pub use suppression::{FileSuppressionsConfig, PlainTextCommentFilterConfig, SuppressionContext};
+pub use suppression::{
+    FileSuppressions, FileSuppressionsConfig, NearbyCommentFilterConfig,
+    PlainTextCommentFilterConfig, RuleCoverage, SuppressionContext, SuppressionTarget,
+    SuppressionsXmlError, UNUSED_SUPPRESSION, UnusedSuppression,
+};
+pub use syntax::{SYNTAX_ERROR, has_syntax_errors, syntax_errors};
 
 use lintal_diagnostics::Diagnostic;
 use lintal_java_cst::CstNode;
@@ -0,0 +1,575 @@
+//! Static descriptions of the built-in rules.
+//!
+//! Every rule declares a [`RuleMetadata`] through [`FromConfig::METADATA`]:
+//! what it checks, the properties checkstyle accepts for its module, which
+//! of those lintal honors, and whether violations can be fixed. The CLI
+//! renders this for `lintal rules` and `lintal rule <name>`, and validates
+//! checkstyle.xml against it.
+//!
+//! [`FromConfig::METADATA`]: crate::FromConfig::METADATA
+
+use std::fmt;
+
+use lintal_diagnostics::FixAvailability;
+use regex::Regex;
+
+use crate::Properties;
+
+/// Properties that checkstyle accepts on every check.
+pub const COMMON_PROPERTIES: &[PropertySpec] = &[
+    PropertySpec::new(
+        "id",
+        PropertyKind::String,
+        "",
+        "An identifier for the module, used by suppressions.",
+    ),
+    PropertySpec::new(
+        "severity",
+        PropertyKind::Enum(&["ignore", "info", "warning", "error"]),
+        "error",
+        "The severity of violations, inherited from the enclosing module.",
+    ),
+];
+
+/// Describes a rule independently of any configuration.
+#[derive(Debug, Clone, Copy)]
+pub struct RuleMetadata {
+    /// A one-sentence summary of what the rule checks.
+    pub description: &'static str,
+    /// The checkstyle package the module belongs to.
+    pub category: RuleCategory,
+    /// Every property checkstyle accepts for the module, including those
+    /// that lintal ignores.
+    pub properties: &'static [PropertySpec],
+    /// Whether violations come with an automatic fix.
+    pub fix: FixAvailability,
+    /// Known differences from checkstyle's behavior.
+    pub compatibility: &'static [&'static str],
+    /// Java source that violates the rule under its default properties.
+    pub example: &'static str,
+}
+
+impl RuleMetadata {
+    /// Look up a property by its checkstyle name.
+    pub fn property(&self, name: &str) -> Option<&PropertySpec> {
+        self.properties
+            .iter()
+            .chain(COMMON_PROPERTIES)
+            .find(|property| property.name == name)
+    }
+
+    /// Check configured properties against the rule's schema, returning
+    /// the problems sorted by property name.
+    pub fn validate(&self, properties: &Properties) -> Vec<PropertyIssue> {
+        validate_properties(&[self.properties, COMMON_PROPERTIES], properties)
+    }
+}
+
+/// Properties of the root `Checker` module.
+pub const CHECKER_PROPERTIES: &[PropertySpec] = &[
+    PropertySpec::new(
+        "basedir",
+        PropertyKind::String,
+        "",
+        "Directory that file paths are reported relative to, and matched against suppressions.",
+    ),
+    PropertySpec::new(
+        "cacheFile",
+        PropertyKind::String,
+        "",
+        "File that caches the results of unchanged files.",
+    )
+    .unsupported(),
+    PropertySpec::new(
+        "charset",
+        PropertyKind::String,
+        "UTF-8",
+        "Encoding of the source files.",
+    ),
+    PropertySpec::new(
+        "fileExtensions",
+        PropertyKind::String,
+        "",
+        "Extensions of the files to check, all if empty.",
+    ),
+    PropertySpec::new(
+        "haltOnException",
+        PropertyKind::Boolean,
+        "true",
+        "Stop when a file cannot be processed.",
+    )
+    .unsupported(),
+    PropertySpec::new(
+        "localeCountry",
+        PropertyKind::String,
+        "",
+        "Country of the locale for violation messages.",
+    )
+    .unsupported(),
+    PropertySpec::new(
+        "localeLanguage",
+        PropertyKind::String,
+        "",
+        "Language of the locale for violation messages.",
+    )
+    .unsupported(),
+    PropertySpec::new(
+        "severity",
+        PropertyKind::Enum(&["ignore", "info", "warning", "error"]),
+        "error",
+        "The default severity of violations.",
+    ),
+    PropertySpec::new(
+        "tabWidth",
+        PropertyKind::PositiveInteger,
+        "8",
+        "Number of columns a tab advances to, for reported columns and indentation.",
+    ),
+];
+
+/// Properties of the `TreeWalker` module.
+pub const TREE_WALKER_PROPERTIES: &[PropertySpec] = &[
+    PropertySpec::new(
+        "fileExtensions",
+        PropertyKind::String,
+        "java",
+        "Extensions of the files the checks run on.",
+    ),
+    PropertySpec::new(
+        "id",
+        PropertyKind::String,
+        "",
+        "An identifier for the module, used by suppressions.",
+    ),
+    PropertySpec::new(
+        "javaParseExceptionSeverity",
+        PropertyKind::Enum(&["ignore", "info", "warning", "error"]),
+        "error",
+        "The severity of files that cannot be parsed.",
+    )
+    .unsupported(),
+    PropertySpec::new(
+        "severity",
+        PropertyKind::Enum(&["ignore", "info", "warning", "error"]),
+        "error",
+        "The severity of violations, inherited from Checker.",
+    ),
+    PropertySpec::new(
+        "skipFileOnJavaParseException",
+        PropertyKind::Boolean,
+        "false",
+        "Skip files that cannot be parsed instead of failing.",
+    )
+    .unsupported(),
+];
+
+/// Check configured properties against a schema made of one or more lists
+/// of properties, returning the problems sorted by property name.
+pub fn validate_properties(
+    schema: &[&'static [PropertySpec]],
+    properties: &Properties,
+) -> Vec<PropertyIssue> {
+    let specs = || schema.iter().flat_map(|specs| specs.iter());
+    let mut names: Vec<&str> = properties.keys().copied().collect();
+    names.sort_unstable();
+
+    let mut issues = Vec::new();
+    for name in names {
+        let Some(spec) = specs().find(|spec| spec.name == name) else {
+            issues.push(PropertyIssue {
+                property: name.to_string(),
+                kind: PropertyIssueKind::Unknown {
+                    suggestion: suggest(specs(), name),
+                },
+            });
+            continue;
+        };
+        let value = properties[name];
+        if let Err(reason) = spec.kind.check(value) {
+            issues.push(PropertyIssue {
+                property: name.to_string(),
+                kind: PropertyIssueKind::InvalidValue {
+                    value: value.to_string(),
+                    reason,
+                },
+            });
+        } else if !spec.supported {
+            issues.push(PropertyIssue {
+                property: name.to_string(),
+                kind: PropertyIssueKind::Unsupported,
+            });
+        }
+    }
+    issues
+}
+
+/// The known property closest to a misspelled `name`, if any is close.
+fn suggest<'a>(specs: impl Iterator<Item = &'a PropertySpec>, name: &str) -> Option<&'static str> {
+    specs
+        .map(|spec| (edit_distance(name, spec.name), spec.name))
+        .filter(|(distance, _)| *distance <= 2 || *distance < name.len() / 4)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name)
+}
+
+/// A problem with a configured property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyIssue {
+    pub property: String,
+    pub kind: PropertyIssueKind,
+}
+
+impl PropertyIssue {
+    /// Errors are properties that are misspelled or have values lintal
+    /// cannot use; the rest only warn that lintal ignores them.
+    pub fn is_error(&self) -> bool {
+        !matches!(self.kind, PropertyIssueKind::Unsupported)
+    }
+}
+
+impl fmt::Display for PropertyIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            PropertyIssueKind::Unknown { suggestion } => {
+                write!(f, "unknown property '{}'", self.property)?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, " (did you mean '{suggestion}'?)")?;
+                }
+                Ok(())
+            }
+            PropertyIssueKind::InvalidValue { value, reason } => write!(
+                f,
+                "invalid value '{value}' for property '{}': {reason}",
+                self.property
+            ),
+            PropertyIssueKind::Unsupported => write!(
+                f,
+                "property '{}' is supported by checkstyle but ignored by lintal",
+                self.property
+            ),
+        }
+    }
+}
+
+/// The kind of problem with a configured property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropertyIssueKind {
+    /// Checkstyle does not know the property either.
+    Unknown {
+        /// A known property with a similar name.
+        suggestion: Option<&'static str>,
+    },
+    /// The value cannot be parsed, so the default is used instead.
+    InvalidValue { value: String, reason: String },
+    /// Checkstyle honors the property, but lintal ignores it.
+    Unsupported,
+}
+
+/// The checkstyle package a rule is found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RuleCategory {
+    Whitespace,
+    Indentation,
+    Blocks,
+    Modifier,
+    /// Checks in the root `checks` package, which checkstyle's
+    /// documentation lists as miscellaneous.
+    Misc,
+    Imports,
+    Coding,
+    Design,
+    Naming,
+}
+
+impl RuleCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RuleCategory::Whitespace => "whitespace",
+            RuleCategory::Indentation => "indentation",
+            RuleCategory::Blocks => "blocks",
+            RuleCategory::Modifier => "modifier",
+            RuleCategory::Misc => "misc",
+            RuleCategory::Imports => "imports",
+            RuleCategory::Coding => "coding",
+            RuleCategory::Design => "design",
+            RuleCategory::Naming => "naming",
+        }
+    }
+
+    /// The fully qualified name of the Java package.
+    pub fn package(self) -> String {
+        const ROOT: &str = "com.puppycrawl.tools.checkstyle.checks";
+        match self {
+            RuleCategory::Misc => ROOT.to_string(),
+            _ => format!("{ROOT}.{}", self.as_str()),
+        }
+    }
+}
+
+/// A property of a checkstyle module.
+#[derive(Debug, Clone, Copy)]
+pub struct PropertySpec {
+    /// The name used in checkstyle.xml.
+    pub name: &'static str,
+    pub kind: PropertyKind,
+    /// The default value, as it would be written in checkstyle.xml.
+    pub default: &'static str,
+    pub description: &'static str,
+    /// False for properties checkstyle accepts but lintal ignores.
+    pub supported: bool,
+}
+
+impl PropertySpec {
+    pub const fn new(
+        name: &'static str,
+        kind: PropertyKind,
+        default: &'static str,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            kind,
+            default,
+            description,
+            supported: true,
+        }
+    }
+
+    /// Mark the property as accepted by checkstyle but ignored by lintal.
+    #[must_use]
+    pub const fn unsupported(mut self) -> Self {
+        self.supported = false;
+        self
+    }
+}
+
+/// The type of a property value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyKind {
+    /// `true` or `false`.
+    Boolean,
+    /// A decimal integer.
+    Integer,
+    /// A decimal integer of at least 1.
+    PositiveInteger,
+    /// Free-form text.
+    String,
+    /// A Java regular expression.
+    Regex,
+    /// One of a fixed set of values, matched case-insensitively.
+    Enum(&'static [&'static str]),
+    /// A comma-separated subset of these checkstyle tokens.
+    Tokens(&'static [&'static str]),
+    /// A comma-separated subset of these values.
+    Set(&'static [&'static str]),
+}
+
+impl PropertyKind {
+    /// The type's name, as shown by `lintal rule`.
+    pub fn name(self) -> &'static str {
+        match self {
+            PropertyKind::Boolean => "boolean",
+            PropertyKind::Integer => "integer",
+            PropertyKind::PositiveInteger => "positive integer",
+            PropertyKind::String => "string",
+            PropertyKind::Regex => "regex",
+            PropertyKind::Enum(_) => "enum",
+            PropertyKind::Tokens(_) => "tokens",
+            PropertyKind::Set(_) => "set",
+        }
+    }
+
+    /// The accepted values of an enum, token or set property.
+    pub fn values(self) -> &'static [&'static str] {
+        match self {
+            PropertyKind::Enum(values)
+            | PropertyKind::Tokens(values)
+            | PropertyKind::Set(values) => values,
+            _ => &[],
+        }
+    }
+
+    /// Check that `value` is valid for this type, returning why not.
+    pub fn check(self, value: &str) -> Result<(), String> {
+        let value = value.trim();
+        match self {
+            PropertyKind::Boolean => {
+                if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+                    Ok(())
+                } else {
+                    Err("expected true or false".to_string())
+                }
+            }
+            PropertyKind::Integer => value
+                .parse::<i64>()
+                .map(|_| ())
+                .map_err(|_| "expected an integer".to_string()),
+            PropertyKind::PositiveInteger => match value.parse::<i64>() {
+                Ok(n) if n >= 1 => Ok(()),
+                _ => Err("expected an integer of at least 1".to_string()),
+            },
+            PropertyKind::String => Ok(()),
+            PropertyKind::Regex => Regex::new(value).map(|_| ()).map_err(|error| {
+                // Syntax errors render the pattern over several lines, ending
+                // with the error itself
+                let error = error.to_string();
+                let reason = error.lines().last().unwrap_or_default().trim();
+                let reason = reason.strip_prefix("error: ").unwrap_or(reason);
+                format!("not a supported regular expression ({reason})")
+            }),
+            PropertyKind::Enum(values) => {
+                if values.iter().any(|v| v.eq_ignore_ascii_case(value)) {
+                    Ok(())
+                } else {
+                    Err(format!("expected one of {}", values.join(", ")))
+                }
+            }
+            PropertyKind::Tokens(values) | PropertyKind::Set(values) => {
+                // Token names are case-sensitive in checkstyle, other sets are not
+                let case_sensitive = matches!(self, PropertyKind::Tokens(_));
+                let unknown: Vec<&str> = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .filter(|item| {
+                        !values.iter().any(|v| {
+                            if case_sensitive {
+                                v == item
+                            } else {
+                                v.eq_ignore_ascii_case(item)
+                            }
+                        })
+                    })
+                    .collect();
+                match unknown.as_slice() {
+                    [] => Ok(()),
+                    [item] => Err(format!("'{item}' is not accepted here")),
+                    items => Err(format!("{} are not accepted here", items.join(", "))),
+                }
+            }
+        }
+    }
+}
+
+/// The Levenshtein distance between two ASCII names, ignoring case.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.to_ascii_lowercase().into_bytes();
+    let b = b.to_ascii_lowercase().into_bytes();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::FromConfig;
+    use crate::rules::{Indentation, WhitespaceAround};
+
+    fn validate(metadata: &RuleMetadata, properties: &[(&str, &str)]) -> Vec<String> {
+        let properties: HashMap<&str, &str> = properties.iter().copied().collect();
+        metadata
+            .validate(&properties)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_properties() {
+        let issues = validate(
+            &WhitespaceAround::METADATA,
+            &[
+                ("allowEmptyLambdas", "TRUE"),
+                ("tokens", "ASSIGN, GENERIC_START"),
+                ("severity", "warning"),
+                ("id", "ws"),
+            ],
+        );
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn test_unknown_property_suggests_similar_name() {
+        assert_eq!(
+            validate(&WhitespaceAround::METADATA, &[("allowEmptyLambda", "true")]),
+            ["unknown property 'allowEmptyLambda' (did you mean 'allowEmptyLambdas'?)"]
+        );
+        assert_eq!(
+            validate(&WhitespaceAround::METADATA, &[("max", "100")]),
+            ["unknown property 'max'"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        assert_eq!(
+            validate(
+                &Indentation::METADATA,
+                &[("basicOffset", "four"), ("forceStrictCondition", "maybe")]
+            ),
+            [
+                "invalid value 'four' for property 'basicOffset': expected an integer",
+                "invalid value 'maybe' for property 'forceStrictCondition': expected true or false",
+            ]
+        );
+        assert_eq!(
+            validate(
+                &WhitespaceAround::METADATA,
+                &[("tokens", "ASSIGN, NOPE"), ("severity", "fatal")]
+            ),
+            [
+                "invalid value 'fatal' for property 'severity': expected one of ignore, info, warning, error",
+                "invalid value 'ASSIGN, NOPE' for property 'tokens': 'NOPE' is not accepted here",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let issues =
+            crate::rules::MemberName::METADATA.validate(&HashMap::from([("format", "^[a-z")]));
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(
+            &issues[0].kind,
+            PropertyIssueKind::InvalidValue { reason, .. }
+                if reason == "not a supported regular expression (unclosed character class)"
+        ));
+    }
+
+    #[test]
+    fn test_checker_properties() {
+        let properties =
+            HashMap::from([("tabWidth", "0"), ("localeLanguage", "de"), ("charst", "")]);
+        let issues: Vec<String> = validate_properties(&[CHECKER_PROPERTIES], &properties)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                "unknown property 'charst' (did you mean 'charset'?)",
+                "property 'localeLanguage' is supported by checkstyle but ignored by lintal",
+                "invalid value '0' for property 'tabWidth': expected an integer of at least 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_property_is_a_warning() {
+        let issues =
+            crate::rules::EmptyBlock::METADATA.validate(&HashMap::from([("tokens", "LITERAL_IF")]));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, PropertyIssueKind::Unsupported);
+        assert!(!issues[0].is_error());
+    }
+}
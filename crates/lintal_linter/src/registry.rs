@@ -2,7 +2,7 @@
 
 use std::collections::HashMap;
 
-use crate::Rule;
+use crate::{Rule, RuleMetadata};
 
 /// Properties from a checkstyle module configuration.
 pub type Properties<'a> = HashMap<&'a str, &'a str>;
@@ -12,6 +12,10 @@ pub trait FromConfig: Rule + Sized {
     /// The checkstyle module name this rule corresponds to.
     const MODULE_NAME: &'static str;
 
+    /// What the rule checks, the properties it accepts and whether it can
+    /// fix violations.
+    const METADATA: RuleMetadata;
+
     /// Create a rule instance from config properties.
     /// Properties are key-value pairs from the checkstyle module.
     fn from_config(properties: &Properties) -> Self;
@@ -23,6 +27,7 @@ type RuleFactory = fn(&Properties) -> Box<dyn Rule>;
 /// Registry mapping checkstyle module names to rule factories.
 pub struct RuleRegistry {
     factories: HashMap<&'static str, RuleFactory>,
+    metadata: HashMap<&'static str, RuleMetadata>,
 }
 
 impl RuleRegistry {
@@ -30,6 +35,7 @@ impl RuleRegistry {
     pub fn new() -> Self {
         Self {
             factories: HashMap::new(),
+            metadata: HashMap::new(),
         }
     }
 
@@ -44,6 +50,7 @@ impl RuleRegistry {
     pub fn register<R: FromConfig + 'static>(&mut self) {
         self.factories
             .insert(R::MODULE_NAME, |props| Box::new(R::from_config(props)));
+        self.metadata.insert(R::MODULE_NAME, R::METADATA);
     }
 
     /// Register all built-in rules.
@@ -130,6 +137,22 @@ impl RuleRegistry {
     pub fn module_names(&self) -> impl Iterator<Item = &'static str> + '_ {
         self.factories.keys().copied()
     }
+
+    /// Get the metadata of a registered rule.
+    pub fn metadata(&self, module_name: &str) -> Option<&RuleMetadata> {
+        self.metadata.get(module_name)
+    }
+
+    /// Get the metadata of all registered rules, sorted by module name.
+    pub fn rules(&self) -> Vec<(&'static str, &RuleMetadata)> {
+        let mut rules: Vec<_> = self
+            .metadata
+            .iter()
+            .map(|(name, metadata)| (*name, metadata))
+            .collect();
+        rules.sort_by_key(|(name, _)| *name);
+        rules
+    }
 }
 
 impl Default for RuleRegistry {
@@ -141,6 +164,10 @@ impl Default for RuleRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::CheckContext;
+    use lintal_diagnostics::FixAvailability;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
 
     #[test]
     fn test_registry_creates_whitespace_around() {
@@ -173,4 +200,48 @@ mod tests {
 
         assert!(rule.is_none());
     }
+
+    #[test]
+    fn test_every_example_violates_its_rule() {
+        let registry = RuleRegistry::builtin();
+        let mut parser = JavaParser::new();
+
+        for (name, metadata) in registry.rules() {
+            let source = metadata.example;
+            let rule = registry.create_rule(name, &HashMap::new()).unwrap();
+            let tree = parser.parse(source).unwrap().tree;
+            let ctx = CheckContext::new(source);
+            let diagnostics: Vec<_> = TreeWalker::new(tree.root_node(), source)
+                .flat_map(|node| rule.check(&ctx, &node))
+                .collect();
+            assert!(!diagnostics.is_empty(), "{name}: example has no violations");
+            match metadata.fix {
+                FixAvailability::None => assert!(
+                    diagnostics.iter().all(|d| d.fix.is_none()),
+                    "{name}: fixes are not declared"
+                ),
+                FixAvailability::Always => assert!(
+                    diagnostics.iter().all(|d| d.fix.is_some()),
+                    "{name}: fixes are declared for every violation"
+                ),
+                FixAvailability::Sometimes => {}
+            }
+        }
+    }
+
+    #[test]
+    fn test_property_defaults_are_accepted_values() {
+        let registry = RuleRegistry::builtin();
+
+        for (name, metadata) in registry.rules() {
+            for property in metadata.properties {
+                if let Err(reason) = property.kind.check(property.default) {
+                    panic!(
+                        "{name}.{}: default {} is not accepted: {reason}",
+                        property.name, property.default
+                    );
+                }
+            }
+        }
+    }
 }
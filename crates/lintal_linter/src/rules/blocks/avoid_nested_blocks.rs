@@ -7,7 +7,10 @@ use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::TextRange;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Configuration for AvoidNestedBlocks rule.
 #[derive(Debug, Clone, Default)]
@@ -21,6 +24,28 @@ const RELEVANT_KINDS: &[&str] = &["block"];
 impl FromConfig for AvoidNestedBlocks {
     const MODULE_NAME: &'static str = "AvoidNestedBlocks";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Finds nested blocks, i.e. blocks used freely in the code.",
+        category: RuleCategory::Blocks,
+        properties: &[PropertySpec::new(
+            "allowInSwitchCase",
+            PropertyKind::Boolean,
+            "false",
+            "Allow a nested block as the whole body of a case group.",
+        )],
+        fix: FixAvailability::of(&[NestedBlock::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void run() {
+        {
+            int x = 1;
+        }
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let allow_in_switch_case = properties
             .get("allowInSwitchCase")
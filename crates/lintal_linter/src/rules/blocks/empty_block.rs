@@ -7,7 +7,10 @@ use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::TextRange;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Block option for empty block checking.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -44,6 +47,27 @@ const RELEVANT_KINDS: &[&str] = &[
 impl FromConfig for EmptyBlock {
     const MODULE_NAME: &'static str = "EmptyBlock";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks for empty blocks.",
+        category: RuleCategory::Blocks,
+        properties: &[
+            PropertySpec::new("option", PropertyKind::Enum(&["statement", "text"]), "statement", "Whether a block must contain a statement or only some text, such as a comment."),
+            PropertySpec::new("tokens", PropertyKind::Tokens(&["LITERAL_WHILE", "LITERAL_TRY", "LITERAL_FINALLY", "LITERAL_DO", "LITERAL_IF", "LITERAL_ELSE", "LITERAL_FOR", "INSTANCE_INIT", "STATIC_INIT", "LITERAL_SWITCH", "LITERAL_SYNCHRONIZED", "LITERAL_CATCH", "ARRAY_INIT", "LITERAL_DEFAULT", "LITERAL_CASE"]), "LITERAL_WHILE, LITERAL_TRY, LITERAL_FINALLY, LITERAL_DO, LITERAL_IF, LITERAL_ELSE, LITERAL_FOR, INSTANCE_INIT, STATIC_INIT, LITERAL_SWITCH, LITERAL_SYNCHRONIZED", "Blocks to check.").unsupported(),
+        ],
+        fix: FixAvailability::of(&[EmptyBlockNoStatement::FIX_AVAILABILITY, EmptyBlockNoText::FIX_AVAILABILITY]),
+        compatibility: &[
+            "The default tokens are always checked; `tokens` is ignored.",
+        ],
+        example: "\
+class Example {
+    void run(boolean ready) {
+        if (ready) {
+        }
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let option = properties
             .get("option")
@@ -7,7 +7,10 @@ use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Configuration for EmptyCatchBlock rule.
 #[derive(Debug, Clone)]
@@ -32,6 +35,37 @@ impl Default for EmptyCatchBlock {
 impl FromConfig for EmptyCatchBlock {
     const MODULE_NAME: &'static str = "EmptyCatchBlock";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks for empty catch blocks.",
+        category: RuleCategory::Blocks,
+        properties: &[
+            PropertySpec::new(
+                "exceptionVariableName",
+                PropertyKind::Regex,
+                "^$",
+                "Allow empty catch blocks whose exception variable name matches.",
+            ),
+            PropertySpec::new(
+                "commentFormat",
+                PropertyKind::Regex,
+                ".*",
+                "Allow empty catch blocks whose first comment matches.",
+            ),
+        ],
+        fix: FixAvailability::of(&[EmptyCatchBlockViolation::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void run() {
+        try {
+            run();
+        } catch (RuntimeException e) {
+        }
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let exception_variable_name = properties
             .get("exceptionVariableName")
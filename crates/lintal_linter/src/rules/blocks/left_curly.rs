@@ -7,7 +7,10 @@ use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 use super::common::are_on_same_line;
 
@@ -77,6 +80,27 @@ impl Default for LeftCurly {
 impl FromConfig for LeftCurly {
     const MODULE_NAME: &'static str = "LeftCurly";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks the placement of left curly braces ('{') for code blocks.",
+        category: RuleCategory::Blocks,
+        properties: &[
+            PropertySpec::new("option", PropertyKind::Enum(&["eol", "nl", "nlow"]), "eol", "Where the brace goes: at the end of the line, on a new line, or on a new line only when the statement wraps."),
+            PropertySpec::new("ignoreEnums", PropertyKind::Boolean, "true", "Ignore braces of enum constant bodies on the same line."),
+            PropertySpec::new("tokens", PropertyKind::Tokens(&["ANNOTATION_DEF", "CLASS_DEF", "CTOR_DEF", "ENUM_CONSTANT_DEF", "ENUM_DEF", "INTERFACE_DEF", "LAMBDA", "LITERAL_CASE", "LITERAL_CATCH", "LITERAL_DEFAULT", "LITERAL_DO", "LITERAL_ELSE", "LITERAL_FINALLY", "LITERAL_FOR", "LITERAL_IF", "LITERAL_SWITCH", "LITERAL_SYNCHRONIZED", "LITERAL_TRY", "LITERAL_WHILE", "METHOD_DEF", "OBJBLOCK", "STATIC_INIT", "RECORD_DEF", "COMPACT_CTOR_DEF"]), "ANNOTATION_DEF, CLASS_DEF, CTOR_DEF, ENUM_CONSTANT_DEF, ENUM_DEF, INTERFACE_DEF, LAMBDA, LITERAL_CASE, LITERAL_CATCH, LITERAL_DEFAULT, LITERAL_DO, LITERAL_ELSE, LITERAL_FINALLY, LITERAL_FOR, LITERAL_IF, LITERAL_SWITCH, LITERAL_SYNCHRONIZED, LITERAL_TRY, LITERAL_WHILE, METHOD_DEF, OBJBLOCK, STATIC_INIT, RECORD_DEF, COMPACT_CTOR_DEF", "Constructs to check.").unsupported(),
+        ],
+        fix: FixAvailability::of(&[LeftCurlyShouldBeOnNewLine::FIX_AVAILABILITY, LeftCurlyShouldBeOnPreviousLine::FIX_AVAILABILITY, LeftCurlyShouldHaveLineBreakAfter::FIX_AVAILABILITY]),
+        compatibility: &[
+            "Type, method and statement braces are always checked; `tokens` is ignored.",
+        ],
+        example: "\
+class Example
+{
+    void run() {
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let option = properties
             .get("option")
@@ -112,6 +136,10 @@ impl Violation for LeftCurlyShouldBeOnNewLine {
     fn message(&self) -> String {
         format!("'{{' at column {} should be on a new line", self.column)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Move '{' to a new line".to_string())
+    }
 }
 
 /// Violation for left curly should be on the previous line.
@@ -129,6 +157,10 @@ impl Violation for LeftCurlyShouldBeOnPreviousLine {
             self.column
         )
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Move '{' to the previous line".to_string())
+    }
 }
 
 /// Violation for left curly should have line break after.
@@ -146,6 +178,10 @@ impl Violation for LeftCurlyShouldHaveLineBreakAfter {
             self.column
         )
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Insert line break after '{'".to_string())
+    }
 }
 
 impl Rule for LeftCurly {
@@ -6,7 +6,10 @@
 use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Configuration for NeedBraces rule.
 #[derive(Debug, Clone, Default)]
@@ -26,6 +29,53 @@ const RELEVANT_KINDS: &[&str] = &[
 impl FromConfig for NeedBraces {
     const MODULE_NAME: &'static str = "NeedBraces";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks for braces around code blocks.",
+        category: RuleCategory::Blocks,
+        properties: &[
+            PropertySpec::new(
+                "allowSingleLineStatement",
+                PropertyKind::Boolean,
+                "false",
+                "Allow a statement without braces on the same line as its condition.",
+            ),
+            PropertySpec::new(
+                "allowEmptyLoopBody",
+                PropertyKind::Boolean,
+                "false",
+                "Allow loops with an empty body and no braces.",
+            ),
+            PropertySpec::new(
+                "tokens",
+                PropertyKind::Tokens(&[
+                    "LITERAL_DO",
+                    "LITERAL_ELSE",
+                    "LITERAL_FOR",
+                    "LITERAL_IF",
+                    "LITERAL_WHILE",
+                    "LITERAL_CASE",
+                    "LITERAL_DEFAULT",
+                    "LAMBDA",
+                ]),
+                "LITERAL_DO, LITERAL_ELSE, LITERAL_FOR, LITERAL_IF, LITERAL_WHILE",
+                "Statements to check.",
+            )
+            .unsupported(),
+        ],
+        fix: FixAvailability::of(&[NeedBracesViolation::FIX_AVAILABILITY]),
+        compatibility: &[
+            "`if`, `else`, `for`, `while` and `do` are always checked; `tokens` is ignored.",
+        ],
+        example: "\
+class Example {
+    void run(boolean ready) {
+        if (ready)
+            run(false);
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let allow_single_line_statement = properties
             .get("allowSingleLineStatement")
@@ -7,7 +7,10 @@ use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 use super::common::are_on_same_line;
 
@@ -134,6 +137,64 @@ impl Default for RightCurly {
 impl FromConfig for RightCurly {
     const MODULE_NAME: &'static str = "RightCurly";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks the placement of right curly braces ('}') for code blocks.",
+        category: RuleCategory::Blocks,
+        properties: &[
+            PropertySpec::new(
+                "option",
+                PropertyKind::Enum(&["same", "alone", "alone_or_singleline"]),
+                "same",
+                "Whether the brace shares a line with the next part of the statement, stands alone, or may also close a single-line block.",
+            ),
+            PropertySpec::new(
+                "tokens",
+                PropertyKind::Tokens(&[
+                    "LITERAL_TRY",
+                    "LITERAL_CATCH",
+                    "LITERAL_FINALLY",
+                    "LITERAL_IF",
+                    "LITERAL_ELSE",
+                    "CLASS_DEF",
+                    "METHOD_DEF",
+                    "CTOR_DEF",
+                    "LITERAL_FOR",
+                    "LITERAL_WHILE",
+                    "LITERAL_DO",
+                    "STATIC_INIT",
+                    "INSTANCE_INIT",
+                    "ANNOTATION_DEF",
+                    "ENUM_DEF",
+                    "INTERFACE_DEF",
+                    "RECORD_DEF",
+                    "COMPACT_CTOR_DEF",
+                    "LITERAL_SWITCH",
+                    "LITERAL_CASE",
+                ]),
+                "LITERAL_TRY, LITERAL_CATCH, LITERAL_FINALLY, LITERAL_IF, LITERAL_ELSE",
+                "Constructs to check.",
+            ),
+        ],
+        fix: FixAvailability::of(&[
+            RightCurlyShouldBeSameLine::FIX_AVAILABILITY,
+            RightCurlyShouldBeAlone::FIX_AVAILABILITY,
+            RightCurlyShouldHaveLineBreakBefore::FIX_AVAILABILITY,
+        ]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void run(boolean ready) {
+        if (ready) {
+            run(false);
+        }
+        else {
+            run(true);
+        }
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let option = properties
             .get("option")
@@ -181,6 +242,10 @@ impl Violation for RightCurlyShouldBeSameLine {
             self.column
         )
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Join '}' with the next part".to_string())
+    }
 }
 
 /// Violation for right curly should be alone on line.
@@ -195,6 +260,10 @@ impl Violation for RightCurlyShouldBeAlone {
     fn message(&self) -> String {
         format!("'}}' at column {} should be alone on a line", self.column)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Move '}' to its own line".to_string())
+    }
 }
 
 /// Violation for right curly should have line break before.
@@ -212,6 +281,10 @@ impl Violation for RightCurlyShouldHaveLineBreakBefore {
             self.column
         )
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Insert line break before '}'".to_string())
+    }
 }
 
 impl Rule for RightCurly {
@@ -8,7 +8,10 @@
 use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Violation: default should be last label in the switch.
 #[derive(Debug, Clone)]
@@ -47,6 +50,34 @@ const RELEVANT_KINDS: &[&str] = &["switch_block"];
 impl FromConfig for DefaultComesLast {
     const MODULE_NAME: &'static str = "DefaultComesLast";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that the `default` label is the last label in a switch block.",
+        category: RuleCategory::Coding,
+        properties: &[PropertySpec::new(
+            "skipIfLastAndSharedWithCase",
+            PropertyKind::Boolean,
+            "false",
+            "Allow `default` before other labels when it shares a group with the last case.",
+        )],
+        fix: FixAvailability::of(&[
+            DefaultComesLastViolation::FIX_AVAILABILITY,
+            DefaultComesLastInGroupViolation::FIX_AVAILABILITY,
+        ]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void run(int x) {
+        switch (x) {
+            default:
+                break;
+            case 1:
+                break;
+        }
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let skip_if_last_and_shared_with_case = properties
             .get("skipIfLastAndSharedWithCase")
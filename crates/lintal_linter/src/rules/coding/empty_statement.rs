@@ -7,7 +7,7 @@
 use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{CheckContext, FromConfig, Properties, Rule, RuleCategory, RuleMetadata};
 
 /// Violation: empty statement detected.
 #[derive(Debug, Clone)]
@@ -19,6 +19,10 @@ impl Violation for EmptyStatementViolation {
     fn message(&self) -> String {
         "Empty statement.".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Remove empty statement".to_string())
+    }
 }
 
 /// Configuration for EmptyStatement rule.
@@ -37,6 +41,21 @@ const RELEVANT_KINDS: &[&str] = &[
 impl FromConfig for EmptyStatement {
     const MODULE_NAME: &'static str = "EmptyStatement";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Detects empty statements (lone semicolons).",
+        category: RuleCategory::Coding,
+        properties: &[],
+        fix: FixAvailability::of(&[EmptyStatementViolation::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void run(boolean ready) {
+        if (ready);
+    }
+}
+",
+    };
+
     fn from_config(_properties: &Properties) -> Self {
         Self
     }
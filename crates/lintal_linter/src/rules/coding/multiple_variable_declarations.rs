@@ -7,7 +7,7 @@
 use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{CheckContext, FromConfig, Properties, Rule, RuleCategory, RuleMetadata};
 
 /// Violation: comma-separated variables in single declaration.
 #[derive(Debug, Clone)]
@@ -19,6 +19,10 @@ impl Violation for MultipleInStatementViolation {
     fn message(&self) -> String {
         "Each variable declaration must be in its own statement.".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Split into separate declarations".to_string())
+    }
 }
 
 /// Violation: multiple declarations on same line.
@@ -31,6 +35,10 @@ impl Violation for MultipleOnLineViolation {
     fn message(&self) -> String {
         "Only one variable definition per line allowed.".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Split into separate declarations".to_string())
+    }
 }
 
 /// Configuration for MultipleVariableDeclarations rule.
@@ -47,6 +55,22 @@ const RELEVANT_KINDS: &[&str] = &[
 impl FromConfig for MultipleVariableDeclarations {
     const MODULE_NAME: &'static str = "MultipleVariableDeclarations";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that each variable is declared in its own statement and on its own line.",
+        category: RuleCategory::Coding,
+        properties: &[],
+        fix: FixAvailability::of(&[
+            MultipleInStatementViolation::FIX_AVAILABILITY,
+            MultipleOnLineViolation::FIX_AVAILABILITY,
+        ]),
+        compatibility: &[],
+        example: "\
+class Example {
+    int width, height;
+}
+",
+    };
+
     fn from_config(_properties: &Properties) -> Self {
         Self
     }
@@ -7,7 +7,10 @@
 use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Violation: multiple statements on same line.
 #[derive(Debug, Clone)]
@@ -19,6 +22,10 @@ impl Violation for OneStatementPerLineViolation {
     fn message(&self) -> String {
         "Only one statement per line allowed.".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Move statement to its own line".to_string())
+    }
 }
 
 /// Configuration for OneStatementPerLine rule.
@@ -33,6 +40,26 @@ const RELEVANT_KINDS: &[&str] = &["block", "class_body"];
 impl FromConfig for OneStatementPerLine {
     const MODULE_NAME: &'static str = "OneStatementPerLine";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that there is only one statement per line.",
+        category: RuleCategory::Coding,
+        properties: &[PropertySpec::new(
+            "treatTryResourcesAsStatement",
+            PropertyKind::Boolean,
+            "false",
+            "Count try-with-resources resources as statements.",
+        )],
+        fix: FixAvailability::of(&[OneStatementPerLineViolation::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void run() {
+        int x = 1; int y = 2;
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let treat_try_resources_as_statement = properties
             .get("treatTryResourcesAsStatement")
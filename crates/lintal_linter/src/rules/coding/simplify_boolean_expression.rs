@@ -7,7 +7,7 @@
 use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{CheckContext, FromConfig, Properties, Rule, RuleCategory, RuleMetadata};
 
 /// Violation: boolean expression can be simplified.
 #[derive(Debug, Clone)]
@@ -24,6 +24,10 @@ impl Violation for SimplifyBooleanExpressionViolation {
             self.suggestion
         )
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Replace with '{}'", self.suggestion))
+    }
 }
 
 /// Violation: expression is always true or false.
@@ -56,6 +60,24 @@ const RELEVANT_KINDS: &[&str] = &[
 impl FromConfig for SimplifyBooleanExpression {
     const MODULE_NAME: &'static str = "SimplifyBooleanExpression";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks for boolean expressions that can be simplified.",
+        category: RuleCategory::Coding,
+        properties: &[],
+        fix: FixAvailability::of(&[
+            SimplifyBooleanExpressionViolation::FIX_AVAILABILITY,
+            AlwaysTrueOrFalseViolation::FIX_AVAILABILITY,
+        ]),
+        compatibility: &[],
+        example: "\
+class Example {
+    boolean run(boolean ready) {
+        return ready == true;
+    }
+}
+",
+    };
+
     fn from_config(_properties: &Properties) -> Self {
         Self
     }
@@ -7,18 +7,22 @@
 use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{CheckContext, FromConfig, Properties, Rule, RuleCategory, RuleMetadata};
 
 /// Violation: conditional logic can be removed.
 #[derive(Debug, Clone)]
 pub struct SimplifyBooleanReturnViolation;
 
 impl Violation for SimplifyBooleanReturnViolation {
-    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
 
     fn message(&self) -> String {
         "Conditional logic can be removed.".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Return the condition directly".to_string())
+    }
 }
 
 /// Configuration for SimplifyBooleanReturn rule.
@@ -30,6 +34,25 @@ const RELEVANT_KINDS: &[&str] = &["if_statement"];
 impl FromConfig for SimplifyBooleanReturn {
     const MODULE_NAME: &'static str = "SimplifyBooleanReturn";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks for overly complicated boolean return statements.",
+        category: RuleCategory::Coding,
+        properties: &[],
+        fix: FixAvailability::of(&[SimplifyBooleanReturnViolation::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    boolean run(boolean ready) {
+        if (ready) {
+            return true;
+        } else {
+            return false;
+        }
+    }
+}
+",
+    };
+
     fn from_config(_properties: &Properties) -> Self {
         Self
     }
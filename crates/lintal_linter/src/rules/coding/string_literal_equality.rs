@@ -9,7 +9,7 @@ use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use tree_sitter::Node;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{CheckContext, FromConfig, Properties, Rule, RuleCategory, RuleMetadata};
 
 /// Violation: string literals should be compared with equals(), not ==.
 #[derive(Debug, Clone)]
@@ -21,6 +21,10 @@ impl Violation for StringLiteralEqualityViolation {
     fn message(&self) -> String {
         "Literal Strings should be compared using equals(), not '=='.".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Compare using equals()".to_string())
+    }
 }
 
 /// Configuration for StringLiteralEquality rule.
@@ -32,6 +36,21 @@ const RELEVANT_KINDS: &[&str] = &["binary_expression"];
 impl FromConfig for StringLiteralEquality {
     const MODULE_NAME: &'static str = "StringLiteralEquality";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks for string literals compared with `==` or `!=` instead of `equals()`.",
+        category: RuleCategory::Coding,
+        properties: &[],
+        fix: FixAvailability::of(&[StringLiteralEqualityViolation::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    boolean run(String name) {
+        return name == \"lintal\";
+    }
+}
+",
+    };
+
     fn from_config(_properties: &Properties) -> Self {
         Self
     }
@@ -14,7 +14,7 @@ use lintal_java_cst::CstNode;
 use lintal_source_file::LineIndex;
 use lintal_text_size::{TextRange, TextSize};
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{CheckContext, FromConfig, Properties, Rule, RuleCategory, RuleMetadata};
 
 use super::common::{ImportInfo, collect_imports, get_package_name};
 
@@ -28,6 +28,10 @@ impl Violation for SamePackageImport {
     fn message(&self) -> String {
         "Redundant import from the same package.".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Remove redundant import".to_string())
+    }
 }
 
 /// Violation: import from java.lang package.
@@ -40,6 +44,10 @@ impl Violation for JavaLangImport {
     fn message(&self) -> String {
         "Redundant import from the java.lang package.".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Remove redundant import".to_string())
+    }
 }
 
 /// Violation: duplicate import.
@@ -54,6 +62,10 @@ impl Violation for DuplicateImport {
     fn message(&self) -> String {
         format!("Duplicate import to line {}.", self.first_line)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Remove duplicate import".to_string())
+    }
 }
 
 /// Configuration for RedundantImport rule.
@@ -65,6 +77,25 @@ const RELEVANT_KINDS: &[&str] = &["program"];
 impl FromConfig for RedundantImport {
     const MODULE_NAME: &'static str = "RedundantImport";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Detects duplicate imports, imports from java.lang and imports from the same package.",
+        category: RuleCategory::Imports,
+        properties: &[],
+        fix: FixAvailability::of(&[
+            SamePackageImport::FIX_AVAILABILITY,
+            JavaLangImport::FIX_AVAILABILITY,
+            DuplicateImport::FIX_AVAILABILITY,
+        ]),
+        compatibility: &[],
+        example: "\
+import java.lang.String;
+
+class Example {
+    String name;
+}
+",
+    };
+
     fn from_config(_properties: &Properties) -> Self {
         Self
     }
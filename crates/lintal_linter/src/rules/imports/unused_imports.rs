@@ -9,7 +9,10 @@ use lintal_java_cst::CstNode;
 use lintal_source_file::LineIndex;
 use lintal_text_size::{TextRange, TextSize};
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 use super::common::{ImportInfo, collect_imports, collect_javadoc_references, collect_type_usages};
 
@@ -25,6 +28,10 @@ impl Violation for UnusedImportViolation {
     fn message(&self) -> String {
         format!("Unused import - {}.", self.import_path)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Remove unused import".to_string())
+    }
 }
 
 /// Configuration for UnusedImports rule.
@@ -47,6 +54,25 @@ impl Default for UnusedImports {
 impl FromConfig for UnusedImports {
     const MODULE_NAME: &'static str = "UnusedImports";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Detects imports that are never used.",
+        category: RuleCategory::Imports,
+        properties: &[PropertySpec::new(
+            "processJavadoc",
+            PropertyKind::Boolean,
+            "true",
+            "Count references in Javadoc as uses.",
+        )],
+        fix: FixAvailability::of(&[UnusedImportViolation::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+import java.util.List;
+
+class Example {
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let process_javadoc = properties
             .get("processJavadoc")
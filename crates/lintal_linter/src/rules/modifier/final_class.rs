@@ -7,7 +7,7 @@
 use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{CheckContext, FromConfig, Properties, Rule, RuleCategory, RuleMetadata};
 
 use super::common::has_modifier;
 
@@ -23,6 +23,10 @@ impl Violation for ClassShouldBeFinalViolation {
     fn message(&self) -> String {
         format!("Class {} should be declared as final.", self.class_name)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Declare {} final", self.class_name))
+    }
 }
 
 /// Configuration for FinalClass rule.
@@ -34,6 +38,20 @@ const RELEVANT_KINDS: &[&str] = &["class_declaration"];
 impl FromConfig for FinalClass {
     const MODULE_NAME: &'static str = "FinalClass";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that classes with only private constructors are declared final.",
+        category: RuleCategory::Design,
+        properties: &[],
+        fix: FixAvailability::of(&[ClassShouldBeFinalViolation::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    private Example() {
+    }
+}
+",
+    };
+
     fn from_config(_properties: &Properties) -> Self {
         Self
     }
@@ -2,7 +2,9 @@
 //!
 //! This is a complex stateful rule that tracks variable declarations and assignments.
 
-use crate::{CheckContext, FromConfig, Rule};
+use crate::{
+    CheckContext, FromConfig, PropertyKind, PropertySpec, Rule, RuleCategory, RuleMetadata,
+};
 use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::TextRange;
@@ -34,11 +36,51 @@ impl Violation for VariableShouldBeFinal {
     fn message(&self) -> String {
         format!("Variable '{}' should be declared final.", self.var_name)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Declare '{}' final", self.var_name))
+    }
 }
 
 impl FromConfig for FinalLocalVariable {
     const MODULE_NAME: &'static str = "FinalLocalVariable";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that local variables that are never reassigned are declared final.",
+        category: RuleCategory::Coding,
+        properties: &[
+            PropertySpec::new(
+                "validateEnhancedForLoopVariable",
+                PropertyKind::Boolean,
+                "false",
+                "Also check the variable of enhanced for loops.",
+            ),
+            PropertySpec::new(
+                "validateUnnamedVariables",
+                PropertyKind::Boolean,
+                "false",
+                "Also check unnamed variables (`_`).",
+            ),
+            PropertySpec::new(
+                "tokens",
+                PropertyKind::Tokens(&["VARIABLE_DEF", "PARAMETER_DEF"]),
+                "VARIABLE_DEF",
+                "Declarations to check.",
+            )
+            .unsupported(),
+        ],
+        fix: FixAvailability::of(&[VariableShouldBeFinal::FIX_AVAILABILITY]),
+        compatibility: &["Only local variables are checked; `tokens` is ignored."],
+        example: "\
+class Example {
+    int run() {
+        int x = 1;
+        return x;
+    }
+}
+",
+    };
+
     fn from_config(properties: &HashMap<&str, &str>) -> Self {
         let validate_enhanced_for_loop_variable = properties
             .get("validateEnhancedForLoopVariable")
@@ -7,7 +7,10 @@ use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use std::collections::HashSet;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Configuration for FinalParameters rule.
 #[derive(Debug, Clone)]
@@ -53,6 +56,44 @@ impl Default for FinalParameters {
 impl FromConfig for FinalParameters {
     const MODULE_NAME: &'static str = "FinalParameters";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that parameters of methods, constructors, catch and for-each blocks are final.",
+        category: RuleCategory::Misc,
+        properties: &[
+            PropertySpec::new(
+                "tokens",
+                PropertyKind::Tokens(&[
+                    "METHOD_DEF",
+                    "CTOR_DEF",
+                    "LITERAL_CATCH",
+                    "FOR_EACH_CLAUSE",
+                ]),
+                "METHOD_DEF, CTOR_DEF",
+                "Parameters to check.",
+            ),
+            PropertySpec::new(
+                "ignorePrimitiveTypes",
+                PropertyKind::Boolean,
+                "false",
+                "Ignore parameters of primitive types.",
+            ),
+            PropertySpec::new(
+                "ignoreUnnamedParameters",
+                PropertyKind::Boolean,
+                "true",
+                "Ignore unnamed parameters (`_`).",
+            ),
+        ],
+        fix: FixAvailability::of(&[ParameterShouldBeFinal::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void run(String name) {
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let tokens = if let Some(tokens_str) = properties.get("tokens") {
             parse_tokens(tokens_str)
@@ -118,6 +159,10 @@ impl Violation for ParameterShouldBeFinal {
     fn message(&self) -> String {
         format!("Parameter {} should be final.", self.param_name)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Declare {} final", self.param_name))
+    }
 }
 
 impl Rule for FinalParameters {
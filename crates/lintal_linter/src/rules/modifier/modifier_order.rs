@@ -6,7 +6,7 @@
 use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{CheckContext, FromConfig, Properties, Rule, RuleCategory, RuleMetadata};
 
 /// Configuration for ModifierOrder rule.
 #[derive(Debug, Clone)]
@@ -23,6 +23,22 @@ impl Default for ModifierOrder {
 impl FromConfig for ModifierOrder {
     const MODULE_NAME: &'static str = "ModifierOrder";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that modifiers are in the order suggested by the Java Language Specification.",
+        category: RuleCategory::Modifier,
+        properties: &[],
+        fix: FixAvailability::of(&[
+            ModifierOutOfOrder::FIX_AVAILABILITY,
+            AnnotationMustPrecedeModifiers::FIX_AVAILABILITY,
+        ]),
+        compatibility: &[],
+        example: "\
+class Example {
+    final static int SIZE = 1;
+}
+",
+    };
+
     fn from_config(_properties: &Properties) -> Self {
         Self
     }
@@ -44,6 +60,10 @@ impl Violation for ModifierOutOfOrder {
             self.modifier
         )
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Reorder modifiers".to_string())
+    }
 }
 
 /// Violation for annotation must come before other modifiers.
@@ -62,6 +82,10 @@ impl Violation for AnnotationMustPrecedeModifiers {
             self.annotation
         )
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Move '{}' before the modifiers", self.annotation))
+    }
 }
 
 impl Rule for ModifierOrder {
@@ -7,7 +7,10 @@ use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::TextRange;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Configuration for RedundantModifier rule.
 #[derive(Debug, Clone)]
@@ -44,6 +47,24 @@ impl Default for RedundantModifier {
 impl FromConfig for RedundantModifier {
     const MODULE_NAME: &'static str = "RedundantModifier";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks for redundant modifiers.",
+        category: RuleCategory::Modifier,
+        properties: &[
+            PropertySpec::new("jdkVersion", PropertyKind::String, "22", "The Java version the sources target, as `8` or `1.8`."),
+            PropertySpec::new("tokens", PropertyKind::Tokens(&["METHOD_DEF", "VARIABLE_DEF", "ANNOTATION_FIELD_DEF", "INTERFACE_DEF", "CTOR_DEF", "CLASS_DEF", "ENUM_DEF", "RESOURCE", "ANNOTATION_DEF", "RECORD_DEF", "PATTERN_VARIABLE_DEF", "LITERAL_CATCH", "LAMBDA"]), "METHOD_DEF, VARIABLE_DEF, ANNOTATION_FIELD_DEF, INTERFACE_DEF, CTOR_DEF, CLASS_DEF, ENUM_DEF, RESOURCE, ANNOTATION_DEF, RECORD_DEF, PATTERN_VARIABLE_DEF, LITERAL_CATCH, LAMBDA", "Declarations to check.").unsupported(),
+        ],
+        fix: FixAvailability::of(&[RedundantModifierViolation::FIX_AVAILABILITY]),
+        compatibility: &[
+            "All declarations are checked; `tokens` is ignored.",
+        ],
+        example: "\
+interface Example {
+    public void run();
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let jdk_version = properties
             .get("jdkVersion")
@@ -75,6 +96,10 @@ impl Violation for RedundantModifierViolation {
     fn message(&self) -> String {
         format!("Redundant '{}' modifier.", self.modifier)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Remove '{}' modifier", self.modifier))
+    }
 }
 
 impl Rule for RedundantModifier {
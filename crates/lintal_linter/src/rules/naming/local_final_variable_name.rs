@@ -7,7 +7,10 @@ use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Default pattern for local final variable names: camelCase starting with lowercase, or just underscore
 const DEFAULT_FORMAT: &str = r"^([a-z][a-zA-Z0-9]*|_)$";
@@ -76,6 +79,34 @@ impl Default for LocalFinalVariableName {
 impl FromConfig for LocalFinalVariableName {
     const MODULE_NAME: &'static str = "LocalFinalVariableName";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that final local variable names match a pattern.",
+        category: RuleCategory::Naming,
+        properties: &[
+            PropertySpec::new(
+                "format",
+                PropertyKind::Regex,
+                DEFAULT_FORMAT,
+                "The pattern names must match.",
+            ),
+            PropertySpec::new(
+                "tokens",
+                PropertyKind::Tokens(&["VARIABLE_DEF", "PARAMETER_DEF", "RESOURCE"]),
+                "VARIABLE_DEF, PARAMETER_DEF, RESOURCE",
+                "Declarations to check.",
+            ),
+        ],
+        fix: FixAvailability::of(&[LocalFinalVariableNameInvalid::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void run() {
+        final int Count = 1;
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let format_str = properties
             .get("format")
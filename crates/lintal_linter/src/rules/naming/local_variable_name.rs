@@ -7,7 +7,10 @@ use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Default pattern for local variable names: camelCase starting with lowercase, or just underscore
 const DEFAULT_FORMAT: &str = r"^([a-z][a-zA-Z0-9]*|_)$";
@@ -39,6 +42,34 @@ impl Default for LocalVariableName {
 impl FromConfig for LocalVariableName {
     const MODULE_NAME: &'static str = "LocalVariableName";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that local variable names match a pattern.",
+        category: RuleCategory::Naming,
+        properties: &[
+            PropertySpec::new(
+                "format",
+                PropertyKind::Regex,
+                DEFAULT_FORMAT,
+                "The pattern names must match.",
+            ),
+            PropertySpec::new(
+                "allowOneCharVarInForLoop",
+                PropertyKind::Boolean,
+                "false",
+                "Allow one-character variable names in for loop initializers.",
+            ),
+        ],
+        fix: FixAvailability::of(&[LocalVariableNameInvalid::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void run() {
+        int Count = 1;
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let format_str = properties
             .get("format")
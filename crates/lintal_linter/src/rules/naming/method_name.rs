@@ -8,7 +8,10 @@ use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Default pattern for method names: camelCase starting with lowercase
 const DEFAULT_FORMAT: &str = r"^[a-z][a-zA-Z0-9]*$";
@@ -52,6 +55,60 @@ impl Default for MethodName {
 impl FromConfig for MethodName {
     const MODULE_NAME: &'static str = "MethodName";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that method names match a pattern and differ from the enclosing class name.",
+        category: RuleCategory::Naming,
+        properties: &[
+            PropertySpec::new(
+                "format",
+                PropertyKind::Regex,
+                DEFAULT_FORMAT,
+                "The pattern names must match.",
+            ),
+            PropertySpec::new(
+                "allowClassName",
+                PropertyKind::Boolean,
+                "false",
+                "Allow methods named like the enclosing class.",
+            ),
+            PropertySpec::new(
+                "applyToPublic",
+                PropertyKind::Boolean,
+                "true",
+                "Check public members.",
+            ),
+            PropertySpec::new(
+                "applyToProtected",
+                PropertyKind::Boolean,
+                "true",
+                "Check protected members.",
+            ),
+            PropertySpec::new(
+                "applyToPackage",
+                PropertyKind::Boolean,
+                "true",
+                "Check package-private members.",
+            ),
+            PropertySpec::new(
+                "applyToPrivate",
+                PropertyKind::Boolean,
+                "true",
+                "Check private members.",
+            ),
+        ],
+        fix: FixAvailability::of(&[
+            MethodNameInvalid::FIX_AVAILABILITY,
+            MethodNameEqualsClassName::FIX_AVAILABILITY,
+        ]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void Run() {
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let format_str = properties
             .get("format")
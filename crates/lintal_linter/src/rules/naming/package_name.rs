@@ -7,7 +7,10 @@ use lintal_java_cst::CstNode;
 use lintal_text_size::TextRange;
 use regex::Regex;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Default pattern for package names: starts with lowercase, followed by dot-separated segments
 const DEFAULT_FORMAT: &str = r"^[a-z]+(\.[a-zA-Z_]\w*)*$";
@@ -36,6 +39,25 @@ impl Default for PackageName {
 impl FromConfig for PackageName {
     const MODULE_NAME: &'static str = "PackageName";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that package names match a pattern.",
+        category: RuleCategory::Naming,
+        properties: &[PropertySpec::new(
+            "format",
+            PropertyKind::Regex,
+            DEFAULT_FORMAT,
+            "The pattern names must match.",
+        )],
+        fix: FixAvailability::of(&[PackageNameInvalid::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+package Com.example;
+
+class Example {
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let format_str = properties
             .get("format")
@@ -7,7 +7,10 @@ use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Default pattern for parameter names: camelCase starting with lowercase
 const DEFAULT_FORMAT: &str = r"^[a-z][a-zA-Z0-9]*$";
@@ -80,6 +83,39 @@ impl Default for ParameterName {
 impl FromConfig for ParameterName {
     const MODULE_NAME: &'static str = "ParameterName";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that method and constructor parameter names match a pattern.",
+        category: RuleCategory::Naming,
+        properties: &[
+            PropertySpec::new(
+                "format",
+                PropertyKind::Regex,
+                DEFAULT_FORMAT,
+                "The pattern names must match.",
+            ),
+            PropertySpec::new(
+                "ignoreOverridden",
+                PropertyKind::Boolean,
+                "false",
+                "Ignore parameters of methods annotated with `@Override`.",
+            ),
+            PropertySpec::new(
+                "accessModifiers",
+                PropertyKind::Set(&["public", "protected", "package", "private"]),
+                "public, protected, package, private",
+                "Check methods with these access modifiers.",
+            ),
+        ],
+        fix: FixAvailability::of(&[ParameterNameInvalid::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void run(int Count) {
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let format_str = properties
             .get("format")
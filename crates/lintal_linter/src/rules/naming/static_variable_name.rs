@@ -11,7 +11,10 @@ use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Default pattern for static variable names: camelCase starting with lowercase
 const DEFAULT_FORMAT: &str = r"^[a-z][a-zA-Z0-9]*$";
@@ -52,6 +55,50 @@ impl Default for StaticVariableName {
 impl FromConfig for StaticVariableName {
     const MODULE_NAME: &'static str = "StaticVariableName";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that static, non-final field names match a pattern.",
+        category: RuleCategory::Naming,
+        properties: &[
+            PropertySpec::new(
+                "format",
+                PropertyKind::Regex,
+                DEFAULT_FORMAT,
+                "The pattern names must match.",
+            ),
+            PropertySpec::new(
+                "applyToPublic",
+                PropertyKind::Boolean,
+                "true",
+                "Check public members.",
+            ),
+            PropertySpec::new(
+                "applyToProtected",
+                PropertyKind::Boolean,
+                "true",
+                "Check protected members.",
+            ),
+            PropertySpec::new(
+                "applyToPackage",
+                PropertyKind::Boolean,
+                "true",
+                "Check package-private members.",
+            ),
+            PropertySpec::new(
+                "applyToPrivate",
+                PropertyKind::Boolean,
+                "true",
+                "Check private members.",
+            ),
+        ],
+        fix: FixAvailability::of(&[StaticVariableNameInvalid::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    static int Count;
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let format_str = properties
             .get("format")
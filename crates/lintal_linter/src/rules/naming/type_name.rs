@@ -8,7 +8,10 @@ use lintal_java_cst::CstNode;
 use regex::Regex;
 use std::collections::HashSet;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Default pattern for type names: PascalCase
 const DEFAULT_FORMAT: &str = r"^[A-Z][a-zA-Z0-9]*$";
@@ -103,6 +106,61 @@ fn parse_tokens(tokens_str: &str) -> HashSet<TypeNameToken> {
 impl FromConfig for TypeName {
     const MODULE_NAME: &'static str = "TypeName";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that class, interface, enum, annotation and record names match a pattern.",
+        category: RuleCategory::Naming,
+        properties: &[
+            PropertySpec::new(
+                "format",
+                PropertyKind::Regex,
+                DEFAULT_FORMAT,
+                "The pattern names must match.",
+            ),
+            PropertySpec::new(
+                "tokens",
+                PropertyKind::Tokens(&[
+                    "CLASS_DEF",
+                    "INTERFACE_DEF",
+                    "ENUM_DEF",
+                    "ANNOTATION_DEF",
+                    "RECORD_DEF",
+                ]),
+                "CLASS_DEF, INTERFACE_DEF, ENUM_DEF, ANNOTATION_DEF, RECORD_DEF",
+                "Declarations to check.",
+            ),
+            PropertySpec::new(
+                "applyToPublic",
+                PropertyKind::Boolean,
+                "true",
+                "Check public members.",
+            ),
+            PropertySpec::new(
+                "applyToProtected",
+                PropertyKind::Boolean,
+                "true",
+                "Check protected members.",
+            ),
+            PropertySpec::new(
+                "applyToPackage",
+                PropertyKind::Boolean,
+                "true",
+                "Check package-private members.",
+            ),
+            PropertySpec::new(
+                "applyToPrivate",
+                PropertyKind::Boolean,
+                "true",
+                "Check private members.",
+            ),
+        ],
+        fix: FixAvailability::of(&[TypeNameInvalid::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class example {
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let format_str = properties
             .get("format")
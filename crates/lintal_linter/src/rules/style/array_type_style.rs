@@ -25,7 +25,10 @@ use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Violation: array brackets at illegal position.
 #[derive(Debug, Clone)]
@@ -37,6 +40,10 @@ impl Violation for ArrayTypeStyleViolation {
     fn message(&self) -> String {
         "Array brackets at illegal position.".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Move array brackets".to_string())
+    }
 }
 
 /// Configuration for ArrayTypeStyle rule.
@@ -62,6 +69,24 @@ impl Default for ArrayTypeStyle {
 impl FromConfig for ArrayTypeStyle {
     const MODULE_NAME: &'static str = "ArrayTypeStyle";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that array brackets follow the type (`int[] a`) rather than the name (`int a[]`).",
+        category: RuleCategory::Misc,
+        properties: &[PropertySpec::new(
+            "javaStyle",
+            PropertyKind::Boolean,
+            "true",
+            "Require Java style (`int[] a`) instead of C style (`int a[]`).",
+        )],
+        fix: FixAvailability::of(&[ArrayTypeStyleViolation::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    int values[];
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let java_style = properties
             .get("javaStyle")
@@ -16,7 +16,7 @@ use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{CheckContext, FromConfig, Properties, Rule, RuleCategory, RuleMetadata};
 
 /// Violation: long literal uses lowercase 'l' suffix.
 #[derive(Debug, Clone)]
@@ -28,6 +28,10 @@ impl Violation for UpperEllViolation {
     fn message(&self) -> String {
         "Should use uppercase 'L'.".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Replace 'l' with 'L'".to_string())
+    }
 }
 
 /// Configuration for UpperEll rule.
@@ -46,6 +50,19 @@ const RELEVANT_KINDS: &[&str] = &[
 impl FromConfig for UpperEll {
     const MODULE_NAME: &'static str = "UpperEll";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that long literals use an uppercase 'L' suffix.",
+        category: RuleCategory::Misc,
+        properties: &[],
+        fix: FixAvailability::of(&[UpperEllViolation::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    long size = 10l;
+}
+",
+    };
+
     fn from_config(_properties: &Properties) -> Self {
         Self
     }
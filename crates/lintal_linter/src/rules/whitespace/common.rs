@@ -109,6 +109,10 @@ impl Violation for NotFollowed {
     fn message(&self) -> String {
         format!("'{}' is not followed by whitespace", self.token)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Insert whitespace after '{}'", self.token))
+    }
 }
 
 /// Violation: token is not preceded by whitespace.
@@ -123,6 +127,10 @@ impl Violation for NotPreceded {
     fn message(&self) -> String {
         format!("'{}' is not preceded by whitespace", self.token)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Insert whitespace before '{}'", self.token))
+    }
 }
 
 /// Violation: token is followed by whitespace (when it shouldn't be).
@@ -137,6 +145,10 @@ impl Violation for Followed {
     fn message(&self) -> String {
         format!("'{}' is followed by whitespace", self.token)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Remove whitespace after '{}'", self.token))
+    }
 }
 
 /// Violation: token is preceded by whitespace (when it shouldn't be).
@@ -151,6 +163,10 @@ impl Violation for Preceded {
     fn message(&self) -> String {
         format!("'{}' is preceded by whitespace", self.token)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Remove whitespace before '{}'", self.token))
+    }
 }
 
 // ============================================================================
@@ -5,14 +5,18 @@
 //! forbidden. No check occurs if there is a line wrap at the initializer.
 //! Checkstyle equivalent: EmptyForInitializerPad
 
-use lintal_diagnostics::Diagnostic;
+use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::TextSize;
 
 use crate::rules::whitespace::common::{
-    diag_not_preceded, diag_preceded, has_whitespace_before, whitespace_range_before,
+    NotPreceded, Preceded, diag_not_preceded, diag_preceded, has_whitespace_before,
+    whitespace_range_before,
+};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
 };
-use crate::{CheckContext, FromConfig, Properties, Rule};
 
 /// Configuration option for EmptyForInitializerPad.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -53,6 +57,27 @@ impl Default for EmptyForInitializerPad {
 impl FromConfig for EmptyForInitializerPad {
     const MODULE_NAME: &'static str = "EmptyForInitializerPad";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks the padding of an empty for loop initializer.",
+        category: RuleCategory::Whitespace,
+        properties: &[PropertySpec::new(
+            "option",
+            PropertyKind::Enum(&["nospace", "space"]),
+            "nospace",
+            "Whether a space is required or forbidden before the semicolon.",
+        )],
+        fix: FixAvailability::of(&[NotPreceded::FIX_AVAILABILITY, Preceded::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void run(int i) {
+        for ( ; i < 10; i++) {
+        }
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let option = properties
             .get("option")
@@ -10,7 +10,10 @@ use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Violation: element should be separated from previous line.
 #[derive(Debug, Clone)]
@@ -24,6 +27,10 @@ impl Violation for ShouldBeSeparated {
     fn message(&self) -> String {
         format!("'{}' should be separated from previous line.", self.element)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Insert empty line".to_string())
+    }
 }
 
 /// Violation: element has too many empty lines before it.
@@ -38,6 +45,10 @@ impl Violation for TooManyEmptyLines {
     fn message(&self) -> String {
         format!("'{}' has more than 1 empty lines before.", self.element)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Remove extra empty lines".to_string())
+    }
 }
 
 /// Violation: closing brace has too many empty lines after it.
@@ -50,6 +61,10 @@ impl Violation for TooManyEmptyLinesAfter {
     fn message(&self) -> String {
         "'}' has more than 1 empty lines after.".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Remove extra empty lines".to_string())
+    }
 }
 
 /// Violation: too many empty lines inside a class member.
@@ -62,6 +77,10 @@ impl Violation for TooManyEmptyLinesInside {
     fn message(&self) -> String {
         "There is more than 1 empty line after this line.".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Remove extra empty lines".to_string())
+    }
 }
 
 /// Violation: comment has too many empty lines before it.
@@ -79,6 +98,10 @@ impl Violation for CommentTooManyEmptyLines {
             self.comment_start
         )
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Remove extra empty lines".to_string())
+    }
 }
 
 /// Token types that can be checked.
@@ -189,6 +212,67 @@ impl Default for EmptyLineSeparator {
 impl FromConfig for EmptyLineSeparator {
     const MODULE_NAME: &'static str = "EmptyLineSeparator";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that declarations are separated by empty lines.",
+        category: RuleCategory::Whitespace,
+        properties: &[
+            PropertySpec::new(
+                "allowNoEmptyLineBetweenFields",
+                PropertyKind::Boolean,
+                "false",
+                "Allow fields without an empty line between them.",
+            ),
+            PropertySpec::new(
+                "allowMultipleEmptyLines",
+                PropertyKind::Boolean,
+                "true",
+                "Allow more than one empty line between declarations.",
+            ),
+            PropertySpec::new(
+                "allowMultipleEmptyLinesInsideClassMembers",
+                PropertyKind::Boolean,
+                "true",
+                "Allow more than one empty line inside class members.",
+            ),
+            PropertySpec::new(
+                "tokens",
+                PropertyKind::Tokens(&[
+                    "PACKAGE_DEF",
+                    "IMPORT",
+                    "STATIC_IMPORT",
+                    "CLASS_DEF",
+                    "INTERFACE_DEF",
+                    "ENUM_DEF",
+                    "STATIC_INIT",
+                    "INSTANCE_INIT",
+                    "METHOD_DEF",
+                    "CTOR_DEF",
+                    "VARIABLE_DEF",
+                    "RECORD_DEF",
+                    "COMPACT_CTOR_DEF",
+                ]),
+                "PACKAGE_DEF, IMPORT, STATIC_IMPORT, CLASS_DEF, INTERFACE_DEF, ENUM_DEF, STATIC_INIT, INSTANCE_INIT, METHOD_DEF, CTOR_DEF, VARIABLE_DEF, RECORD_DEF, COMPACT_CTOR_DEF",
+                "Declarations that need an empty line before them.",
+            ),
+        ],
+        fix: FixAvailability::of(&[
+            ShouldBeSeparated::FIX_AVAILABILITY,
+            TooManyEmptyLines::FIX_AVAILABILITY,
+            TooManyEmptyLinesAfter::FIX_AVAILABILITY,
+            TooManyEmptyLinesInside::FIX_AVAILABILITY,
+            CommentTooManyEmptyLines::FIX_AVAILABILITY,
+        ]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void start() {
+    }
+    void stop() {
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let allow_no_empty_line_between_fields = properties
             .get("allowNoEmptyLineBetweenFields")
@@ -9,7 +9,10 @@ use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Violation: file contains tab character.
 #[derive(Debug, Clone)]
@@ -21,6 +24,10 @@ impl Violation for FileContainsTabViolation {
     fn message(&self) -> String {
         "File contains tab characters (this is the first instance)".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Replace tabs with spaces".to_string())
+    }
 }
 
 /// Violation: line contains tab character (when eachLine=true).
@@ -33,6 +40,10 @@ impl Violation for LineContainsTabViolation {
     fn message(&self) -> String {
         "Line contains a tab character".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Replace tabs with spaces".to_string())
+    }
 }
 
 /// Configuration for FileTabCharacter rule.
@@ -58,6 +69,42 @@ impl Default for FileTabCharacter {
 impl FromConfig for FileTabCharacter {
     const MODULE_NAME: &'static str = "FileTabCharacter";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that there are no tab characters in the source.",
+        category: RuleCategory::Whitespace,
+        properties: &[
+            PropertySpec::new(
+                "eachLine",
+                PropertyKind::Boolean,
+                "false",
+                "Report every line with a tab instead of only the first.",
+            ),
+            PropertySpec::new(
+                "tabWidth",
+                PropertyKind::PositiveInteger,
+                "8",
+                "Columns per tab stop, used when replacing tabs with spaces.",
+            ),
+            PropertySpec::new(
+                "fileExtensions",
+                PropertyKind::String,
+                "",
+                "File extensions to check.",
+            )
+            .unsupported(),
+        ],
+        fix: FixAvailability::of(&[
+            FileContainsTabViolation::FIX_AVAILABILITY,
+            LineContainsTabViolation::FIX_AVAILABILITY,
+        ]),
+        compatibility: &["Every Java file is checked; `fileExtensions` is ignored."],
+        example: "\
+class Example {
+\tint count;
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let each_line = properties
             .get("eachLine")
@@ -67,6 +114,7 @@ impl FromConfig for FileTabCharacter {
         let tab_width = properties
             .get("tabWidth")
             .and_then(|s| s.parse().ok())
+            .filter(|&width| width > 0)
             .unwrap_or(8);
 
         Self {
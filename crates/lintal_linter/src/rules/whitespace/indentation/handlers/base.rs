@@ -31,6 +31,10 @@ impl Violation for IndentationError {
             self.element, self.actual, self.expected
         )
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Indent to level {}", self.expected))
+    }
 }
 
 /// Violation for incorrect child indentation.
@@ -53,6 +57,10 @@ impl Violation for IndentationChildError {
             self.parent, self.actual, self.expected
         )
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Indent to level {}", self.expected))
+    }
 }
 
 /// Context for indentation checking, shared across all handlers.
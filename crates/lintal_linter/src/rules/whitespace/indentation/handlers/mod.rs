@@ -5,4 +5,4 @@
 
 mod base;
 
-pub use base::{HandlerContext, IndentHandler};
+pub use base::{HandlerContext, IndentHandler, IndentationChildError, IndentationError};
@@ -6,12 +6,15 @@
 pub mod handlers;
 pub mod indent_level;
 
-use lintal_diagnostics::Diagnostic;
+use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
-pub use handlers::{HandlerContext, IndentHandler};
+pub use handlers::{HandlerContext, IndentHandler, IndentationChildError, IndentationError};
 pub use indent_level::IndentLevel;
 
 /// Default indentation amount (matches checkstyle).
@@ -56,6 +59,74 @@ impl Default for Indentation {
 impl FromConfig for Indentation {
     const MODULE_NAME: &'static str = "Indentation";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks the indentation of Java code.",
+        category: RuleCategory::Indentation,
+        properties: &[
+            PropertySpec::new(
+                "basicOffset",
+                PropertyKind::Integer,
+                "4",
+                "Indentation of a new block.",
+            ),
+            PropertySpec::new(
+                "braceAdjustment",
+                PropertyKind::Integer,
+                "0",
+                "Extra indentation of braces on their own line.",
+            ),
+            PropertySpec::new(
+                "caseIndent",
+                PropertyKind::Integer,
+                "4",
+                "Indentation of case labels within a switch.",
+            ),
+            PropertySpec::new(
+                "throwsIndent",
+                PropertyKind::Integer,
+                "4",
+                "Indentation of a wrapped throws clause.",
+            ),
+            PropertySpec::new(
+                "arrayInitIndent",
+                PropertyKind::Integer,
+                "4",
+                "Indentation of array initializers.",
+            ),
+            PropertySpec::new(
+                "lineWrappingIndentation",
+                PropertyKind::Integer,
+                "4",
+                "Indentation of wrapped lines.",
+            ),
+            PropertySpec::new(
+                "forceStrictCondition",
+                PropertyKind::Boolean,
+                "false",
+                "Require wrapped lines to be indented exactly, not at least, by lineWrappingIndentation.",
+            ),
+            PropertySpec::new(
+                "tabWidth",
+                PropertyKind::PositiveInteger,
+                "4",
+                "Columns per tab stop.",
+            ),
+        ],
+        fix: FixAvailability::of(&[
+            IndentationError::FIX_AVAILABILITY,
+            IndentationChildError::FIX_AVAILABILITY,
+        ]),
+        compatibility: &[
+            "Finds about 89% of the violations in checkstyle's own test fixtures, favoring no false positives.",
+        ],
+        example: "\
+class Example {
+  void run() {
+  }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         Self {
             basic_offset: properties
@@ -10,7 +10,10 @@ use lintal_java_cst::CstNode;
 use lintal_text_size::TextRange;
 
 use crate::rules::whitespace::common::{has_whitespace_before, whitespace_range_before};
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Tokens that can be checked by MethodParamPad.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -71,6 +74,10 @@ impl Violation for WsPreceded {
     fn message(&self) -> String {
         format!("'{}' is preceded by whitespace", self.token)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Remove whitespace".to_string())
+    }
 }
 
 /// Violation: '(' is not preceded by whitespace (when option=space).
@@ -85,6 +92,10 @@ impl Violation for WsNotPreceded {
     fn message(&self) -> String {
         format!("'{}' is not preceded by whitespace", self.token)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Insert whitespace".to_string())
+    }
 }
 
 /// Violation: '(' should be on the previous line (when allowLineBreaks=false).
@@ -99,6 +110,10 @@ impl Violation for LinePrevious {
     fn message(&self) -> String {
         format!("'{}' should be on the previous line", self.token)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Move '{}' to the previous line", self.token))
+    }
 }
 
 /// Configuration for MethodParamPad rule.
@@ -144,6 +159,51 @@ impl Default for MethodParamPad {
 impl FromConfig for MethodParamPad {
     const MODULE_NAME: &'static str = "MethodParamPad";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks the padding between a method or constructor name and its parameter list.",
+        category: RuleCategory::Whitespace,
+        properties: &[
+            PropertySpec::new(
+                "option",
+                PropertyKind::Enum(&["nospace", "space"]),
+                "nospace",
+                "Whether a space is required or forbidden before the parenthesis.",
+            ),
+            PropertySpec::new(
+                "allowLineBreaks",
+                PropertyKind::Boolean,
+                "false",
+                "Allow a line break before the parenthesis.",
+            ),
+            PropertySpec::new(
+                "tokens",
+                PropertyKind::Tokens(&[
+                    "CTOR_DEF",
+                    "LITERAL_NEW",
+                    "METHOD_CALL",
+                    "METHOD_DEF",
+                    "SUPER_CTOR_CALL",
+                    "ENUM_CONSTANT_DEF",
+                    "RECORD_DEF",
+                ]),
+                "CTOR_DEF, LITERAL_NEW, METHOD_CALL, METHOD_DEF, SUPER_CTOR_CALL, ENUM_CONSTANT_DEF, RECORD_DEF",
+                "Constructs to check.",
+            ),
+        ],
+        fix: FixAvailability::of(&[
+            WsPreceded::FIX_AVAILABILITY,
+            WsNotPreceded::FIX_AVAILABILITY,
+            LinePrevious::FIX_AVAILABILITY,
+        ]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void run () {
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let option = properties
             .get("option")
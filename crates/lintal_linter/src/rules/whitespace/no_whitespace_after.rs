@@ -5,12 +5,15 @@
 
 use std::collections::HashSet;
 
-use lintal_diagnostics::Diagnostic;
+use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::TextSize;
 
-use crate::rules::whitespace::common::{diag_followed, whitespace_range_after};
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::rules::whitespace::common::{Followed, diag_followed, whitespace_range_after};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Tokens that can be checked by NoWhitespaceAfter.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -104,6 +107,49 @@ impl Default for NoWhitespaceAfter {
 impl FromConfig for NoWhitespaceAfter {
     const MODULE_NAME: &'static str = "NoWhitespaceAfter";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that there is no whitespace after certain tokens.",
+        category: RuleCategory::Whitespace,
+        properties: &[
+            PropertySpec::new(
+                "allowLineBreaks",
+                PropertyKind::Boolean,
+                "true",
+                "Allow a line break after the token.",
+            ),
+            PropertySpec::new(
+                "tokens",
+                PropertyKind::Tokens(&[
+                    "ARRAY_INIT",
+                    "AT",
+                    "INC",
+                    "DEC",
+                    "UNARY_MINUS",
+                    "UNARY_PLUS",
+                    "BNOT",
+                    "LNOT",
+                    "DOT",
+                    "ARRAY_DECLARATOR",
+                    "INDEX_OP",
+                    "TYPECAST",
+                    "LITERAL_SYNCHRONIZED",
+                    "METHOD_REF",
+                ]),
+                "ARRAY_INIT, AT, INC, DEC, UNARY_MINUS, UNARY_PLUS, BNOT, LNOT, DOT, ARRAY_DECLARATOR, INDEX_OP",
+                "Tokens to check.",
+            ),
+        ],
+        fix: FixAvailability::of(&[Followed::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    int negate(int x) {
+        return - x;
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let tokens_str = properties.get("tokens").copied().unwrap_or("");
         let tokens: HashSet<_> = if tokens_str.is_empty() {
@@ -5,12 +5,15 @@
 
 use std::collections::HashSet;
 
-use lintal_diagnostics::Diagnostic;
+use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::TextSize;
 
-use crate::rules::whitespace::common::{diag_preceded, whitespace_range_before};
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::rules::whitespace::common::{Preceded, diag_preceded, whitespace_range_before};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Tokens that can be checked by NoWhitespaceBefore.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -75,6 +78,45 @@ impl Default for NoWhitespaceBefore {
 impl FromConfig for NoWhitespaceBefore {
     const MODULE_NAME: &'static str = "NoWhitespaceBefore";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that there is no whitespace before certain tokens.",
+        category: RuleCategory::Whitespace,
+        properties: &[
+            PropertySpec::new(
+                "allowLineBreaks",
+                PropertyKind::Boolean,
+                "false",
+                "Allow a line break before the token.",
+            ),
+            PropertySpec::new(
+                "tokens",
+                PropertyKind::Tokens(&[
+                    "COMMA",
+                    "SEMI",
+                    "POST_INC",
+                    "POST_DEC",
+                    "DOT",
+                    "GENERIC_START",
+                    "GENERIC_END",
+                    "ELLIPSIS",
+                    "LABELED_STAT",
+                    "METHOD_REF",
+                ]),
+                "COMMA, SEMI, POST_INC, POST_DEC, ELLIPSIS, LABELED_STAT",
+                "Tokens to check.",
+            ),
+        ],
+        fix: FixAvailability::of(&[Preceded::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void run() {
+        run() ;
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let tokens_str = properties.get("tokens").copied().unwrap_or("");
         let tokens: HashSet<_> = if tokens_str.is_empty() {
@@ -9,7 +9,10 @@ use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 use std::collections::HashSet;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Violation: operator should be on a new line.
 #[derive(Debug, Clone)]
@@ -23,6 +26,10 @@ impl Violation for OperatorShouldBeOnNewLine {
     fn message(&self) -> String {
         format!("'{}' should be on a new line.", self.operator)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Move '{}' to the next line", self.operator))
+    }
 }
 
 /// Violation: operator should be on the previous line.
@@ -37,6 +44,10 @@ impl Violation for OperatorShouldBeOnPrevLine {
     fn message(&self) -> String {
         format!("'{}' should be on the previous line.", self.operator)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Move '{}' to the previous line", self.operator))
+    }
 }
 
 /// Option for where operators should be placed.
@@ -234,6 +245,73 @@ impl Default for OperatorWrap {
 impl FromConfig for OperatorWrap {
     const MODULE_NAME: &'static str = "OperatorWrap";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks on which line an operator goes when an expression wraps.",
+        category: RuleCategory::Whitespace,
+        properties: &[
+            PropertySpec::new(
+                "option",
+                PropertyKind::Enum(&["nl", "eol"]),
+                "nl",
+                "Whether the operator starts the next line or ends the current one.",
+            ),
+            PropertySpec::new(
+                "tokens",
+                PropertyKind::Tokens(&[
+                    "QUESTION",
+                    "COLON",
+                    "EQUAL",
+                    "NOT_EQUAL",
+                    "DIV",
+                    "PLUS",
+                    "MINUS",
+                    "STAR",
+                    "MOD",
+                    "SR",
+                    "BSR",
+                    "GE",
+                    "GT",
+                    "SL",
+                    "LE",
+                    "LT",
+                    "BXOR",
+                    "BOR",
+                    "LOR",
+                    "BAND",
+                    "LAND",
+                    "TYPE_EXTENSION_AND",
+                    "LITERAL_INSTANCEOF",
+                    "METHOD_REF",
+                    "ASSIGN",
+                    "PLUS_ASSIGN",
+                    "MINUS_ASSIGN",
+                    "STAR_ASSIGN",
+                    "DIV_ASSIGN",
+                    "MOD_ASSIGN",
+                    "SL_ASSIGN",
+                    "SR_ASSIGN",
+                    "BSR_ASSIGN",
+                    "BXOR_ASSIGN",
+                    "BOR_ASSIGN",
+                    "BAND_ASSIGN",
+                ]),
+                "QUESTION, COLON, EQUAL, NOT_EQUAL, DIV, PLUS, MINUS, STAR, MOD, SR, BSR, GE, GT, SL, LE, LT, BXOR, BOR, LOR, BAND, LAND, TYPE_EXTENSION_AND, LITERAL_INSTANCEOF",
+                "Operators to check.",
+            ),
+        ],
+        fix: FixAvailability::of(&[
+            OperatorShouldBeOnNewLine::FIX_AVAILABILITY,
+            OperatorShouldBeOnPrevLine::FIX_AVAILABILITY,
+        ]),
+        compatibility: &[],
+        example: "\
+class Example {
+    int total = 1 +
+        2;
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let option = properties
             .get("option")
@@ -5,14 +5,18 @@
 
 use std::collections::HashSet;
 
-use lintal_diagnostics::Diagnostic;
+use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::rules::whitespace::common::{
-    char_after, char_before, diag_followed, diag_not_followed, diag_not_preceded, diag_preceded,
-    has_whitespace_after, has_whitespace_before, whitespace_range_after, whitespace_range_before,
+    Followed, NotFollowed, NotPreceded, Preceded, char_after, char_before, diag_followed,
+    diag_not_followed, diag_not_preceded, diag_preceded, has_whitespace_after,
+    has_whitespace_before, whitespace_range_after, whitespace_range_before,
+};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
 };
-use crate::{CheckContext, FromConfig, Properties, Rule};
 
 /// Tokens that can be checked by ParenPad.
 ///
@@ -160,6 +164,61 @@ impl Default for ParenPad {
 impl FromConfig for ParenPad {
     const MODULE_NAME: &'static str = "ParenPad";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks the padding inside parentheses.",
+        category: RuleCategory::Whitespace,
+        properties: &[
+            PropertySpec::new(
+                "option",
+                PropertyKind::Enum(&["nospace", "space"]),
+                "nospace",
+                "Whether a space is required or forbidden inside the parentheses.",
+            ),
+            PropertySpec::new(
+                "tokens",
+                PropertyKind::Tokens(&[
+                    "ANNOTATION",
+                    "ANNOTATION_FIELD_DEF",
+                    "CTOR_CALL",
+                    "CTOR_DEF",
+                    "ENUM_CONSTANT_DEF",
+                    "EXPR",
+                    "LITERAL_CATCH",
+                    "LITERAL_DO",
+                    "LITERAL_FOR",
+                    "LITERAL_IF",
+                    "LITERAL_NEW",
+                    "LITERAL_SWITCH",
+                    "LITERAL_SYNCHRONIZED",
+                    "LITERAL_WHILE",
+                    "METHOD_CALL",
+                    "METHOD_DEF",
+                    "QUESTION",
+                    "RESOURCE_SPECIFICATION",
+                    "SUPER_CTOR_CALL",
+                    "LAMBDA",
+                    "RECORD_DEF",
+                ]),
+                "ANNOTATION, ANNOTATION_FIELD_DEF, CTOR_CALL, CTOR_DEF, ENUM_CONSTANT_DEF, EXPR, LITERAL_CATCH, LITERAL_DO, LITERAL_FOR, LITERAL_IF, LITERAL_NEW, LITERAL_SWITCH, LITERAL_SYNCHRONIZED, LITERAL_WHILE, METHOD_CALL, METHOD_DEF, QUESTION, RESOURCE_SPECIFICATION, SUPER_CTOR_CALL, LAMBDA, RECORD_DEF",
+                "Constructs to check.",
+            ),
+        ],
+        fix: FixAvailability::of(&[
+            Followed::FIX_AVAILABILITY,
+            NotFollowed::FIX_AVAILABILITY,
+            NotPreceded::FIX_AVAILABILITY,
+            Preceded::FIX_AVAILABILITY,
+        ]),
+        compatibility: &[],
+        example: "\
+class Example {
+    void run(int x) {
+        run( x );
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let option = properties
             .get("option")
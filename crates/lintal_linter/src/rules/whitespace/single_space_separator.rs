@@ -10,7 +10,10 @@ use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Violation: multiple spaces separating non-whitespace characters.
 #[derive(Debug, Clone)]
@@ -22,6 +25,10 @@ impl Violation for SingleSpaceSeparatorViolation {
     fn message(&self) -> String {
         "'Use a single space to separate non-whitespace characters".to_string()
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Replace with a single space".to_string())
+    }
 }
 
 /// Configuration for SingleSpaceSeparator rule.
@@ -34,6 +41,24 @@ pub struct SingleSpaceSeparator {
 impl FromConfig for SingleSpaceSeparator {
     const MODULE_NAME: &'static str = "SingleSpaceSeparator";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that tokens are separated by exactly one space.",
+        category: RuleCategory::Whitespace,
+        properties: &[PropertySpec::new(
+            "validateComments",
+            PropertyKind::Boolean,
+            "false",
+            "Also check the whitespace before comments.",
+        )],
+        fix: FixAvailability::of(&[SingleSpaceSeparatorViolation::FIX_AVAILABILITY]),
+        compatibility: &[],
+        example: "\
+class Example {
+    int  count;
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let validate_comments = properties
             .get("validateComments")
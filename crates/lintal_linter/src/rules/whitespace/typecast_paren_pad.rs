@@ -3,14 +3,18 @@
 //! Checks for whitespace padding inside typecast parentheses.
 //! Checkstyle equivalent: TypecastParenPad
 
-use lintal_diagnostics::Diagnostic;
+use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::rules::whitespace::common::{
-    diag_followed, diag_not_followed, diag_not_preceded, diag_preceded, has_whitespace_after,
-    has_whitespace_before, whitespace_range_after, whitespace_range_before,
+    Followed, NotFollowed, NotPreceded, Preceded, diag_followed, diag_not_followed,
+    diag_not_preceded, diag_preceded, has_whitespace_after, has_whitespace_before,
+    whitespace_range_after, whitespace_range_before,
+};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
 };
-use crate::{CheckContext, FromConfig, Properties, Rule};
 
 /// TypecastParenPad option: space or nospace
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,6 +52,31 @@ impl Default for TypecastParenPad {
 impl FromConfig for TypecastParenPad {
     const MODULE_NAME: &'static str = "TypecastParenPad";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks the padding inside typecast parentheses.",
+        category: RuleCategory::Whitespace,
+        properties: &[PropertySpec::new(
+            "option",
+            PropertyKind::Enum(&["nospace", "space"]),
+            "nospace",
+            "Whether a space is required or forbidden inside the parentheses.",
+        )],
+        fix: FixAvailability::of(&[
+            Followed::FIX_AVAILABILITY,
+            NotFollowed::FIX_AVAILABILITY,
+            NotPreceded::FIX_AVAILABILITY,
+            Preceded::FIX_AVAILABILITY,
+        ]),
+        compatibility: &[],
+        example: "\
+class Example {
+    int run(long x) {
+        return ( int ) x;
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let option = properties
             .get("option")
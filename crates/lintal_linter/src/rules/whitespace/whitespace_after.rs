@@ -5,11 +5,14 @@
 
 use std::collections::HashSet;
 
-use lintal_diagnostics::Diagnostic;
+use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 
-use crate::rules::whitespace::common::{diag_not_followed, has_whitespace_after};
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::rules::whitespace::common::{NotFollowed, diag_not_followed, has_whitespace_after};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Tokens that can be checked by WhitespaceAfter.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -72,6 +75,37 @@ impl Default for WhitespaceAfter {
 impl FromConfig for WhitespaceAfter {
     const MODULE_NAME: &'static str = "WhitespaceAfter";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that certain tokens are followed by whitespace.",
+        category: RuleCategory::Whitespace,
+        properties: &[PropertySpec::new(
+            "tokens",
+            PropertyKind::Tokens(&[
+                "COMMA",
+                "SEMI",
+                "TYPECAST",
+                "LITERAL_IF",
+                "LITERAL_ELSE",
+                "LITERAL_WHILE",
+                "LITERAL_DO",
+                "LITERAL_FOR",
+                "DO_WHILE",
+            ]),
+            "COMMA, SEMI",
+            "Tokens to check.",
+        )],
+        fix: FixAvailability::of(&[NotFollowed::FIX_AVAILABILITY]),
+        compatibility: &[
+            "Only `COMMA` and `SEMI` are checked by default, where checkstyle also checks keywords, typecasts and others; list them in `tokens` to check them.",
+        ],
+        example: "\
+class Example {
+    void run(int x,int y) {
+    }
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         let tokens_str = properties.get("tokens").copied().unwrap_or("COMMA, SEMI");
         let tokens: HashSet<_> = tokens_str
@@ -7,7 +7,10 @@ use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::TextSize;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{
+    CheckContext, FromConfig, Properties, PropertyKind, PropertySpec, Rule, RuleCategory,
+    RuleMetadata,
+};
 
 /// Configuration for WhitespaceAround rule.
 #[derive(Debug, Clone)]
@@ -87,6 +90,131 @@ impl Default for WhitespaceAround {
 impl FromConfig for WhitespaceAround {
     const MODULE_NAME: &'static str = "WhitespaceAround";
 
+    const METADATA: RuleMetadata = RuleMetadata {
+        description: "Checks that operators, keywords and braces are surrounded by whitespace.",
+        category: RuleCategory::Whitespace,
+        properties: &[
+            PropertySpec::new(
+                "allowEmptyConstructors",
+                PropertyKind::Boolean,
+                "false",
+                "Allow empty constructor bodies: `Foo() {}`.",
+            ),
+            PropertySpec::new(
+                "allowEmptyMethods",
+                PropertyKind::Boolean,
+                "false",
+                "Allow empty method bodies: `void foo() {}`.",
+            ),
+            PropertySpec::new(
+                "allowEmptyTypes",
+                PropertyKind::Boolean,
+                "false",
+                "Allow empty class, interface and enum bodies: `class Foo {}`.",
+            ),
+            PropertySpec::new(
+                "allowEmptyLoops",
+                PropertyKind::Boolean,
+                "false",
+                "Allow empty loop bodies: `while (true) {}`.",
+            ),
+            PropertySpec::new(
+                "allowEmptyLambdas",
+                PropertyKind::Boolean,
+                "false",
+                "Allow empty lambda bodies: `() -> {}`.",
+            ),
+            PropertySpec::new(
+                "allowEmptyCatches",
+                PropertyKind::Boolean,
+                "false",
+                "Allow empty catch blocks: `catch (Exception e) {}`.",
+            ),
+            PropertySpec::new(
+                "ignoreEnhancedForColon",
+                PropertyKind::Boolean,
+                "true",
+                "Ignore the colon of enhanced for loops.",
+            ),
+            PropertySpec::new(
+                "tokens",
+                PropertyKind::Tokens(&[
+                    "ASSIGN",
+                    "ARRAY_INIT",
+                    "BAND",
+                    "BAND_ASSIGN",
+                    "BOR",
+                    "BOR_ASSIGN",
+                    "BSR",
+                    "BSR_ASSIGN",
+                    "BXOR",
+                    "BXOR_ASSIGN",
+                    "COLON",
+                    "DIV",
+                    "DIV_ASSIGN",
+                    "DO_WHILE",
+                    "EQUAL",
+                    "GE",
+                    "GT",
+                    "LAMBDA",
+                    "LAND",
+                    "LCURLY",
+                    "LE",
+                    "LITERAL_CATCH",
+                    "LITERAL_DO",
+                    "LITERAL_ELSE",
+                    "LITERAL_FINALLY",
+                    "LITERAL_FOR",
+                    "LITERAL_IF",
+                    "LITERAL_RETURN",
+                    "LITERAL_SWITCH",
+                    "LITERAL_SYNCHRONIZED",
+                    "LITERAL_TRY",
+                    "LITERAL_WHILE",
+                    "LOR",
+                    "LT",
+                    "MINUS",
+                    "MINUS_ASSIGN",
+                    "MOD",
+                    "MOD_ASSIGN",
+                    "NOT_EQUAL",
+                    "PLUS",
+                    "PLUS_ASSIGN",
+                    "QUESTION",
+                    "RCURLY",
+                    "SL",
+                    "SLIST",
+                    "SL_ASSIGN",
+                    "SR",
+                    "SR_ASSIGN",
+                    "STAR",
+                    "STAR_ASSIGN",
+                    "LITERAL_ASSERT",
+                    "TYPE_EXTENSION_AND",
+                    "WILDCARD_TYPE",
+                    "GENERIC_START",
+                    "GENERIC_END",
+                    "ELLIPSIS",
+                    "LITERAL_WHEN",
+                ]),
+                "ASSIGN, BAND, BAND_ASSIGN, BOR, BOR_ASSIGN, BSR, BSR_ASSIGN, BXOR, BXOR_ASSIGN, COLON, DIV, DIV_ASSIGN, DO_WHILE, EQUAL, GE, GT, LAMBDA, LAND, LCURLY, LE, LITERAL_CATCH, LITERAL_DO, LITERAL_ELSE, LITERAL_FINALLY, LITERAL_FOR, LITERAL_IF, LITERAL_RETURN, LITERAL_SWITCH, LITERAL_SYNCHRONIZED, LITERAL_TRY, LITERAL_WHILE, LOR, LT, MINUS, MINUS_ASSIGN, MOD, MOD_ASSIGN, NOT_EQUAL, PLUS, PLUS_ASSIGN, QUESTION, RCURLY, SL, SLIST, SL_ASSIGN, SR, SR_ASSIGN, STAR, STAR_ASSIGN, LITERAL_ASSERT, TYPE_EXTENSION_AND, LITERAL_WHEN",
+                "Tokens to check.",
+            ),
+        ],
+        fix: FixAvailability::of(&[
+            MissingWhitespaceBefore::FIX_AVAILABILITY,
+            MissingWhitespaceAfter::FIX_AVAILABILITY,
+        ]),
+        compatibility: &[
+            "`tokens` can only add GENERIC_START, GENERIC_END and WILDCARD_TYPE; the default tokens are always checked.",
+        ],
+        example: "\
+class Example {
+    int count=1;
+}
+",
+    };
+
     fn from_config(properties: &Properties) -> Self {
         // Check if tokens property includes generics tokens
         let tokens = properties.get("tokens").copied().unwrap_or("");
@@ -142,6 +270,10 @@ impl Violation for MissingWhitespaceBefore {
     fn message(&self) -> String {
         format!("Missing whitespace before `{}`", self.token)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Insert whitespace before `{}`", self.token))
+    }
 }
 
 /// Violation for missing whitespace after a token.
@@ -156,6 +288,10 @@ impl Violation for MissingWhitespaceAfter {
     fn message(&self) -> String {
         format!("Missing whitespace after `{}`", self.token)
     }
+
+    fn fix_title(&self) -> Option<String> {
+        Some(format!("Insert whitespace after `{}`", self.token))
+    }
 }
 
 impl Rule for WhitespaceAround {
@@ -4,13 +4,26 @@
 //! - `// CHECKSTYLE:OFF:RuleName` / `// CHECKSTYLE:ON:RuleName` comments
 //! - `/* CHECKSTYLE:OFF:RuleName */` block comments
 //! - `@SuppressWarnings("checkstyle:RuleName")` annotations
+//! - `SuppressWithNearbyCommentFilter` comments such as `// SUPPRESS CHECKSTYLE MagicNumber`
+//! - `suppressions.xml` files referenced by a `SuppressionFilter`
 //!
 //! Suppressions work by tracking ranges where specific rules are disabled.
 
-use lintal_java_cst::CstNode;
-use lintal_text_size::TextSize;
+use crate::xpath::XpathQuery;
+use lintal_diagnostics::{Diagnostic, Edit, Fix, Violation};
+use lintal_java_cst::{CstNode, node_range};
+use lintal_text_size::{TextRange, TextSize};
+use quick_xml::Reader;
+use quick_xml::encoding::Decoder;
+use quick_xml::events::{BytesStart, Event};
 use regex::Regex;
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Name under which unused suppressions are reported.
+pub const UNUSED_SUPPRESSION: &str = "UnusedSuppression";
 
 /// A suppression region where a specific rule is disabled.
 #[derive(Debug, Clone)]
@@ -21,6 +34,110 @@ pub struct SuppressionRegion {
     pub start: TextSize,
     /// End offset in the source (None means until end of file).
     pub end: Option<TextSize>,
+    /// Where the suppression was declared, if it can be reported as unused.
+    pub source: Option<SuppressionSource>,
+    /// Set once the region has suppressed a diagnostic.
+    used: Cell<bool>,
+}
+
+impl SuppressionRegion {
+    /// Returns true if the region covers `pos`.
+    fn contains(&self, pos: TextSize) -> bool {
+        pos >= self.start && self.end.is_none_or(|end| pos < end)
+    }
+}
+
+/// Where a suppression was declared, so that it can be reported when unused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuppressionSource {
+    /// Range of the comment or annotation value that declared the suppression.
+    pub range: TextRange,
+    /// Ranges to delete to remove the suppression.
+    pub deletions: Vec<TextRange>,
+}
+
+/// The rules lintal runs, used to decide which suppressions can be judged.
+///
+/// A suppression is only reported as unused if lintal runs every configured
+/// rule it could apply to; otherwise it may be hiding a violation that only
+/// checkstyle reports.
+#[derive(Debug, Clone, Default)]
+pub struct RuleCoverage {
+    running: Vec<String>,
+    unsupported: Vec<String>,
+}
+
+impl RuleCoverage {
+    /// Create a coverage from the rules lintal runs and the configured rules
+    /// it does not implement.
+    pub fn new(
+        running: impl IntoIterator<Item = impl Into<String>>,
+        unsupported: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            running: running.into_iter().map(Into::into).collect(),
+            unsupported: unsupported.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns true if suppressions of exactly `rule` can be judged.
+    fn covers_rule(&self, rule: &str) -> bool {
+        self.running.iter().any(|r| r == rule)
+    }
+
+    /// Returns true if suppressions of the checks matched by `checks` can be
+    /// judged.
+    fn covers_pattern(&self, checks: &Regex) -> bool {
+        self.running.iter().any(|r| checks_match(checks, r))
+            && !self.unsupported.iter().any(|r| checks_match(checks, r))
+    }
+
+    /// Returns true if suppressions that apply to any rule can be judged.
+    fn covers_all(&self) -> bool {
+        !self.running.is_empty() && self.unsupported.is_empty()
+    }
+}
+
+/// A suppression that did not suppress any diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedSuppression {
+    /// What the suppression was for, e.g. a rule name or checks pattern.
+    pub suppressed: String,
+    /// Where the suppression was declared.
+    pub source: SuppressionSource,
+}
+
+impl UnusedSuppression {
+    /// Convert to a diagnostic whose fix deletes the suppression.
+    pub fn into_diagnostic(self) -> Diagnostic {
+        let diagnostic = Diagnostic::new(
+            UnusedSuppressionViolation {
+                suppressed: self.suppressed,
+            },
+            self.source.range,
+        );
+        let mut edits = self.source.deletions.into_iter().map(Edit::range_deletion);
+        match edits.next() {
+            Some(first) => diagnostic.with_fix(Fix::safe_edits(first, edits)),
+            None => diagnostic,
+        }
+    }
+}
+
+/// Violation: a suppression no longer suppresses anything.
+#[derive(Debug, Clone)]
+pub struct UnusedSuppressionViolation {
+    suppressed: String,
+}
+
+impl Violation for UnusedSuppressionViolation {
+    fn message(&self) -> String {
+        format!("Unused suppression for `{}`", self.suppressed)
+    }
+
+    fn fix_title(&self) -> Option<String> {
+        Some("Remove unused suppression".to_string())
+    }
 }
 
 /// Configuration for a plain text comment filter.
@@ -68,14 +185,218 @@ impl PlainTextCommentFilterConfig {
     }
 }
 
-/// A file-based suppression rule from suppressions.xml.
-/// Matches file paths and rule names using regex patterns.
+/// Configuration for a nearby comment filter (`SuppressWithNearbyCommentFilter`).
+///
+/// A matching comment suppresses violations on the comment's line, extended
+/// by `influence_format` lines after it (or before it, if negative). The
+/// check, message and id formats may refer to groups of `comment_pattern`
+/// as `$1`, `$2`, ... and are matched against each violation.
+#[derive(Debug, Clone)]
+pub struct NearbyCommentFilterConfig {
+    /// Regex pattern for suppression comments.
+    pub comment_pattern: Regex,
+    /// Template for the regex matching check names.
+    pub check_format: String,
+    /// Template for the regex matching violation messages.
+    pub message_format: Option<String>,
+    /// Template for the regex matching module ids.
+    pub id_format: Option<String>,
+    /// Template for the number of lines the suppression covers.
+    pub influence_format: String,
+    /// Whether `//` comments can suppress.
+    pub check_cpp: bool,
+    /// Whether `/* */` comments can suppress.
+    pub check_c: bool,
+}
+
+impl NearbyCommentFilterConfig {
+    /// Create a new filter config from checkstyle properties, using
+    /// checkstyle's defaults for any that are absent.
+    ///
+    /// - `comment_format`: Regex for suppression comments (default `SUPPRESS CHECKSTYLE (\w+)`)
+    /// - `check_format`: Check name template (default `.*`)
+    /// - `influence_format`: Number of lines affected (default `0`)
+    pub fn new(
+        comment_format: Option<&str>,
+        check_format: Option<&str>,
+        message_format: Option<&str>,
+        id_format: Option<&str>,
+        influence_format: Option<&str>,
+    ) -> Option<Self> {
+        let comment_pattern =
+            Regex::new(comment_format.unwrap_or(r"SUPPRESS CHECKSTYLE (\w+)")).ok()?;
+        Some(Self {
+            comment_pattern,
+            check_format: check_format.unwrap_or(".*").to_string(),
+            message_format: message_format.map(str::to_string),
+            id_format: id_format.map(str::to_string),
+            influence_format: influence_format.unwrap_or("0").to_string(),
+            check_cpp: true,
+            check_c: true,
+        })
+    }
+
+    /// Build the suppression for a comment line, if it matches.
+    fn suppression(
+        &self,
+        text: &str,
+        line: usize,
+        source: &SuppressionSource,
+    ) -> Option<NearbySuppression> {
+        let captures = self.comment_pattern.captures(text)?;
+        let check = Regex::new(&fill_template(&self.check_format, &captures)).ok()?;
+        let message = match &self.message_format {
+            Some(format) => Some(Regex::new(&fill_template(format, &captures)).ok()?),
+            None => None,
+        };
+        let id = match &self.id_format {
+            Some(format) => Some(Regex::new(&fill_template(format, &captures)).ok()?),
+            None => None,
+        };
+        let influence = fill_template(&self.influence_format, &captures);
+        let influence = influence.trim();
+        let influence: i64 = influence
+            .strip_prefix('+')
+            .unwrap_or(influence)
+            .parse()
+            .ok()?;
+
+        let line = i64::try_from(line).ok()?;
+        let (first, last) = if influence >= 1 {
+            (line, line + influence)
+        } else {
+            (line + influence, line)
+        };
+        Some(NearbySuppression {
+            first_line: usize::try_from(first.max(1)).ok()?,
+            last_line: usize::try_from(last.max(1)).ok()?,
+            check,
+            message,
+            id,
+            source: source.clone(),
+            used: Cell::new(false),
+        })
+    }
+}
+
+/// Replace `$0`, `$1`, ... in a template with the corresponding capture
+/// groups, as checkstyle does.
+fn fill_template(template: &str, captures: &regex::Captures) -> String {
+    let mut result = template.to_string();
+    // Highest group first, so that `$1` does not clobber `$10`.
+    for i in (0..captures.len()).rev() {
+        let group = captures.get(i).map_or("", |m| m.as_str());
+        result = result.replace(&format!("${i}"), group);
+    }
+    result
+}
+
+/// Lines on which a nearby comment suppresses matching violations.
 #[derive(Debug, Clone)]
+struct NearbySuppression {
+    first_line: usize,
+    last_line: usize,
+    check: Regex,
+    message: Option<Regex>,
+    id: Option<Regex>,
+    source: SuppressionSource,
+    used: Cell<bool>,
+}
+
+impl NearbySuppression {
+    fn matches(&self, target: &SuppressionTarget) -> bool {
+        (self.first_line..=self.last_line).contains(&target.line)
+            && checks_match(&self.check, target.rule)
+            && self
+                .id
+                .as_ref()
+                .is_none_or(|id| target.id.is_some_and(|target_id| id.is_match(target_id)))
+            && self
+                .message
+                .as_ref()
+                .is_none_or(|message| message.is_match(target.message))
+    }
+}
+
+/// A set of line or column numbers from suppressions.xml, such as `1,5-10`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberSet {
+    ranges: Vec<(usize, usize)>,
+}
+
+impl NumberSet {
+    /// Parse a comma-separated list of numbers and inclusive `a-b` ranges.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut ranges = Vec::new();
+        for part in value.split(',').map(str::trim) {
+            let parse = |n: &str| {
+                n.trim()
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid number '{}' in '{value}'", n.trim()))
+            };
+            let range = match part.split_once('-') {
+                Some((start, end)) => (parse(start)?, parse(end)?),
+                None => {
+                    let n = parse(part)?;
+                    (n, n)
+                }
+            };
+            if range.0 > range.1 {
+                return Err(format!("invalid range '{part}' in '{value}'"));
+            }
+            ranges.push(range);
+        }
+        Ok(Self { ranges })
+    }
+
+    /// Returns true if `n` is in the set.
+    pub fn contains(&self, n: usize) -> bool {
+        self.ranges
+            .iter()
+            .any(|&(start, end)| start <= n && n <= end)
+    }
+}
+
+/// A violation tested against suppressions.xml entries.
+#[derive(Debug, Clone, Copy)]
+pub struct SuppressionTarget<'a> {
+    /// The rule (checkstyle module) name.
+    pub rule: &'a str,
+    /// The module's `id` property, if set.
+    pub id: Option<&'a str>,
+    /// The violation message.
+    pub message: &'a str,
+    /// 1-based line of the violation.
+    pub line: usize,
+    /// 1-based column of the violation.
+    pub column: usize,
+    /// Offset of the violation, used by `suppress-xpath` queries.
+    pub offset: TextSize,
+}
+
+/// A `<suppress>` or `<suppress-xpath>` entry from suppressions.xml.
+///
+/// Every attribute that is present must match for a violation to be
+/// suppressed, as in checkstyle. `lines` and `columns` are the exception:
+/// when both are given, matching either one is enough.
+#[derive(Debug, Clone, Default)]
 pub struct FileSuppressionRule {
-    /// Regex pattern to match file paths.
-    pub files_pattern: Regex,
+    /// Regex pattern to match file paths (all files if absent).
+    pub files_pattern: Option<Regex>,
     /// Regex pattern to match rule/check names ("." matches all).
-    pub checks_pattern: Regex,
+    pub checks_pattern: Option<Regex>,
+    /// Regex pattern to match violation messages.
+    pub message_pattern: Option<Regex>,
+    /// Module id that must match exactly.
+    pub id: Option<String>,
+    /// Lines on which violations are suppressed.
+    pub lines: Option<NumberSet>,
+    /// Columns on which violations are suppressed.
+    pub columns: Option<NumberSet>,
+    /// Query selecting the code in which violations are suppressed.
+    pub query: Option<XpathQuery>,
+    /// Location of the entry in suppressions.xml.
+    pub range: TextRange,
 }
 
 impl FileSuppressionRule {
@@ -84,61 +405,210 @@ impl FileSuppressionRule {
         let files_pattern = Regex::new(files).ok()?;
         let checks_pattern = Regex::new(checks).ok()?;
         Some(Self {
-            files_pattern,
-            checks_pattern,
+            files_pattern: Some(files_pattern),
+            checks_pattern: Some(checks_pattern),
+            ..Self::default()
         })
     }
 
     /// Check if this rule suppresses the given check for the given file path.
+    ///
+    /// Only entries that apply to the whole file are considered; entries
+    /// restricted by message, id, position or query need a
+    /// [`SuppressionTarget`].
     pub fn is_suppressed(&self, file_path: &str, rule_name: &str) -> bool {
-        self.files_pattern.is_match(file_path) && self.checks_pattern.is_match(rule_name)
+        self.is_file_wide()
+            && self.matches_file(file_path)
+            && self
+                .checks_pattern
+                .as_ref()
+                .is_some_and(|checks| checks_match(checks, rule_name))
+    }
+
+    /// Returns true if the entry is not restricted to part of a file or to
+    /// particular violations.
+    fn is_file_wide(&self) -> bool {
+        self.message_pattern.is_none()
+            && self.id.is_none()
+            && self.lines.is_none()
+            && self.columns.is_none()
+            && self.query.is_none()
+    }
+
+    fn matches_file(&self, file_path: &str) -> bool {
+        self.files_pattern
+            .as_ref()
+            .is_none_or(|files| files.is_match(file_path))
+    }
+
+    /// Check everything except the file and query against a violation.
+    fn matches_target(&self, target: &SuppressionTarget) -> bool {
+        let position_matches = match (&self.lines, &self.columns) {
+            (None, None) => true,
+            (lines, columns) => {
+                lines.as_ref().is_some_and(|l| l.contains(target.line))
+                    || columns.as_ref().is_some_and(|c| c.contains(target.column))
+            }
+        };
+        position_matches
+            && self
+                .checks_pattern
+                .as_ref()
+                .is_none_or(|checks| checks_match(checks, target.rule))
+            && self.id.as_deref().is_none_or(|id| target.id == Some(id))
+            && self
+                .message_pattern
+                .as_ref()
+                .is_none_or(|message| message.is_match(target.message))
+    }
+}
+
+/// Checkstyle matches `checks` against the check's class name, so accept
+/// both `LineLength` and `LineLengthCheck`.
+fn checks_match(checks: &Regex, rule_name: &str) -> bool {
+    checks.is_match(rule_name) || checks.is_match(&format!("{rule_name}Check"))
+}
+
+/// A problem with an entry in suppressions.xml.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuppressionsXmlError {
+    /// 1-based line of the offending element.
+    pub line: usize,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for SuppressionsXmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
     }
 }
 
 /// Collection of file-based suppression rules.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Default)]
 pub struct FileSuppressionsConfig {
     rules: Vec<FileSuppressionRule>,
+    /// Whether each rule has been applied to a file and has suppressed a
+    /// diagnostic, parallel to `rules`.
+    usage: Vec<EntryUsage>,
+}
+
+/// Usage of a suppressions.xml entry, recorded across all checked files.
+#[derive(Debug, Default)]
+struct EntryUsage {
+    applied: AtomicBool,
+    used: AtomicBool,
 }
 
 impl FileSuppressionsConfig {
     /// Create a new empty config.
     pub fn new() -> Self {
-        Self { rules: vec![] }
+        Self {
+            rules: vec![],
+            usage: vec![],
+        }
     }
 
-    /// Parse suppressions from an XML file (suppressions.xml format).
+    /// Parse suppressions from an XML file (suppressions.xml format),
+    /// skipping malformed entries.
     pub fn from_xml(xml: &str) -> Self {
-        let mut config = Self::new();
+        Self::parse(xml).0
+    }
 
-        // Simple XML parsing for suppress elements
-        // Format: <suppress files="pattern" checks="pattern"/>
-        for line in xml.lines() {
-            let line = line.trim();
-            if !line.starts_with("<suppress ") {
-                continue;
+    /// Parse suppressions from an XML file, returning the valid entries
+    /// together with a description of every malformed one.
+    ///
+    /// Malformed entries are left out rather than guessed at, so that a typo
+    /// never suppresses more than intended.
+    pub fn parse(xml: &str) -> (Self, Vec<SuppressionsXmlError>) {
+        let mut config = Self::new();
+        let mut errors = Vec::new();
+        let line_of = |pos: u64| {
+            let pos = usize::try_from(pos).unwrap_or(usize::MAX).min(xml.len());
+            xml[..pos].matches('\n').count() + 1
+        };
+
+        let mut reader = Reader::from_str(xml);
+        loop {
+            let start = reader.buffer_position();
+            match reader.read_event() {
+                Ok(Event::Start(element) | Event::Empty(element)) => {
+                    let xpath = match element.name().as_ref() {
+                        b"suppress" => false,
+                        b"suppress-xpath" => true,
+                        _ => continue,
+                    };
+                    match Self::parse_entry(&element, xpath, reader.decoder()) {
+                        Ok(mut rule) => {
+                            rule.range = text_range(
+                                usize::try_from(start).unwrap_or_default(),
+                                usize::try_from(reader.buffer_position()).unwrap_or_default(),
+                            );
+                            config.rules.push(rule);
+                            config.usage.push(EntryUsage::default());
+                        }
+                        Err(message) => errors.push(SuppressionsXmlError {
+                            line: line_of(start),
+                            message,
+                        }),
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(err) => {
+                    errors.push(SuppressionsXmlError {
+                        line: line_of(reader.error_position()),
+                        message: format!("invalid XML: {err}"),
+                    });
+                    break;
+                }
             }
+        }
 
-            let files = Self::extract_attr(line, "files");
-            let checks = Self::extract_attr(line, "checks");
+        (config, errors)
+    }
 
-            if let (Some(files), Some(checks)) = (files, checks)
-                && let Some(rule) = FileSuppressionRule::new(files, checks)
-            {
-                config.rules.push(rule);
+    /// Build a rule from the attributes of a `suppress` or `suppress-xpath`
+    /// element.
+    fn parse_entry(
+        element: &BytesStart,
+        xpath: bool,
+        decoder: Decoder,
+    ) -> Result<FileSuppressionRule, String> {
+        let tag = if xpath { "suppress-xpath" } else { "suppress" };
+        let regex = |name: &str, value: &str| {
+            Regex::new(value).map_err(|err| format!("invalid {name} pattern '{value}': {err}"))
+        };
+
+        let mut rule = FileSuppressionRule::default();
+        for attr in element.attributes() {
+            let attr = attr.map_err(|err| format!("malformed <{tag}> attribute: {err}"))?;
+            let name = String::from_utf8_lossy(attr.key.as_ref()).into_owned();
+            let value = attr
+                .decode_and_unescape_value(decoder)
+                .map_err(|err| format!("invalid value for '{name}': {err}"))?;
+            match name.as_str() {
+                "files" => rule.files_pattern = Some(regex("files", &value)?),
+                "checks" => rule.checks_pattern = Some(regex("checks", &value)?),
+                "message" => rule.message_pattern = Some(regex("message", &value)?),
+                "id" => rule.id = Some(value.into_owned()),
+                "lines" if !xpath => rule.lines = Some(NumberSet::parse(&value)?),
+                "columns" if !xpath => rule.columns = Some(NumberSet::parse(&value)?),
+                "query" if xpath => {
+                    let query = XpathQuery::parse(&value)
+                        .map_err(|err| format!("unsupported query '{value}': {err}"))?;
+                    rule.query = Some(query);
+                }
+                _ => return Err(format!("unknown attribute '{name}' on <{tag}>")),
             }
         }
 
-        config
-    }
-
-    /// Extract an attribute value from an XML element.
-    fn extract_attr<'a>(line: &'a str, attr: &str) -> Option<&'a str> {
-        let pattern = format!("{}=\"", attr);
-        let start = line.find(&pattern)? + pattern.len();
-        let rest = &line[start..];
-        let end = rest.find('"')?;
-        Some(&rest[..end])
+        if rule.checks_pattern.is_none() && rule.message_pattern.is_none() && rule.id.is_none() {
+            return Err(format!(
+                "<{tag}> needs at least one of 'checks', 'message' or 'id'"
+            ));
+        }
+        Ok(rule)
     }
 
     /// Check if a rule is suppressed for the given file path.
@@ -151,11 +621,70 @@ impl FileSuppressionsConfig {
     /// Check if all rules are suppressed for the given file path.
     pub fn is_file_fully_suppressed(&self, file_path: &str) -> bool {
         self.rules.iter().any(|rule| {
-            rule.files_pattern.is_match(file_path)
-                && (rule.checks_pattern.as_str() == "." || rule.checks_pattern.as_str() == ".*")
+            rule.is_file_wide()
+                && rule.matches_file(file_path)
+                && rule
+                    .checks_pattern
+                    .as_ref()
+                    .is_some_and(|checks| checks.as_str() == "." || checks.as_str() == ".*")
         })
     }
 
+    /// Select the entries that apply to one file, evaluating `suppress-xpath`
+    /// queries against its syntax tree.
+    pub fn for_file(&self, file_path: &str, root: &CstNode, source: &str) -> FileSuppressions<'_> {
+        let entries = self
+            .rules
+            .iter()
+            .zip(&self.usage)
+            .filter(|(rule, _)| rule.matches_file(file_path))
+            .map(|(rule, usage)| {
+                usage.applied.store(true, Ordering::Relaxed);
+                let ranges = rule
+                    .query
+                    .as_ref()
+                    .map(|query| query.matching_ranges(root.inner(), source));
+                (rule, usage, ranges)
+            })
+            .collect();
+        FileSuppressions { entries }
+    }
+
+    /// Entries that applied to at least one file selected with
+    /// [`Self::for_file`] but never suppressed a diagnostic.
+    ///
+    /// Entries whose checks could include rules outside `coverage` are not
+    /// reported. Their source ranges point into suppressions.xml; no deletion
+    /// is offered.
+    pub fn unused(&self, coverage: &RuleCoverage) -> Vec<UnusedSuppression> {
+        self.rules
+            .iter()
+            .zip(&self.usage)
+            .filter(|(_, usage)| {
+                usage.applied.load(Ordering::Relaxed) && !usage.used.load(Ordering::Relaxed)
+            })
+            .filter_map(|(rule, _)| {
+                let suppressed = match (&rule.checks_pattern, &rule.id, &rule.message_pattern) {
+                    (Some(checks), _, _) if coverage.covers_pattern(checks) => {
+                        checks.as_str().to_string()
+                    }
+                    (None, Some(id), _) if coverage.covers_all() => format!("id {id}"),
+                    (None, None, Some(message)) if coverage.covers_all() => {
+                        format!("message {}", message.as_str())
+                    }
+                    _ => return None,
+                };
+                Some(UnusedSuppression {
+                    suppressed,
+                    source: SuppressionSource {
+                        range: rule.range,
+                        deletions: vec![],
+                    },
+                })
+            })
+            .collect()
+    }
+
     /// Returns true if there are no suppression rules.
     pub fn is_empty(&self) -> bool {
         self.rules.is_empty()
@@ -167,12 +696,50 @@ impl FileSuppressionsConfig {
     }
 }
 
+/// The suppressions.xml entries that apply to a single file.
+#[derive(Debug)]
+pub struct FileSuppressions<'a> {
+    /// Each entry with its usage and the ranges matched by its query, if it
+    /// has one.
+    entries: Vec<(
+        &'a FileSuppressionRule,
+        &'a EntryUsage,
+        Option<Vec<TextRange>>,
+    )>,
+}
+
+impl FileSuppressions<'_> {
+    /// Returns true if no entries apply to the file.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Check if a violation is suppressed by any entry, marking every
+    /// matching entry as used.
+    pub fn is_suppressed(&self, target: &SuppressionTarget) -> bool {
+        let mut suppressed = false;
+        for (rule, usage, ranges) in &self.entries {
+            if rule.matches_target(target)
+                && ranges
+                    .as_ref()
+                    .is_none_or(|ranges| ranges.iter().any(|range| range.contains(target.offset)))
+            {
+                usage.used.store(true, Ordering::Relaxed);
+                suppressed = true;
+            }
+        }
+        suppressed
+    }
+}
+
 /// Manages suppressions for a source file.
 #[derive(Debug)]
 pub struct SuppressionContext {
     /// Suppression regions indexed by rule name.
     /// Key "*" matches all rules.
     regions: HashMap<String, Vec<SuppressionRegion>>,
+    /// Line-based suppressions from nearby comments.
+    nearby: Vec<NearbySuppression>,
 }
 
 impl SuppressionContext {
@@ -180,6 +747,7 @@ impl SuppressionContext {
     pub fn new() -> Self {
         Self {
             regions: HashMap::new(),
+            nearby: Vec::new(),
         }
     }
 
@@ -197,7 +765,7 @@ impl SuppressionContext {
     /// Parse suppressions using a specific filter configuration.
     fn parse_with_filter(&mut self, source: &str, filter: &PlainTextCommentFilterConfig) {
         // Track open suppressions: rule -> start offset
-        let mut open_suppressions: HashMap<String, TextSize> = HashMap::new();
+        let mut open_suppressions: HashMap<String, TextRange> = HashMap::new();
 
         // Use find() to skip to potential comment locations instead of byte-by-byte iteration
         let bytes = source.as_bytes();
@@ -221,6 +789,7 @@ impl SuppressionContext {
                 let comment = &source[pos..line_end];
 
                 self.process_comment(
+                    source,
                     comment,
                     TextSize::new(pos as u32),
                     filter,
@@ -239,6 +808,7 @@ impl SuppressionContext {
                     let comment = &source[pos..comment_end];
 
                     self.process_comment(
+                        source,
                         comment,
                         TextSize::new(pos as u32),
                         filter,
@@ -259,11 +829,16 @@ impl SuppressionContext {
 
         // Close any remaining open suppressions at end of file
         let end_pos = TextSize::new(source.len() as u32);
-        for (rule, start) in open_suppressions {
+        for (rule, off) in open_suppressions {
             self.add_region(SuppressionRegion {
                 rule,
-                start,
+                start: off.start(),
                 end: Some(end_pos),
+                source: Some(SuppressionSource {
+                    range: off,
+                    deletions: vec![deletion_range(source, off)],
+                }),
+                used: Cell::new(false),
             });
         }
     }
@@ -271,11 +846,14 @@ impl SuppressionContext {
     /// Process a single comment for suppression directives.
     fn process_comment(
         &mut self,
+        source: &str,
         comment: &str,
         comment_pos: TextSize,
         filter: &PlainTextCommentFilterConfig,
-        open_suppressions: &mut HashMap<String, TextSize>,
+        open_suppressions: &mut HashMap<String, TextRange>,
     ) {
+        let comment_range = TextRange::at(comment_pos, TextSize::of(comment));
+
         // Check for OFF pattern
         if let Some(captures) = filter.off_pattern.captures(comment) {
             let rule = if filter.check_format_group > 0 {
@@ -288,7 +866,7 @@ impl SuppressionContext {
             };
 
             // Start a new suppression region
-            open_suppressions.insert(rule, comment_pos);
+            open_suppressions.insert(rule, comment_range);
         }
 
         // Check for ON pattern
@@ -303,11 +881,19 @@ impl SuppressionContext {
             };
 
             // Close the suppression region
-            if let Some(start) = open_suppressions.remove(&rule) {
+            if let Some(off) = open_suppressions.remove(&rule) {
                 self.add_region(SuppressionRegion {
                     rule,
-                    start,
+                    start: off.start(),
                     end: Some(comment_pos),
+                    source: Some(SuppressionSource {
+                        range: off,
+                        deletions: vec![
+                            deletion_range(source, off),
+                            deletion_range(source, comment_range),
+                        ],
+                    }),
+                    used: Cell::new(false),
                 });
             }
         }
@@ -323,33 +909,74 @@ impl SuppressionContext {
 
     /// Check if a diagnostic at the given position for the given rule is suppressed.
     pub fn is_suppressed(&self, rule_name: &str, pos: TextSize) -> bool {
-        // Check rule-specific suppressions
-        if let Some(regions) = self.regions.get(rule_name) {
-            for region in regions {
-                if pos >= region.start {
-                    match region.end {
-                        Some(end) if pos < end => return true,
-                        None => return true,
-                        _ => {}
-                    }
+        // Check rule-specific and wildcard suppressions, marking every
+        // matching region as used
+        let mut suppressed = false;
+        for key in [rule_name, "*"] {
+            for region in self.regions.get(key).into_iter().flatten() {
+                if region.contains(pos) {
+                    region.used.set(true);
+                    suppressed = true;
                 }
             }
         }
+        suppressed
+    }
 
-        // Check wildcard suppressions
-        if let Some(regions) = self.regions.get("*") {
-            for region in regions {
-                if pos >= region.start {
-                    match region.end {
-                        Some(end) if pos < end => return true,
-                        None => return true,
-                        _ => {}
-                    }
-                }
+    /// Check if a violation is suppressed by a nearby comment.
+    pub fn is_suppressed_nearby(&self, target: &SuppressionTarget) -> bool {
+        let mut suppressed = false;
+        for suppression in &self.nearby {
+            if suppression.matches(target) {
+                suppression.used.set(true);
+                suppressed = true;
             }
         }
+        suppressed
+    }
 
-        false
+    /// Suppressions that have not suppressed any diagnostic so far.
+    ///
+    /// Wildcard regions and suppressions of rules outside `coverage` are
+    /// never reported. A comment that declares several suppressions is
+    /// reported once.
+    pub fn unused_suppressions(&self, coverage: &RuleCoverage) -> Vec<UnusedSuppression> {
+        let mut unused: Vec<UnusedSuppression> = Vec::new();
+        let mut used_sources: Vec<TextRange> = Vec::new();
+
+        let regions = self
+            .regions
+            .values()
+            .flatten()
+            .filter(|region| region.rule != "*")
+            .filter_map(|region| {
+                let source = region.source.as_ref()?;
+                let judged = coverage.covers_rule(&region.rule);
+                Some((region.used.get(), judged, region.rule.as_str(), source))
+            });
+        let nearby = self.nearby.iter().map(|suppression| {
+            (
+                suppression.used.get(),
+                coverage.covers_pattern(&suppression.check),
+                suppression.check.as_str(),
+                &suppression.source,
+            )
+        });
+
+        for (used, judged, suppressed, source) in regions.chain(nearby) {
+            if used {
+                used_sources.push(source.range);
+            } else if judged && !unused.iter().any(|u| u.source.range == source.range) {
+                unused.push(UnusedSuppression {
+                    suppressed: suppressed.to_string(),
+                    source: source.clone(),
+                });
+            }
+        }
+
+        unused.retain(|u| !used_sources.contains(&u.source.range));
+        unused.sort_by_key(|u| u.source.range.start());
+        unused
     }
 
     /// Check if there are any suppressions.
@@ -357,6 +984,52 @@ impl SuppressionContext {
         !self.regions.is_empty()
     }
 
+    /// Check if there are any nearby comment suppressions.
+    pub fn has_nearby_suppressions(&self) -> bool {
+        !self.nearby.is_empty()
+    }
+
+    /// Parse nearby comment suppressions from the comments in a CST tree.
+    ///
+    /// Each line of a block comment is matched separately, as in checkstyle.
+    pub fn parse_nearby_comments(
+        &mut self,
+        source: &str,
+        root: &CstNode,
+        filters: &[NearbyCommentFilterConfig],
+    ) {
+        if filters.is_empty() {
+            return;
+        }
+
+        let mut stack = vec![root.inner()];
+        while let Some(node) = stack.pop() {
+            let is_cpp = node.kind() == "line_comment";
+            if is_cpp || node.kind() == "block_comment" {
+                let start_line = node.start_position().row + 1;
+                let range = node_range(&node);
+                let origin = SuppressionSource {
+                    range,
+                    deletions: vec![deletion_range(source, range)],
+                };
+                for (i, text) in source[node.byte_range()].lines().enumerate() {
+                    for filter in filters {
+                        if (is_cpp && !filter.check_cpp) || (!is_cpp && !filter.check_c) {
+                            continue;
+                        }
+                        if let Some(suppression) = filter.suppression(text, start_line + i, &origin)
+                        {
+                            self.nearby.push(suppression);
+                        }
+                    }
+                }
+                continue;
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+    }
+
     /// Parse @SuppressWarnings annotations from a CST tree.
     /// Looks for annotations like:
     /// - `@SuppressWarnings("checkstyle:RuleName")`
@@ -438,11 +1111,22 @@ impl SuppressionContext {
         if let Some(args) = annotation.child_by_field_name("arguments") {
             // Extract the string values from the annotation
             let rules = self.extract_suppress_warnings_rules(source, &args);
-            for rule in rules {
+            for (rule, value, shared) in rules {
+                // Remove just the value if the annotation has others
+                let deletion = if shared {
+                    element_deletion_range(source, value)
+                } else {
+                    deletion_range(source, annotation.range())
+                };
                 self.add_region(SuppressionRegion {
                     rule,
                     start: target.range().start(),
                     end: Some(target.range().end()),
+                    source: Some(SuppressionSource {
+                        range: value,
+                        deletions: vec![deletion],
+                    }),
+                    used: Cell::new(false),
                 });
             }
         }
@@ -450,7 +1134,14 @@ impl SuppressionContext {
 
     /// Extract rule names from @SuppressWarnings annotation arguments.
     /// Handles both single strings and arrays: "checkstyle:Rule" or {"checkstyle:Rule1", "rule2"}
-    fn extract_suppress_warnings_rules(&self, source: &str, args: &CstNode) -> Vec<String> {
+    ///
+    /// Each rule is returned with the range of its string literal and whether
+    /// the literal shares an array with other values.
+    fn extract_suppress_warnings_rules(
+        &self,
+        source: &str,
+        args: &CstNode,
+    ) -> Vec<(String, TextRange, bool)> {
         let mut rules = Vec::new();
 
         // Look for string literals or array initializers
@@ -460,41 +1151,51 @@ impl SuppressionContext {
         // - annotation_argument_list > string_literal (direct value)
         // - annotation_argument_list > element_value_array_initializer > string_literal (direct array)
 
-        self.extract_rules_recursive(source, args, &mut rules);
+        self.extract_rules_recursive(source, args, false, &mut rules);
 
         rules
     }
 
     /// Recursively extract string values from annotation arguments.
-    fn extract_rules_recursive(&self, source: &str, node: &CstNode, rules: &mut Vec<String>) {
+    fn extract_rules_recursive(
+        &self,
+        source: &str,
+        node: &CstNode,
+        shared: bool,
+        rules: &mut Vec<(String, TextRange, bool)>,
+    ) {
         match node.kind() {
             "string_literal" => {
                 if let Some(rule) = self.parse_suppress_warning_value(source, node) {
-                    rules.push(rule);
+                    rules.push((rule, node.range(), shared));
                 }
             }
             "element_value_array_initializer" | "array_initializer" => {
                 // Array of values
+                let values = node
+                    .named_children()
+                    .filter(|c| !c.kind().ends_with("comment"))
+                    .count();
                 for child in node.named_children() {
-                    self.extract_rules_recursive(source, &child, rules);
+                    self.extract_rules_recursive(source, &child, values > 1, rules);
                 }
             }
             "element_value_pair" => {
                 // key=value pair - extract the value
                 if let Some(value) = node.child_by_field_name("value") {
-                    self.extract_rules_recursive(source, &value, rules);
+                    self.extract_rules_recursive(source, &value, shared, rules);
                 }
             }
             "annotation_argument_list" => {
                 // Recurse into all children
                 for child in node.named_children() {
-                    self.extract_rules_recursive(source, &child, rules);
+                    self.extract_rules_recursive(source, &child, shared, rules);
                 }
             }
             _ => {
                 // Try children for other node types
                 for child in node.named_children() {
-                    self.extract_rules_recursive(source, &child, rules);
+                    self.extract_rules_recursive(source, &child, shared, rules);
                 }
             }
         }
@@ -527,6 +1228,54 @@ impl Default for SuppressionContext {
     }
 }
 
+/// The range to delete to remove `range` from the source.
+///
+/// Removes the whole line if nothing else is on it, otherwise the range
+/// together with the whitespace separating it from the rest of the line.
+fn deletion_range(source: &str, range: TextRange) -> TextRange {
+    let start = usize::from(range.start());
+    let end = usize::from(range.end());
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[end..].find('\n').map_or(source.len(), |i| end + i);
+    let before = &source[line_start..start];
+    let after = &source[end..line_end];
+
+    let (start, end) = if before.trim().is_empty() && after.trim().is_empty() {
+        (line_start, (line_end + 1).min(source.len()))
+    } else if after.trim().is_empty() {
+        (start - (before.len() - before.trim_end().len()), line_end)
+    } else {
+        (start, end + (after.len() - after.trim_start().len()))
+    };
+    text_range(start, end)
+}
+
+/// The range to delete to remove one value from an array initializer,
+/// including the comma separating it from its neighbour.
+fn element_deletion_range(source: &str, range: TextRange) -> TextRange {
+    let start = usize::from(range.start());
+    let end = usize::from(range.end());
+    let after = &source[end..];
+    let after_trimmed = after.trim_start();
+    if let Some(rest) = after_trimmed.strip_prefix(',') {
+        let comma_end = source.len() - rest.len();
+        let spaces = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+        return text_range(start, comma_end + spaces);
+    }
+    let before = source[..start].trim_end();
+    match before.strip_suffix(',') {
+        Some(rest) => text_range(rest.len(), end),
+        None => range,
+    }
+}
+
+fn text_range(start: usize, end: usize) -> TextRange {
+    TextRange::new(
+        TextSize::try_from(start).unwrap_or_default(),
+        TextSize::try_from(end).unwrap_or_default(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -834,4 +1583,335 @@ class Foo {
         let config = FileSuppressionsConfig::from_xml(xml);
         assert!(config.is_empty());
     }
+
+    fn target<'a>(
+        rule: &'a str,
+        message: &'a str,
+        line: usize,
+        column: usize,
+    ) -> SuppressionTarget<'a> {
+        SuppressionTarget {
+            rule,
+            id: None,
+            message,
+            line,
+            column,
+            offset: TextSize::new(0),
+        }
+    }
+
+    fn for_file<'a>(
+        config: &'a FileSuppressionsConfig,
+        path: &str,
+        source: &str,
+    ) -> FileSuppressions<'a> {
+        let mut parser = lintal_java_parser::JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let root = CstNode::new(result.tree.root_node(), source);
+        config.for_file(path, &root, source)
+    }
+
+    #[test]
+    fn test_file_suppressions_multiline_elements_and_entities() {
+        let xml = r#"<suppressions>
+    <suppress
+        files="Foo\.java"
+        checks="LineLength"/>
+    <suppress files="Bar\.java" message="^Line is longer than 1&lt;0"/>
+</suppressions>"#;
+
+        let (config, errors) = FileSuppressionsConfig::parse(xml);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(config.len(), 2);
+        assert!(config.is_suppressed("src/Foo.java", "LineLength"));
+
+        let file = for_file(&config, "src/Bar.java", "class Bar {}");
+        assert!(file.is_suppressed(&target("LineLength", "Line is longer than 1<0", 1, 1)));
+        assert!(!file.is_suppressed(&target("LineLength", "Line is too long", 1, 1)));
+    }
+
+    #[test]
+    fn test_file_suppressions_lines_and_columns() {
+        let xml = r#"<suppressions>
+    <suppress checks="WhitespaceAround" lines="3,10-12"/>
+    <suppress checks="NeedBraces" lines="5" columns="9"/>
+</suppressions>"#;
+
+        let config = FileSuppressionsConfig::from_xml(xml);
+        // Position-restricted entries never suppress a whole file.
+        assert!(!config.is_suppressed("A.java", "WhitespaceAround"));
+
+        let file = for_file(&config, "A.java", "class A {}");
+        assert!(file.is_suppressed(&target("WhitespaceAround", "", 3, 1)));
+        assert!(file.is_suppressed(&target("WhitespaceAround", "", 11, 4)));
+        assert!(!file.is_suppressed(&target("WhitespaceAround", "", 13, 1)));
+
+        // Either the line or the column is enough.
+        assert!(file.is_suppressed(&target("NeedBraces", "", 5, 1)));
+        assert!(file.is_suppressed(&target("NeedBraces", "", 7, 9)));
+        assert!(!file.is_suppressed(&target("NeedBraces", "", 7, 1)));
+    }
+
+    #[test]
+    fn test_file_suppressions_id_and_check_suffix() {
+        let xml = r#"<suppressions>
+    <suppress id="strictLength"/>
+    <suppress checks="MethodLengthCheck"/>
+</suppressions>"#;
+
+        let config = FileSuppressionsConfig::from_xml(xml);
+        let file = for_file(&config, "A.java", "class A {}");
+
+        let mut with_id = target("LineLength", "", 1, 1);
+        assert!(!file.is_suppressed(&with_id));
+        with_id.id = Some("strictLength");
+        assert!(file.is_suppressed(&with_id));
+
+        assert!(file.is_suppressed(&target("MethodLength", "", 1, 1)));
+    }
+
+    #[test]
+    fn test_file_suppressions_malformed_entries_are_reported() {
+        let xml = r#"<suppressions>
+    <suppress files="[invalid(" checks="."/>
+    <suppress files="A\.java"/>
+    <suppress files="B\.java" check="LineLength"/>
+    <suppress checks="LineLength" lines="10-5"/>
+    <suppress-xpath checks="." query="//LITERAL_IF"/>
+    <suppress files="C\.java" checks="."/>
+</suppressions>"#;
+
+        let (config, errors) = FileSuppressionsConfig::parse(xml);
+        assert_eq!(config.len(), 1);
+        let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![2, 3, 4, 5, 6]);
+        assert!(errors[0].message.contains("invalid files pattern"));
+        assert!(errors[1].message.contains("at least one of"));
+        assert!(errors[2].message.contains("unknown attribute 'check'"));
+        assert!(errors[3].message.contains("invalid range"));
+        assert!(errors[4].message.contains("unsupported token 'LITERAL_IF'"));
+    }
+
+    #[test]
+    fn test_file_suppressions_invalid_xml() {
+        let (_, errors) = FileSuppressionsConfig::parse("<suppressions>\n<suppress checks=\".\"\n");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.starts_with("invalid XML"));
+    }
+
+    #[test]
+    fn test_file_suppressions_xpath_query() {
+        let xml = r#"<suppressions>
+    <suppress-xpath files="Foo\.java" checks="MagicNumber"
+        query="/COMPILATION_UNIT/CLASS_DEF[./IDENT[@text='Foo']]/OBJBLOCK/METHOD_DEF[./IDENT[@text='bar']]"/>
+    <suppress-xpath checks="FinalParameters" query="//PARAMETER_DEF[./IDENT[@text='ignored']]"/>
+    <suppress-xpath checks="FinalParameters"
+        query="/COMPILATION_UNIT/CLASS_DEF[./IDENT[@text='Foo']]/OBJBLOCK/METHOD_DEF[./IDENT[@text='qux']]/PARAMETERS/PARAMETER_DEF[./IDENT[@text='p']]"/>
+</suppressions>"#;
+        let source = "class Foo {\n    void bar(int ignored) { int x = 42; }\n    void baz(int kept) { int y = 42; }\n    void qux(int p, int q) {}\n}\n";
+
+        let (config, errors) = FileSuppressionsConfig::parse(xml);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert!(!config.is_suppressed("Foo.java", "MagicNumber"));
+
+        let file = for_file(&config, "Foo.java", source);
+        let at = |rule, needle: &str| SuppressionTarget {
+            offset: TextSize::new(u32::try_from(source.find(needle).unwrap()).unwrap()),
+            ..target(rule, "", 1, 1)
+        };
+        assert!(file.is_suppressed(&at("MagicNumber", "42; }\n    void baz")));
+        assert!(!file.is_suppressed(&at("MagicNumber", "42; }\n    void qux")));
+        assert!(file.is_suppressed(&at("FinalParameters", "int ignored")));
+        assert!(!file.is_suppressed(&at("FinalParameters", "int kept")));
+        assert!(file.is_suppressed(&at("FinalParameters", "int p")));
+        assert!(!file.is_suppressed(&at("FinalParameters", "int q")));
+
+        let other = for_file(&config, "Other.java", source);
+        assert!(!other.is_suppressed(&at("MagicNumber", "42; }\n    void baz")));
+    }
+
+    fn nearby(source: &str, filter: NearbyCommentFilterConfig) -> SuppressionContext {
+        let mut parser = lintal_java_parser::JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let root = CstNode::new(result.tree.root_node(), source);
+        let mut ctx = SuppressionContext::new();
+        ctx.parse_nearby_comments(source, &root, &[filter]);
+        ctx
+    }
+
+    #[test]
+    fn test_nearby_comment_default_format() {
+        let source =
+            "class A {\n    int x = 42; // SUPPRESS CHECKSTYLE MagicNumber\n    int y = 43;\n}\n";
+        let ctx = nearby(
+            source,
+            NearbyCommentFilterConfig::new(None, Some("$1"), None, None, None).unwrap(),
+        );
+
+        assert!(ctx.has_nearby_suppressions());
+        assert!(ctx.is_suppressed_nearby(&target("MagicNumber", "", 2, 13)));
+        assert!(!ctx.is_suppressed_nearby(&target("MagicNumber", "", 3, 13)));
+        assert!(!ctx.is_suppressed_nearby(&target("LineLength", "", 2, 1)));
+    }
+
+    #[test]
+    fn test_nearby_comment_positive_and_negative_influence() {
+        let source = "class A {\n    int a = 1;\n    // ALLOW MagicNumber FOR 2 LINES\n    int b = 2;\n    int c = 3;\n    int d = 4;\n    /* ALLOW MagicNumber FOR -2 LINES */\n}\n";
+        let ctx = nearby(
+            source,
+            NearbyCommentFilterConfig::new(
+                Some(r"ALLOW (\w+) FOR ([+-]?\d+) LINES"),
+                Some("$1"),
+                None,
+                None,
+                Some("$2"),
+            )
+            .unwrap(),
+        );
+
+        let suppressed: Vec<usize> = (1..=8)
+            .filter(|&line| ctx.is_suppressed_nearby(&target("MagicNumber", "", line, 1)))
+            .collect();
+        assert_eq!(suppressed, vec![3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_nearby_comment_message_and_id_formats() {
+        let source = "class A {\n    int x = 42; // SUPPRESS magic:42 as strict\n}\n";
+        let ctx = nearby(
+            source,
+            NearbyCommentFilterConfig::new(
+                Some(r"SUPPRESS (\w+):(\d+) as (\w+)"),
+                Some(".*"),
+                Some("'$2'"),
+                Some("$3"),
+                None,
+            )
+            .unwrap(),
+        );
+
+        let mut violation = target("MagicNumber", "'42' is a magic number.", 2, 13);
+        assert!(!ctx.is_suppressed_nearby(&violation));
+        violation.id = Some("strict");
+        assert!(ctx.is_suppressed_nearby(&violation));
+        violation.message = "'43' is a magic number.";
+        assert!(!ctx.is_suppressed_nearby(&violation));
+    }
+
+    #[test]
+    fn test_nearby_comment_style_toggles() {
+        let source = "class A {\n    int x = 42; /* SUPPRESS CHECKSTYLE MagicNumber */\n}\n";
+        let mut filter =
+            NearbyCommentFilterConfig::new(None, Some("$1"), None, None, None).unwrap();
+        filter.check_c = false;
+        let ctx = nearby(source, filter);
+        assert!(!ctx.has_nearby_suppressions());
+    }
+
+    fn apply_deletions(source: &str, unused: &[UnusedSuppression]) -> String {
+        let mut deletions: Vec<TextRange> = unused
+            .iter()
+            .flat_map(|u| u.source.deletions.iter().copied())
+            .collect();
+        deletions.sort_by_key(|range| std::cmp::Reverse(range.start()));
+        let mut result = source.to_string();
+        for range in deletions {
+            result.replace_range(std::ops::Range::<usize>::from(range), "");
+        }
+        result
+    }
+
+    #[test]
+    fn test_unused_comment_regions() {
+        let source = "class A {\n    // CHECKSTYLE:OFF:MagicNumber\n    int x = 42;\n    // CHECKSTYLE:ON:MagicNumber\n    int y = 1; // CHECKSTYLE:OFF:LineLength\n    // CHECKSTYLE:OFF:JavadocMethod\n}\n";
+        let ctx = SuppressionContext::from_source(
+            source,
+            &[PlainTextCommentFilterConfig::checkstyle_default()],
+        );
+        let coverage = RuleCoverage::new(["MagicNumber", "LineLength"], ["JavadocMethod"]);
+
+        let unused = ctx.unused_suppressions(&coverage);
+        assert_eq!(unused.len(), 2);
+        assert_eq!(unused[0].suppressed, "MagicNumber");
+        assert_eq!(unused[1].suppressed, "LineLength");
+        assert_eq!(
+            apply_deletions(source, &unused),
+            "class A {\n    int x = 42;\n    int y = 1;\n    // CHECKSTYLE:OFF:JavadocMethod\n}\n"
+        );
+
+        // A region that suppressed a diagnostic is no longer reported.
+        let offset = TextSize::new(u32::try_from(source.find("42").unwrap()).unwrap());
+        assert!(ctx.is_suppressed("MagicNumber", offset));
+        let unused = ctx.unused_suppressions(&coverage);
+        assert_eq!(unused.len(), 1);
+        assert_eq!(unused[0].suppressed, "LineLength");
+    }
+
+    #[test]
+    fn test_unused_suppress_warnings_values() {
+        let source = "class A {\n    @SuppressWarnings({\"checkstyle:MagicNumber\", \"unchecked\"})\n    void f() {}\n\n    @SuppressWarnings(\"checkstyle:MagicNumber\")\n    void g() {}\n}\n";
+        let mut parser = lintal_java_parser::JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let root = CstNode::new(result.tree.root_node(), source);
+        let mut ctx = SuppressionContext::new();
+        ctx.parse_suppress_warnings(source, &root);
+
+        let unused =
+            ctx.unused_suppressions(&RuleCoverage::new(["MagicNumber"], Vec::<&str>::new()));
+        assert_eq!(unused.len(), 2);
+        assert_eq!(
+            apply_deletions(source, &unused),
+            "class A {\n    @SuppressWarnings({\"unchecked\"})\n    void f() {}\n\n    void g() {}\n}\n"
+        );
+
+        let diagnostic = unused[0].clone().into_diagnostic();
+        assert_eq!(diagnostic.kind.code, "UnusedSuppressionViolation");
+        assert_eq!(diagnostic.kind.body, "Unused suppression for `MagicNumber`");
+        assert!(diagnostic.fixable());
+    }
+
+    #[test]
+    fn test_unused_nearby_comment() {
+        let source = "class A {\n    int x = 42; // SUPPRESS CHECKSTYLE MagicNumber\n    int y = 43; // SUPPRESS CHECKSTYLE MagicNumber\n}\n";
+        let ctx = nearby(
+            source,
+            NearbyCommentFilterConfig::new(None, Some("$1"), None, None, None).unwrap(),
+        );
+        assert!(ctx.is_suppressed_nearby(&target("MagicNumber", "", 2, 13)));
+
+        let unused =
+            ctx.unused_suppressions(&RuleCoverage::new(["MagicNumber"], Vec::<&str>::new()));
+        assert_eq!(unused.len(), 1);
+        assert_eq!(
+            apply_deletions(source, &unused),
+            "class A {\n    int x = 42; // SUPPRESS CHECKSTYLE MagicNumber\n    int y = 43;\n}\n"
+        );
+
+        // Suppressions of rules lintal does not run cannot be judged.
+        let coverage = RuleCoverage::new(["LineLength"], ["MagicNumber"]);
+        assert!(ctx.unused_suppressions(&coverage).is_empty());
+    }
+
+    #[test]
+    fn test_unused_file_suppressions() {
+        let xml = r#"<suppressions>
+    <suppress files="A\.java" checks="LineLength"/>
+    <suppress files="A\.java" checks="MagicNumber"/>
+    <suppress files="B\.java" checks="MagicNumber"/>
+    <suppress files="A\.java" checks="JavadocMethod"/>
+</suppressions>"#;
+        let config = FileSuppressionsConfig::from_xml(xml);
+        let file = for_file(&config, "src/A.java", "class A {}");
+        assert!(file.is_suppressed(&target("LineLength", "", 1, 1)));
+
+        let coverage = RuleCoverage::new(["LineLength", "MagicNumber"], ["JavadocMethod"]);
+        let unused = config.unused(&coverage);
+        // B.java was never checked, and JavadocMethod is not run by lintal.
+        assert_eq!(unused.len(), 1);
+        assert_eq!(unused[0].suppressed, "MagicNumber");
+        assert_eq!(
+            &xml[std::ops::Range::<usize>::from(unused[0].source.range)],
+            r#"<suppress files="A\.java" checks="MagicNumber"/>"#
+        );
+    }
 }
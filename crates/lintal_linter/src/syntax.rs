@@ -0,0 +1,151 @@
+//! Built-in syntax error reporting.
+//!
+//! tree-sitter always produces a tree, recovering from parse errors by
+//! inserting `ERROR` nodes (unexpected input) and zero-width `MISSING` nodes
+//! (expected tokens that were absent). These are reported as `SyntaxError`
+//! diagnostics so that broken files are surfaced rather than silently
+//! producing confusing violations from other rules.
+
+use lintal_diagnostics::{Diagnostic, Violation};
+use lintal_java_cst::{CstNode, node_range};
+
+/// Name under which syntax errors are reported.
+pub const SYNTAX_ERROR: &str = "SyntaxError";
+
+/// Maximum number of characters of an unexpected token shown in messages.
+const MAX_TOKEN_LEN: usize = 30;
+
+/// Violation: the source is not valid Java.
+#[derive(Debug, Clone)]
+pub struct SyntaxErrorViolation {
+    message: String,
+}
+
+impl Violation for SyntaxErrorViolation {
+    fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+/// Returns true if the tree rooted at `root` contains any syntax errors.
+pub fn has_syntax_errors(root: &CstNode) -> bool {
+    root.inner().has_error()
+}
+
+/// Collect a diagnostic for every `ERROR` and `MISSING` node under `root`.
+///
+/// `ERROR` nodes are not descended into, so each region of unparseable input
+/// is reported once.
+pub fn syntax_errors(root: &CstNode, source: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    if !has_syntax_errors(root) {
+        return diagnostics;
+    }
+
+    let mut stack = vec![root.inner()];
+    while let Some(node) = stack.pop() {
+        if node.is_missing() {
+            let expected = if node.is_named() {
+                node.kind().to_string()
+            } else {
+                format!("`{}`", node.kind())
+            };
+            diagnostics.push(Diagnostic::new(
+                SyntaxErrorViolation {
+                    message: format!("expected {expected}"),
+                },
+                node_range(&node),
+            ));
+        } else if node.is_error() {
+            diagnostics.push(unexpected(node, source));
+        } else if node.has_error() {
+            // Push in reverse so errors are reported in source order.
+            let mut cursor = node.walk();
+            let children: Vec<_> = node.children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
+        }
+    }
+
+    diagnostics
+}
+
+/// Build the diagnostic for an `ERROR` node, naming its first token.
+fn unexpected(node: tree_sitter::Node, source: &str) -> Diagnostic {
+    let mut token = node;
+    while let Some(child) = token.child(0) {
+        token = child;
+    }
+
+    let text = source[token.byte_range()].trim();
+    let message = if text.is_empty() {
+        if usize::from(node_range(&node).end()) >= source.trim_end().len() {
+            "unexpected end of file".to_string()
+        } else {
+            "unexpected input".to_string()
+        }
+    } else {
+        let text = text.lines().next().unwrap_or(text);
+        if text.chars().count() > MAX_TOKEN_LEN {
+            let truncated: String = text.chars().take(MAX_TOKEN_LEN).collect();
+            format!("unexpected token `{truncated}...`")
+        } else {
+            format!("unexpected token `{text}`")
+        }
+    };
+
+    // Point at the offending token rather than the whole recovered region.
+    let range = if token.byte_range().is_empty() {
+        node_range(&node)
+    } else {
+        node_range(&token)
+    };
+
+    Diagnostic::new(SyntaxErrorViolation { message }, range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_parser::JavaParser;
+
+    fn messages(source: &str) -> Vec<String> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let root = CstNode::new(result.tree.root_node(), source);
+        syntax_errors(&root, source)
+            .into_iter()
+            .map(|d| d.kind.body)
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_source_has_no_errors() {
+        assert!(messages("class A { void f() { int x = 1; } }").is_empty());
+    }
+
+    #[test]
+    fn test_missing_semicolon() {
+        assert_eq!(
+            messages("class A { void f() { int x = 1 } }"),
+            vec!["expected `;`"]
+        );
+    }
+
+    #[test]
+    fn test_unexpected_token() {
+        let messages = messages("class A { void f() { int x = 1;; ) } }");
+        assert_eq!(messages, vec!["unexpected token `)`"]);
+    }
+
+    #[test]
+    fn test_diagnostic_code() {
+        let source = "class A { int x = 1 }";
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let root = CstNode::new(result.tree.root_node(), source);
+        assert!(has_syntax_errors(&root));
+        let diagnostics = syntax_errors(&root, source);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind.code, "SyntaxErrorViolation");
+    }
+}
@@ -0,0 +1,323 @@
+//! A subset of checkstyle's XPath queries, evaluated over the tree-sitter CST.
+//!
+//! `<suppress-xpath>` queries address checkstyle's own AST, which does not
+//! match tree-sitter's node structure. Only declaration steps are supported,
+//! optionally filtered by name, which covers the queries checkstyle generates
+//! for classes, methods, fields and parameters:
+//!
+//! ```text
+//! /COMPILATION_UNIT/CLASS_DEF[./IDENT[@text='Foo']]/OBJBLOCK/METHOD_DEF[./IDENT[@text='bar']]
+//! /COMPILATION_UNIT/CLASS_DEF/OBJBLOCK/METHOD_DEF/PARAMETERS/PARAMETER_DEF[./IDENT[@text='p']]
+//! //VARIABLE_DEF[./IDENT[@text='count']] | //PARAMETER_DEF
+//! ```
+//!
+//! CST nodes that have no checkstyle counterpart are skipped over, so a child
+//! step (`/`) matches the nearest supported descendant. A violation is
+//! suppressed if it lies anywhere inside a matched node.
+
+use std::fmt;
+
+use lintal_java_cst::node_range;
+use lintal_text_size::TextRange;
+use tree_sitter::Node;
+
+/// Checkstyle token types that can appear in a supported query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    CompilationUnit,
+    ClassDef,
+    InterfaceDef,
+    EnumDef,
+    RecordDef,
+    AnnotationDef,
+    MethodDef,
+    CtorDef,
+    VariableDef,
+    Parameters,
+    ParameterDef,
+    EnumConstantDef,
+    ObjBlock,
+    Slist,
+}
+
+impl Token {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "COMPILATION_UNIT" => Token::CompilationUnit,
+            "CLASS_DEF" => Token::ClassDef,
+            "INTERFACE_DEF" => Token::InterfaceDef,
+            "ENUM_DEF" => Token::EnumDef,
+            "RECORD_DEF" => Token::RecordDef,
+            "ANNOTATION_DEF" => Token::AnnotationDef,
+            "METHOD_DEF" => Token::MethodDef,
+            "CTOR_DEF" => Token::CtorDef,
+            "VARIABLE_DEF" => Token::VariableDef,
+            "PARAMETERS" => Token::Parameters,
+            "PARAMETER_DEF" => Token::ParameterDef,
+            "ENUM_CONSTANT_DEF" => Token::EnumConstantDef,
+            "OBJBLOCK" => Token::ObjBlock,
+            "SLIST" => Token::Slist,
+            _ => return None,
+        })
+    }
+
+    /// The checkstyle token for a CST node kind, if it has one.
+    fn for_kind(kind: &str) -> Option<Self> {
+        Some(match kind {
+            "program" => Token::CompilationUnit,
+            "class_declaration" => Token::ClassDef,
+            "interface_declaration" => Token::InterfaceDef,
+            "enum_declaration" => Token::EnumDef,
+            "record_declaration" => Token::RecordDef,
+            "annotation_type_declaration" => Token::AnnotationDef,
+            "method_declaration" => Token::MethodDef,
+            "constructor_declaration" | "compact_constructor_declaration" => Token::CtorDef,
+            "variable_declarator" => Token::VariableDef,
+            "formal_parameters" => Token::Parameters,
+            "formal_parameter" | "spread_parameter" => Token::ParameterDef,
+            "enum_constant" => Token::EnumConstantDef,
+            "class_body" | "interface_body" | "enum_body" | "annotation_type_body" => {
+                Token::ObjBlock
+            }
+            "block" | "constructor_body" => Token::Slist,
+            _ => return None,
+        })
+    }
+}
+
+/// A single `/TOKEN[...]` or `//TOKEN[...]` step.
+#[derive(Debug, Clone)]
+struct Step {
+    descendant: bool,
+    token: Token,
+    name: Option<String>,
+}
+
+/// A parsed query: a union of step paths.
+#[derive(Debug, Clone)]
+pub struct XpathQuery {
+    paths: Vec<Vec<Step>>,
+    source: String,
+}
+
+/// Error returned for queries outside the supported subset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedQuery(pub String);
+
+impl fmt::Display for UnsupportedQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl XpathQuery {
+    /// Parse a query, rejecting anything outside the supported subset.
+    pub fn parse(query: &str) -> Result<Self, UnsupportedQuery> {
+        let paths = split_union(query)
+            .into_iter()
+            .map(parse_path)
+            .collect::<Result<Vec<_>, _>>()?;
+        if paths.is_empty() {
+            return Err(UnsupportedQuery("empty query".to_string()));
+        }
+        Ok(Self {
+            paths,
+            source: query.to_string(),
+        })
+    }
+
+    /// The original query text.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Source ranges of all nodes matched by the query.
+    pub fn matching_ranges(&self, root: Node, source: &str) -> Vec<TextRange> {
+        let mut ranges = Vec::new();
+        for path in &self.paths {
+            // The document node sits above the root `program` node.
+            let mut context: Vec<Option<Node>> = vec![None];
+            for step in path {
+                let mut next = Vec::new();
+                for node in &context {
+                    let candidates = match node {
+                        None if step.descendant => {
+                            let mut all = vec![root];
+                            mapped_descendants(root, &mut all);
+                            all
+                        }
+                        None => vec![root],
+                        Some(node) if step.descendant => {
+                            let mut all = Vec::new();
+                            mapped_descendants(*node, &mut all);
+                            all
+                        }
+                        Some(node) => {
+                            let mut children = Vec::new();
+                            mapped_children(*node, &mut children);
+                            children
+                        }
+                    };
+                    for candidate in candidates {
+                        if step.matches(candidate, source) && !next.contains(&Some(candidate)) {
+                            next.push(Some(candidate));
+                        }
+                    }
+                }
+                context = next;
+            }
+            ranges.extend(context.into_iter().flatten().map(suppressed_range));
+        }
+        ranges
+    }
+}
+
+impl Step {
+    fn matches(&self, node: Node, source: &str) -> bool {
+        if Token::for_kind(node.kind()) != Some(self.token) {
+            return false;
+        }
+        let Some(expected) = &self.name else {
+            return true;
+        };
+        node.child_by_field_name("name")
+            .is_some_and(|name| source.get(name.byte_range()) == Some(expected.as_str()))
+    }
+}
+
+/// Collect the nearest descendants of `node` that map to a checkstyle token.
+fn mapped_children<'a>(node: Node<'a>, out: &mut Vec<Node<'a>>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if Token::for_kind(child.kind()).is_some() {
+            out.push(child);
+        } else {
+            mapped_children(child, out);
+        }
+    }
+}
+
+/// Collect all descendants of `node` that map to a checkstyle token.
+fn mapped_descendants<'a>(node: Node<'a>, out: &mut Vec<Node<'a>>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if Token::for_kind(child.kind()).is_some() {
+            out.push(child);
+        }
+        mapped_descendants(child, out);
+    }
+}
+
+/// The range suppressed by a matched node. Variables cover their whole
+/// declaration so that violations on the type or modifiers are included.
+fn suppressed_range(node: Node) -> TextRange {
+    if node.kind() == "variable_declarator"
+        && let Some(parent) = node.parent()
+        && matches!(
+            parent.kind(),
+            "field_declaration" | "local_variable_declaration" | "constant_declaration"
+        )
+    {
+        return node_range(&parent);
+    }
+    node_range(&node)
+}
+
+/// Split a query on top-level `|`.
+fn split_union(query: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in query.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '[') => depth += 1,
+            (None, ']') => depth = depth.saturating_sub(1),
+            (None, '|') if depth == 0 => {
+                parts.push(query[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(query[start..].trim());
+    parts.retain(|p| !p.is_empty());
+    parts
+}
+
+/// Parse a single path of steps.
+fn parse_path(path: &str) -> Result<Vec<Step>, UnsupportedQuery> {
+    let mut steps = Vec::new();
+    let mut rest = path;
+    while !rest.is_empty() {
+        let (descendant, after) = if let Some(after) = rest.strip_prefix("//") {
+            (true, after)
+        } else if let Some(after) = rest.strip_prefix('/') {
+            (false, after)
+        } else {
+            return Err(UnsupportedQuery(format!("expected '/' at '{rest}'")));
+        };
+
+        let name_end = after.find(['/', '[']).unwrap_or(after.len());
+        let token_name = &after[..name_end];
+        let token = Token::from_name(token_name)
+            .ok_or_else(|| UnsupportedQuery(format!("unsupported token '{token_name}'")))?;
+        rest = &after[name_end..];
+
+        let mut name = None;
+        while let Some(predicate) = rest.strip_prefix('[') {
+            let end = predicate_end(predicate)
+                .ok_or_else(|| UnsupportedQuery("unterminated predicate".to_string()))?;
+            let body = &predicate[..end];
+            name =
+                Some(parse_name_predicate(body).ok_or_else(|| {
+                    UnsupportedQuery(format!("unsupported predicate '[{body}]'"))
+                })?);
+            rest = &predicate[end + 1..];
+        }
+
+        steps.push(Step {
+            descendant,
+            token,
+            name,
+        });
+    }
+    Ok(steps)
+}
+
+/// Offset of the `]` closing a predicate whose `[` has been consumed.
+fn predicate_end(predicate: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote = None;
+    for (i, c) in predicate.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '[') => depth += 1,
+            (None, ']') if depth == 0 => return Some(i),
+            (None, ']') => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse `./IDENT[@text='name']`, returning `name`.
+fn parse_name_predicate(body: &str) -> Option<String> {
+    let inner = body
+        .trim()
+        .strip_prefix("./IDENT[")?
+        .strip_suffix(']')?
+        .trim()
+        .strip_prefix("@text")?
+        .trim_start()
+        .strip_prefix('=')?
+        .trim();
+    let quote = inner.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+    let value = inner.strip_prefix(quote)?.strip_suffix(quote)?;
+    Some(value.to_string())
+}
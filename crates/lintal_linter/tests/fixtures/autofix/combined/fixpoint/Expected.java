@@ -0,0 +1,15 @@
+import java.util.List;
+
+public class Input {
+    interface Constants {
+        int LIMIT = 10;
+    }
+
+    private List<String> names;
+
+    public int sum(int count, int step) {
+        int total = 0;
+        for (int i = 0; i < count; i++) { total += step; }
+        return total;
+    }
+}
@@ -0,0 +1,16 @@
+import java.util.List;
+import java.util.List;
+
+public class Input {
+    interface Constants {
+        final static public int LIMIT=10;
+    }
+
+    private List<String> names;
+
+    public int sum( int count,int step ) {
+        int total=0;
+        for(int i=0;i<count;i++){total+=step;}
+        return total;
+    }
+}
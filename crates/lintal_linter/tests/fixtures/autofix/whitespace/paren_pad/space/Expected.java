@@ -1,6 +1,6 @@
 public class Input {
     public void method( int x ) {
-        if (  x > 0  ) {
+        if ( x > 0 ) {
             System.out.println( x );
         }
         for ( int i = 0; i < 10; i++ ) {